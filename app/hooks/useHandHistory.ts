@@ -56,8 +56,8 @@ function readI64LE(data: Uint8Array, offset: number): number {
 }
 
 // Parse HandCompleted event from raw buffer (binary deserialization)
-// Layout: table_id[32] + hand_number[8] + timestamp[8] + community_cards[5] + total_pot[8] + player_count[1] + results[6*PlayerHandResult] + results_count[1]
-// PlayerHandResult: player[32] + seat_index[1] + hole_card_1[1] + hole_card_2[1] + hand_rank[1] + chips_won[8] + chips_bet[8] + folded[1] + all_in[1]
+// Layout: table_id[32] + hand_number[8] + timestamp[8] + community_cards[5] + total_pot[8] + player_count[1] + results[8*PlayerHandResult] + results_count[1]
+// PlayerHandResult: player[32] + seat_index[1] + hole_card_1[1] + hole_card_2[1] + hand_rank[1] + chips_won[8] + chips_bet[8] + folded[1] + all_in[1] + net[8]
 function parseEventFromBuffer(data: Uint8Array, signature: string): HandHistoryEntry | null {
   try {
     let offset = 0;
@@ -96,13 +96,14 @@ function parseEventFromBuffer(data: Uint8Array, signature: string): HandHistoryE
 
     console.log("[HandHistory] Parsed header:", { handNumber, timestamp, communityCards, totalPot, playerCount, currentOffset: offset });
 
-    // results: [PlayerHandResult; 6]
-    // PlayerHandResult size: 32 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 1 = 54 bytes
-    const PLAYER_RESULT_SIZE = 54;
+    // results: [PlayerHandResult; 8] (one per seat bitmap bit)
+    // PlayerHandResult size: 32 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 1 + 8 = 62 bytes
+    const PLAYER_RESULT_SIZE = 62;
+    const RESULT_SLOTS = 8;
     const players: PlayerResult[] = [];
 
     // results_count is at the end, read it first
-    const resultsCountOffset = offset + (6 * PLAYER_RESULT_SIZE);
+    const resultsCountOffset = offset + (RESULT_SLOTS * PLAYER_RESULT_SIZE);
     const resultsCount = data[resultsCountOffset];
 
     console.log("[HandHistory] resultsCountOffset:", resultsCountOffset, "resultsCount:", resultsCount, "playerCount:", playerCount);
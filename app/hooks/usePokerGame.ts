@@ -78,7 +78,7 @@ export interface PlayerSeatAccount {
   revealedCard1: number;  // Revealed plaintext card (0-51 or 255)
   revealedCard2: number;  // Revealed plaintext card (0-51 or 255)
   cardsRevealed: boolean; // Whether player has revealed cards for showdown
-  status: { sitting?: object; playing?: object; folded?: object; allIn?: object };
+  status: { sitting?: object; playing?: object; folded?: object; allIn?: object; away?: object };
   hasActed: boolean;
   bump: number;
 }
@@ -116,7 +116,7 @@ export interface GameState {
   deckState: DeckStateAccount | null;
   players: Player[];
   phase: "Dealing" | "PreFlop" | "Flop" | "Turn" | "River" | "Showdown" | "Settled";
-  tableStatus: "Waiting" | "Playing" | "Closed";
+  tableStatus: "Waiting" | "Playing" | "Closed" | "Paused";
   pot: number;
   currentBet: number;
   minRaise: number;
@@ -712,7 +712,7 @@ export function usePokerGame(): UsePokerGameResult {
 
         // Step 1: Join the table on base layer
         const tx = await program.methods
-          .joinTable(seatIndex, new BN(buyInLamports))
+          .joinTable(seatIndex, new BN(buyInLamports), [])
           .accounts({
             player: publicKey,
             table: gameState.tablePDA,
@@ -762,6 +762,7 @@ export function usePokerGame(): UsePokerGameResult {
           table: gameState.tablePDA,
           playerSeat: seatPDA,
           vault: vaultPDA,
+          handState: null,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -2095,6 +2096,8 @@ export function usePokerGame(): UsePokerGameResult {
           table: gameState.tablePDA,
           handState: handPDA,
           vault: vaultPDA,
+          handHistory: null,
+          tableStats: null,
         })
         .remainingAccounts(remainingAccounts)
         .rpc();
@@ -41,7 +41,7 @@ export async function waitForShuffle(
         // 1 byte deal_index
         // 1 byte is_shuffled <-- VRF callback sets THIS when complete
         // 1 byte bump
-        // 33 bytes _reserved
+        // 32 bytes seed_commitment, 1 byte burn_cards
         // is_shuffled is at offset 8 + 32 + 832 + 1 = 873
         const IS_SHUFFLED_OFFSET = 8 + 32 + (52 * 16) + 1;
         const isShuffled = accountInfo.data[IS_SHUFFLED_OFFSET] === 1;
@@ -91,9 +91,10 @@ export function formatAddress(address: string, chars: number = 4): string {
  * Map PlayerStatus enum to string
  */
 export function mapPlayerStatus(
-  status: { sitting?: object; playing?: object; folded?: object; allIn?: object }
+  status: { sitting?: object; playing?: object; folded?: object; allIn?: object; away?: object }
 ): "sitting" | "playing" | "folded" | "allin" | "empty" {
   if ("sitting" in status) return "sitting";
+  if ("away" in status) return "sitting";
   if ("playing" in status) return "playing";
   if ("folded" in status) return "folded";
   if ("allIn" in status) return "allin";
@@ -120,11 +121,12 @@ export function mapGamePhase(
  * Map TableStatus enum to string
  */
 export function mapTableStatus(
-  status: { waiting?: object; playing?: object; closed?: object }
-): "Waiting" | "Playing" | "Closed" {
+  status: { waiting?: object; playing?: object; closed?: object; paused?: object }
+): "Waiting" | "Playing" | "Closed" | "Paused" {
   if ("waiting" in status) return "Waiting";
   if ("playing" in status) return "Playing";
   if ("closed" in status) return "Closed";
+  if ("paused" in status) return "Paused";
   return "Waiting";
 }
 
@@ -172,6 +174,9 @@ export function parseAnchorError(error: unknown, context?: {
       }
       return "Buy-in amount is outside the allowed range for this table";
     },
+    "BigBlindBelowSmallBlind": "Big blind must be at least the small blind.",
+    "MinBuyInAboveMax": "Minimum buy-in cannot be more than the maximum buy-in.",
+    "MaxBuyInBelowBigBlind": "Maximum buy-in must cover at least one big blind.",
     "TableFull": "This table is full. Please try another table.",
     "NotEnoughPlayers": "Need at least 2 players to start a hand.",
     "PlayerNotAtTable": "You are not seated at this table.",
@@ -187,6 +192,7 @@ export function parseAnchorError(error: unknown, context?: {
     "CannotFold": "You cannot fold right now.",
     "CannotCheck": "You cannot check - there's a bet to call.",
     "RaiseTooSmall": "Raise amount is too small.",
+    "RaiseNotReopened": "The last all-in wasn't a full raise - you can only call or fold.",
     "BettingRoundNotComplete": "The betting round is not complete yet.",
     "InvalidPhase": "This action is not valid in the current game phase.",
     "ActionTimeout": "Action timed out.",
@@ -196,13 +202,37 @@ export function parseAnchorError(error: unknown, context?: {
     "DeckAlreadyShuffled": "Cards have already been shuffled.",
     "DeckNotShuffled": "Cards have not been shuffled yet.",
     "ActionNotTimedOut": "Player hasn't timed out yet - wait 60 seconds.",
+    "RevealNotTimedOut": "Player hasn't timed out on reveal yet - wait 3 minutes.",
+    "TableNotExpired": "This table can't be archived yet.",
+    "TableNotEmpty": "Players are still seated at this table.",
+    "MissingSeatAccounts": "Every player still in the hand must be included in showdown.",
+    "PlayerNotAway": "You are not sitting out.",
+    "RaiseCapReached": "Betting is capped for this street - you can only call or fold.",
+    "FixedLimitBetSize": "This is a fixed-limit table - bets and raises must be exactly the street's bet size.",
+    "StaleHand": "That hand is over - refresh to see the current hand.",
+    "BlindSeatMismatch": "Wrong blind seats for this hand - refresh and deal again.",
+    "IncoReturnDataMalformed": "Card encryption service returned unexpected data. Please try again later.",
+    "TableStatsMismatch": "Table stats account mismatch - refresh and try again.",
+    "NoHandShown": "You can only muck after another player has shown their hand.",
+    "AlreadySettled": "This hand has already been paid out.",
+    "InvalidDeck": "The shuffle produced an invalid deck. Please request a new shuffle.",
+    "NotAllowlisted": "This is a private table - you're not on its guest list.",
+    "TablePaused": "The table is paused by its host. Hands will resume shortly.",
+    "TableNotPaused": "The table isn't paused.",
+    "InsufficientVaultBalance": "The table's vault can't cover every stack. Nothing was paid out - please contact support.",
+    "RevealOutOfOrder": "It's not your turn to show yet - hands are revealed in order.",
+    "StackCapWalletMissing": "A stack went over the table cap - include every player's wallet when settling the hand.",
+    "GameTypeUnsupported": "That game type isn't playable yet.",
+    "TableNotInactive": "Table hasn't been inactive long enough to close - wait 1 hour.",
+    "DealNotTimedOut": "Only the table creator can deal yet - wait 30 seconds.",
+    "ShowdownNotTimedOut": "Only the table creator can call showdown yet - wait 60 seconds.",
     "CardsNotDealt": "Cards have not been dealt yet.",
     "AllCardsRevealed": "All community cards are already revealed.",
     "PlayerFolded": "You have already folded.",
     "PlayerAlreadyAllIn": "You are already all-in.",
     "TableNotWaiting": "Table is not in waiting state.",
     "CannotLeaveDuringHand": "You cannot leave during an active hand.",
-    "Overflow": "Transaction calculation error.",
+    "ArithmeticOverflow": "Transaction calculation error.",
     "DuplicateAccount": "Duplicate account error.",
     "InvalidRemainingAccounts": "Invalid accounts provided.",
   };
@@ -4,13 +4,23 @@ pub const SEAT_SEED: &[u8] = b"seat";
 pub const HAND_SEED: &[u8] = b"hand";
 pub const DECK_SEED: &[u8] = b"deck";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const HISTORY_SEED: &[u8] = b"history";
+pub const STATS_SEED: &[u8] = b"stats";
 
 // Game Constants
 pub const MAX_PLAYERS: u8 = 6;
+pub const SEAT_BITMAP_SIZE: usize = 8; // Seats a u8 seat bitmap can address - sizes per-seat event arrays
 pub const MIN_PLAYERS: u8 = 2;
 pub const DECK_SIZE: usize = 52;
 pub const HOLE_CARDS: usize = 2;
-pub const COMMUNITY_CARDS: usize = 5;
+pub const COMMUNITY_CARD_COUNT: usize = 5;
+pub const CARD_SENTINEL: u8 = 255; // Card not dealt / not revealed - never a real card (0-51)
+pub const NO_SEAT: u8 = 255; // No seat recorded (e.g. nobody has bet this street) - never a real seat index
+pub const BURN_CARD_COUNT: usize = 3; // One before each of flop, turn and river, on tables that burn
+pub const MAX_WAITLIST: usize = 8; // Players queued for a seat at a full table
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 16; // Merkle proof depth for an allowlist of up to 65536 players
+pub const MIN_HAND_GAP_SLOTS: u64 = 2; // Slots between hand starts, so hands can't be churned back to back
+pub const AUTO_SIT_OUT_TIMEOUTS: u8 = 3; // Timeout auto-folds in a row before a player is sat out
 
 // Timeouts (in seconds - works consistently across all environments including MagicBlock ER)
 pub const ACTION_TIMEOUT_SECONDS: i64 = 60; // 60 seconds to act
@@ -20,5 +30,8 @@ pub const REVEAL_TIMEOUT_SECONDS: i64 = 180; // 3 minutes to reveal cards at sho
 pub const TABLE_INACTIVE_TIMEOUT_SECONDS: i64 = 3600; // 1 hour of inactivity to auto-close table
 pub const EMERGENCY_TIMEOUT_SECONDS: i64 = 86400; // 24 hours for emergency withdraw
 
+// Lifetimes (in slots, ~400ms each)
+pub const TABLE_MAX_LIFETIME_SLOTS: u64 = 6_480_000; // ~30 days before an empty table can be archived
+
 // Betting
 pub const MIN_RAISE_MULTIPLIER: u64 = 2; // Must raise at least 2x the current bet
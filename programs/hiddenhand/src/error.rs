@@ -96,7 +96,7 @@ pub enum HiddenHandError {
     CannotLeaveDuringHand,
 
     #[msg("Arithmetic overflow")]
-    Overflow,
+    ArithmeticOverflow,
 
     #[msg("Duplicate accounts provided")]
     DuplicateAccount,
@@ -142,4 +142,148 @@ pub enum HiddenHandError {
 
     #[msg("Invalid community cards for current phase")]
     InvalidCommunityCards,
+
+    #[msg("Revealed card duplicates a card already revealed this hand")]
+    DuplicateCardRevealed,
+
+    #[msg("Card encryption failed - Inco returned an uninitialized handle")]
+    EncryptionFailed,
+
+    #[msg("Table has open seats - join directly")]
+    TableHasOpenSeats,
+
+    #[msg("Waitlist is full")]
+    WaitlistFull,
+
+    #[msg("Player is already on the waitlist")]
+    AlreadyOnWaitlist,
+
+    #[msg("Hand history account must be passed if and only if the table records history")]
+    HandHistoryMismatch,
+
+    #[msg("Bet is smaller than the big blind")]
+    BetTooSmall,
+
+    #[msg("Player has not timed out on reveal yet - must wait 3 minutes")]
+    RevealNotTimedOut,
+
+    #[msg("Table has not been inactive long enough to close - must wait 1 hour")]
+    TableNotInactive,
+
+    #[msg("Only the authority can deal before the 30 second deal timeout")]
+    DealNotTimedOut,
+
+    #[msg("Only the authority can call showdown before the 60 second timeout")]
+    ShowdownNotTimedOut,
+
+    #[msg("Add-on limit reached for this seat")]
+    AddOnLimitReached,
+
+    #[msg("Add-ons are not available at this table yet")]
+    AddOnNotAvailable,
+
+    #[msg("Community cards for this street have already been revealed")]
+    CommunityAlreadyRevealed,
+
+    #[msg("Minimum players to start must be between 2 and the table's max players")]
+    InvalidMinPlayersToStart,
+
+    #[msg("This table has no time bank")]
+    TimeBankDisabled,
+
+    #[msg("Time bank already used this hand")]
+    TimeBankAlreadyUsed,
+
+    #[msg("Big blind must be at least the small blind")]
+    BigBlindBelowSmallBlind,
+
+    #[msg("Minimum buy-in cannot exceed the maximum buy-in")]
+    MinBuyInAboveMax,
+
+    #[msg("Maximum buy-in must be at least the big blind")]
+    MaxBuyInBelowBigBlind,
+
+    #[msg("Equity preview needs a full board game with one community card to come")]
+    EquityPreviewUnavailable,
+
+    #[msg("Pass the current hand and deck accounts while a hand is in progress")]
+    HandAccountsRequired,
+
+    #[msg("A hand was started too recently at this table")]
+    HandStartedTooSoon,
+
+    #[msg("Revealed cards do not match the cards dealt to this seat")]
+    RevealMismatch,
+
+    #[msg("This hand has no shuffle commitment to verify against")]
+    NoSeedCommitment,
+
+    #[msg("Revealed seed does not reproduce this hand's deal")]
+    FairnessCheckFailed,
+
+    #[msg("Betting was not reopened by a full raise - only call or fold")]
+    RaiseNotReopened,
+
+    #[msg("Table still has seated players")]
+    TableNotEmpty,
+
+    #[msg("Table has not reached its maximum lifetime yet")]
+    TableNotExpired,
+
+    #[msg("Seat investments do not add up to the pot")]
+    PotAccountingMismatch,
+
+    #[msg("Showdown is missing the seat account of a player still in the hand")]
+    MissingSeatAccounts,
+
+    #[msg("Player is not sitting out")]
+    PlayerNotAway,
+
+    #[msg("No more raises allowed this street")]
+    RaiseCapReached,
+
+    #[msg("Fixed-limit tables only allow bets and raises of the street's fixed size")]
+    FixedLimitBetSize,
+
+    #[msg("Hand account is not the table's current hand")]
+    StaleHand,
+
+    #[msg("Seat accounts passed as blinds are not this hand's blind seats")]
+    BlindSeatMismatch,
+
+    #[msg("Inco returned malformed data - possible Inco version mismatch")]
+    IncoReturnDataMalformed,
+
+    #[msg("Table stats account must be passed if and only if the table tracks stats")]
+    TableStatsMismatch,
+
+    #[msg("Can only muck once another player still in the hand has shown")]
+    NoHandShown,
+
+    #[msg("Hand has already been settled and paid out")]
+    AlreadySettled,
+
+    #[msg("Shuffled deck is not a permutation of 0-51")]
+    InvalidDeck,
+
+    #[msg("Player is not on this table's allowlist")]
+    NotAllowlisted,
+
+    #[msg("Table is paused by the authority")]
+    TablePaused,
+
+    #[msg("Table is not paused")]
+    TableNotPaused,
+
+    #[msg("Vault holds less than the chips owed to players")]
+    InsufficientVaultBalance,
+
+    #[msg("Not this seat's turn to show - hands are revealed in order")]
+    RevealOutOfOrder,
+
+    #[msg("Stack is over the table cap - pass the player's wallet to return the excess")]
+    StackCapWalletMissing,
+
+    #[msg("Game type has no hand evaluator yet and can't be played")]
+    GameTypeUnsupported,
 }
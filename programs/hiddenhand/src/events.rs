@@ -5,6 +5,8 @@
 
 use anchor_lang::prelude::*;
 
+use crate::constants::SEAT_BITMAP_SIZE;
+
 /// Emitted when a hand completes (showdown or everyone folds)
 /// Contains all information needed to reconstruct and verify the hand
 #[event]
@@ -27,12 +29,16 @@ pub struct HandCompleted {
     /// Number of players who participated
     pub player_count: u8,
 
-    /// Results for each player (up to 6)
+    /// Results for each player (one slot per seat the bitmaps can address)
     /// Using fixed array because Vec has variable size issues with events
-    pub results: [PlayerHandResult; 6],
+    pub results: [PlayerHandResult; SEAT_BITMAP_SIZE],
 
     /// How many results are valid (rest are zeroed)
     pub results_count: u8,
+
+    /// Phase the hand ended in (GamePhase as u8): Showdown, or Settled
+    /// when everyone else folded
+    pub final_phase: u8,
 }
 
 /// Individual player's result in a hand
@@ -41,7 +47,7 @@ pub struct PlayerHandResult {
     /// Player's wallet pubkey
     pub player: Pubkey,
 
-    /// Seat index (0-5)
+    /// Seat index (0-7)
     pub seat_index: u8,
 
     /// Hole cards (255 = not shown / folded)
@@ -62,4 +68,369 @@ pub struct PlayerHandResult {
 
     /// Whether player was all-in
     pub all_in: bool,
+
+    /// Chips won minus chips bet (negative if lost)
+    pub net: i64,
+}
+
+impl PlayerHandResult {
+    /// Record the seat's payout once the pot is awarded, along with its net
+    pub fn record_winnings(&mut self, chips_won: u64) {
+        self.chips_won = chips_won;
+        self.net = chips_won as i64 - self.chips_bet as i64;
+    }
+}
+
+/// Emitted when a table is created, so a lobby can list it without scanning accounts
+#[event]
+pub struct TableCreated {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Wallet that created and manages the table
+    pub authority: Pubkey,
+
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub max_players: u8,
+}
+
+/// Emitted when the authority changes a table's stakes between hands
+#[event]
+pub struct BlindsUpdated {
+    pub table_id: [u8; 32],
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub min_buy_in: u64,
+    pub max_buy_in: u64,
+}
+
+/// Emitted when a player takes a seat (join_table or join_table_auto)
+#[event]
+pub struct PlayerJoined {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Joining player's wallet
+    pub player: Pubkey,
+
+    /// Seat the player took
+    pub seat_index: u8,
+
+    /// Chips bought in with
+    pub buy_in: u64,
+}
+
+/// Emitted when a player cashes out through leave_table
+#[event]
+pub struct PlayerLeft {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Leaving player's wallet
+    pub player: Pubkey,
+
+    /// Seat the player left
+    pub seat_index: u8,
+
+    /// Chips returned to the player's wallet
+    pub chips_returned: u64,
+
+    /// Unix timestamp of the leave
+    pub timestamp: i64,
+}
+
+/// Emitted when a seat frees up and the next waitlisted player can take it
+#[event]
+pub struct SeatAvailable {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Waitlisted player popped from the front of the queue
+    pub player: Pubkey,
+
+    /// Seat that was freed
+    pub seat_index: u8,
+}
+
+/// Emitted when a deal is aborted: the VRF callback was sent without every
+/// seat, or it dealt the wrong number of cards
+#[event]
+pub struct Misdeal {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Hand that was aborted
+    pub hand_number: u64,
+
+    /// Bitmap of seats in the hand that were not passed to the deal
+    pub missing_seats: u8,
+
+    /// Unix timestamp of the misdeal
+    pub timestamp: i64,
+}
+
+/// Emitted once the blinds are in and the hand moves to PreFlop
+/// Not emitted for bomb pots, where every seat antes instead
+#[event]
+pub struct BlindsPosted {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Hand the blinds were posted for
+    pub hand_number: u64,
+
+    /// Small blind seat and the amount it actually posted (less if short, 0 if sitting out)
+    pub small_blind_seat: u8,
+    pub small_blind: u64,
+
+    /// Big blind seat and the amount it actually posted (less if short, 0 if sitting out)
+    pub big_blind_seat: u8,
+    pub big_blind: u64,
+
+    /// First seat to act pre-flop - the small blind (dealer) heads-up
+    pub first_to_act: u8,
+
+    /// Unix timestamp of the deal
+    pub timestamp: i64,
+}
+
+/// Emitted when the table authority removes a player
+#[event]
+pub struct PlayerKicked {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Kicked player's wallet
+    pub player: Pubkey,
+
+    /// Seat the player was removed from
+    pub seat_index: u8,
+
+    /// Chips returned to the player's wallet
+    pub chips_returned: u64,
+
+    /// Unix timestamp of the kick
+    pub timestamp: i64,
+}
+
+/// Emitted when a player quits through fold_and_leave
+#[event]
+pub struct PlayerLeaving {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Leaving player's wallet
+    pub player: Pubkey,
+
+    /// Seat the player left
+    pub seat_index: u8,
+
+    /// Whether the player folded a live hand on the way out
+    pub folded: bool,
+
+    /// Chips returned to the player's wallet
+    pub chips_returned: u64,
+
+    /// Unix timestamp of the leave
+    pub timestamp: i64,
+}
+
+/// Emitted when timeout_player sits out a player who keeps timing out
+#[event]
+pub struct PlayerAutoSatOut {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Player's wallet
+    pub player: Pubkey,
+
+    /// Seat that will be left out of new hands until sit_in
+    pub seat_index: u8,
+
+    /// Timeout auto-folds in a row that triggered it
+    pub consecutive_timeouts: u8,
+
+    /// Unix timestamp of the last timeout
+    pub timestamp: i64,
+}
+
+/// One authoritative settlement record per hand, emitted at showdown after
+/// HandCompleted. Indexers can reconcile a hand from this event alone:
+/// `total_awarded + rake + jackpot_contribution == pot + bounties_paid`,
+/// and seat nets minus `departed_forfeit` sum to
+/// `bounties_paid - rake - jackpot_contribution`
+#[event]
+pub struct SettlementSummary {
+    /// Table identifier
+    pub table_id: [u8; 32],
+
+    /// Sequential hand number
+    pub hand_number: u64,
+
+    /// Pot collected from the players (after any uncalled bet was returned)
+    pub pot: u64,
+
+    /// Rake taken by the table (the program takes no rake yet, so always 0)
+    pub rake: u64,
+
+    /// Jackpot contribution taken from the pot (not implemented yet, always 0)
+    pub jackpot_contribution: u64,
+
+    /// Bounties paid on top of the pot (not implemented yet, always 0)
+    pub bounties_paid: u64,
+
+    /// Chips paid out to seats from the pot
+    pub total_awarded: u64,
+
+    /// Chips left in the pot by players who left mid-hand (fold_and_leave,
+    /// kick_player). Their seats are gone, so they aren't listed in `seats`
+    pub departed_forfeit: u64,
+
+    /// Net result per seat (first `seat_count` entries are valid)
+    pub seats: [SeatSettlement; SEAT_BITMAP_SIZE],
+
+    /// How many entries in `seats` are valid
+    pub seat_count: u8,
+}
+
+/// A seat's net result for the hand
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct SeatSettlement {
+    /// Player's wallet pubkey
+    pub player: Pubkey,
+
+    /// Seat index (0-7)
+    pub seat_index: u8,
+
+    /// Chips won minus chips bet this hand
+    pub net: i64,
+}
+
+impl HandCompleted {
+    /// Lay per-seat results out in the event's fixed array. Every seat a
+    /// bitmap can address has a slot, so no seated player's result is dropped
+    pub fn pack_results(results: &[PlayerHandResult]) -> [PlayerHandResult; SEAT_BITMAP_SIZE] {
+        let mut packed = [PlayerHandResult::default(); SEAT_BITMAP_SIZE];
+        for (entry, result) in packed.iter_mut().zip(results.iter()) {
+            *entry = *result;
+        }
+        packed
+    }
+}
+
+impl SettlementSummary {
+    /// Build the summary from the per-seat results of a settled hand.
+    /// `departed_forfeit` is what departed players left in the pot
+    pub fn from_results(
+        table_id: [u8; 32],
+        hand_number: u64,
+        pot: u64,
+        departed_forfeit: u64,
+        results: &[PlayerHandResult],
+    ) -> Self {
+        let mut seats = [SeatSettlement::default(); SEAT_BITMAP_SIZE];
+        for (entry, result) in seats.iter_mut().zip(results.iter()) {
+            *entry = SeatSettlement {
+                player: result.player,
+                seat_index: result.seat_index,
+                net: result.net,
+            };
+        }
+
+        Self {
+            table_id,
+            hand_number,
+            pot,
+            rake: 0,
+            jackpot_contribution: 0,
+            bounties_paid: 0,
+            total_awarded: results.iter().map(|result| result.chips_won).sum(),
+            departed_forfeit,
+            seats,
+            seat_count: results.len().min(seats.len()) as u8,
+        }
+    }
+
+    /// Whether the summary's totals agree with each other: everything in the
+    /// pot went to a seat or the house, and seat nets balance against it once
+    /// the departed players' forfeit is counted as their loss
+    pub fn reconciles(&self) -> bool {
+        let house = self.rake.saturating_add(self.jackpot_contribution);
+        let net_total: i64 = self.seats[..self.seat_count as usize]
+            .iter()
+            .map(|seat| seat.net)
+            .sum::<i64>()
+            - self.departed_forfeit as i64;
+
+        self.total_awarded.saturating_add(house) == self.pot.saturating_add(self.bounties_paid)
+            && net_total == self.bounties_paid as i64 - house as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::CARD_SENTINEL;
+
+    /// Test every seat of a table past six players lands in the hand events
+    #[test]
+    fn test_hand_completed_holds_every_seat() {
+        let results: Vec<PlayerHandResult> = (0..7u8)
+            .map(|seat_index| PlayerHandResult {
+                player: Pubkey::new_unique(),
+                seat_index,
+                hole_card_1: seat_index,
+                hole_card_2: seat_index + 10,
+                hand_rank: 0,
+                chips_won: if seat_index == 6 { 70 } else { 0 },
+                chips_bet: 10,
+                folded: false,
+                all_in: false,
+                net: if seat_index == 6 { 60 } else { -10 },
+            })
+            .collect();
+
+        let packed = HandCompleted::pack_results(&results);
+        for (seat_index, result) in packed.iter().take(7).enumerate() {
+            assert_eq!(result.seat_index, seat_index as u8);
+            assert_eq!(result.player, results[seat_index].player);
+        }
+        assert_eq!(packed[6].chips_won, 70, "Seventh seat's result survives");
+        assert_eq!(packed[7].player, Pubkey::default(), "Unused slot stays zeroed");
+
+        let summary = SettlementSummary::from_results([0; 32], 1, 70, 0, &results);
+        assert_eq!(summary.seat_count, 7);
+        assert_eq!(summary.seats[6].net, 60);
+        assert!(summary.reconciles());
+    }
+
+    /// Test a seat's net result is what it won minus what it bet
+    #[test]
+    fn test_hand_result_net() {
+        let result = |seat_index: u8, chips_bet: u64| PlayerHandResult {
+            player: Pubkey::new_unique(),
+            seat_index,
+            hole_card_1: CARD_SENTINEL,
+            hole_card_2: CARD_SENTINEL,
+            hand_rank: 255,
+            chips_bet,
+            ..Default::default()
+        };
+
+        // Heads-up showdown: seat 0 wins the whole 200 pot
+        let mut winner = result(0, 100);
+        let mut loser = result(1, 100);
+        winner.record_winnings(200);
+        loser.record_winnings(0);
+
+        assert_eq!(winner.net, 100);
+        assert!(winner.net > 0);
+        assert_eq!(loser.net, -100, "Loser's net is minus their bet");
+
+        let summary = SettlementSummary::from_results([0; 32], 1, 200, 0, &[winner, loser]);
+        assert_eq!(summary.seats[0].net, winner.net);
+        assert_eq!(summary.seats[1].net, loser.net);
+        assert!(summary.reconciles());
+    }
 }
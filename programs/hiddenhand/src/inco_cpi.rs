@@ -6,6 +6,11 @@
 //! Key functions:
 //! - `encrypt_card`: Encrypt a card value, returns encrypted handle
 //! - `grant_allowance`: Grant decryption access to a player
+//!
+//! There is deliberately no decrypt CPI: Inco Lightning has no on-chain
+//! decrypt instruction. Covalidators decrypt off-chain for an allowed
+//! address and sign the result, and reveal_cards / reveal_community check
+//! that attestation through the Ed25519 precompile instead
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
@@ -13,6 +18,8 @@ use anchor_lang::solana_program::{
     program::{invoke, invoke_signed},
 };
 
+use crate::error::HiddenHandError;
+
 /// Inco Lightning Program ID
 pub const INCO_PROGRAM_ID: Pubkey = pubkey!("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
 
@@ -32,6 +39,32 @@ impl EncryptedCard {
     pub fn is_initialized(&self) -> bool {
         self.0 != 0
     }
+
+    /// Checked accessor for handles returned by an encrypt CPI.
+    /// A zero handle means Inco never produced a ciphertext, so storing it
+    /// would leave the seat with an undecryptable card - revert instead.
+    pub fn handle(self) -> Result<u128> {
+        require!(self.is_initialized(), HiddenHandError::EncryptionFailed);
+        Ok(self.0)
+    }
+
+    /// Whether both cards carry the exact same handle. Handles are opaque, so
+    /// this says nothing about the card values - only that Inco, which
+    /// randomizes every ciphertext, handed back the same one twice
+    pub fn same_handle(&self, other: &EncryptedCard) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// First handle that appears more than once among `cards`, if any.
+/// Fresh encryptions never collide, so a repeat is a sign of an encrypt CPI
+/// returning a constant instead of a new ciphertext
+pub fn find_repeated_handle(cards: &[EncryptedCard]) -> Option<EncryptedCard> {
+    cards
+        .iter()
+        .enumerate()
+        .find(|(i, card)| cards[i + 1..].iter().any(|other| card.same_handle(other)))
+        .map(|(_, card)| *card)
 }
 
 /// Pre-computed sighash discriminators for Inco functions
@@ -85,20 +118,29 @@ pub fn encrypt_card<'info>(
     invoke(&ix, &[signer.clone()])?;
 
     // Get the return data (encrypted handle)
-    let (_program_id, return_data) = anchor_lang::solana_program::program::get_return_data()
-        .ok_or(ProgramError::InvalidAccountData)?;
-
-    // Parse as u128 (Euint128 is just a wrapper around u128)
-    let handle = u128::from_le_bytes(
-        return_data
-            .try_into()
-            .map_err(|_| ProgramError::InvalidAccountData)?,
-    );
+    let handle = handle_from_return_data(
+        anchor_lang::solana_program::program::get_return_data().map(|(_, data)| data),
+    )?;
 
     msg!("Card encrypted: {} -> handle {}", card_value, handle);
     Ok(EncryptedCard(handle))
 }
 
+/// Parse the encrypted handle from Inco's return data
+/// Euint128 is just a wrapper around u128, returned as 16 little-endian bytes.
+/// Missing or wrongly sized data means the Inco interface changed under us
+pub fn handle_from_return_data(return_data: Option<Vec<u8>>) -> Result<u128> {
+    let data = return_data.unwrap_or_default();
+    let bytes: [u8; 16] = data.as_slice().try_into().map_err(|_| {
+        msg!(
+            "Inco returned {} bytes, expected 16 for a Euint128 handle - Inco version mismatch?",
+            data.len()
+        );
+        HiddenHandError::IncoReturnDataMalformed
+    })?;
+    Ok(u128::from_le_bytes(bytes))
+}
+
 /// Grant decryption access to a player for an encrypted card
 ///
 /// # Arguments
@@ -218,15 +260,9 @@ pub fn encrypt_card_with_pda<'info>(
     invoke_signed(&ix, &[pda_account.clone()], &[pda_seeds])?;
 
     // Get the return data (encrypted handle)
-    let (_program_id, return_data) = anchor_lang::solana_program::program::get_return_data()
-        .ok_or(ProgramError::InvalidAccountData)?;
-
-    // Parse as u128 (Euint128 is just a wrapper around u128)
-    let handle = u128::from_le_bytes(
-        return_data
-            .try_into()
-            .map_err(|_| ProgramError::InvalidAccountData)?,
-    );
+    let handle = handle_from_return_data(
+        anchor_lang::solana_program::program::get_return_data().map(|(_, data)| data),
+    )?;
 
     msg!("Card encrypted (PDA): {} -> handle {}", card_value, handle);
     Ok(EncryptedCard(handle))
@@ -252,4 +288,43 @@ mod tests {
         let empty = EncryptedCard::default();
         assert!(!empty.is_initialized());
     }
+
+    #[test]
+    fn test_handle_rejects_uninitialized() {
+        assert_eq!(EncryptedCard::wrap(12345).handle().unwrap(), 12345);
+
+        let err = EncryptedCard::default().handle().unwrap_err();
+        assert_eq!(err, HiddenHandError::EncryptionFailed.into());
+    }
+
+    #[test]
+    fn test_find_repeated_handle() {
+        let a = EncryptedCard::wrap(1 << 100);
+        let b = EncryptedCard::wrap(2 << 100);
+        let c = EncryptedCard::wrap(3 << 100);
+        assert!(a.same_handle(&EncryptedCard::wrap(1 << 100)));
+        assert!(!a.same_handle(&b));
+
+        assert_eq!(find_repeated_handle(&[a, b, c]), None);
+        assert_eq!(find_repeated_handle(&[]), None);
+
+        // A constant returned for two different cards is caught wherever it lands
+        assert_eq!(find_repeated_handle(&[a, b, c, b]), Some(b));
+        assert_eq!(find_repeated_handle(&[c, c]), Some(c));
+    }
+
+    #[test]
+    fn test_handle_from_return_data() {
+        let handle = 0x0123_4567_89ab_cdef_0011_2233_4455_6677u128;
+        assert_eq!(
+            handle_from_return_data(Some(handle.to_le_bytes().to_vec())).unwrap(),
+            handle
+        );
+
+        // Short, long or missing return data is a distinct, diagnosable error
+        for data in [Some(vec![1u8; 8]), Some(vec![1u8; 32]), Some(vec![]), None] {
+            let err = handle_from_return_data(data).unwrap_err();
+            assert_eq!(err, HiddenHandError::IncoReturnDataMalformed.into());
+        }
+    }
 }
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{PlayerSeat, Table, TableStatus};
+
+#[derive(Accounts)]
+pub struct AddOn<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump,
+        has_one = player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+
+    /// Vault to receive the add-on (SystemAccount validates System Program ownership)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy more chips between hands (tournament add-on / re-entry after busting)
+/// Only open while the table is Waiting once the add-on break level is reached,
+/// and limited to `table.max_addons` per seat
+pub fn handler(ctx: Context<AddOn>, amount: u64) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let player_seat = &mut ctx.accounts.player_seat;
+
+    // Never mid-hand - chips in play are locked
+    require!(
+        table.status == TableStatus::Waiting,
+        HiddenHandError::TableNotWaiting
+    );
+
+    require!(
+        table.addons_open(),
+        HiddenHandError::AddOnNotAvailable
+    );
+
+    require!(
+        player_seat.addon_count < table.max_addons,
+        HiddenHandError::AddOnLimitReached
+    );
+
+    // Stack after the add-on must stay within the table's buy-in cap
+    let new_stack = player_seat
+        .chips
+        .checked_add(amount)
+        .ok_or(HiddenHandError::InvalidBuyIn)?;
+    require!(
+        amount > 0 && new_stack <= table.max_buy_in,
+        HiddenHandError::InvalidBuyIn
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    player_seat.chips = new_stack;
+    player_seat.addon_count += 1;
+
+    msg!(
+        "Seat {} adds on {} chips (stack {}, add-on {} of {})",
+        player_seat.seat_index,
+        amount,
+        new_stack,
+        player_seat.addon_count,
+        table.max_addons
+    );
+
+    Ok(())
+}
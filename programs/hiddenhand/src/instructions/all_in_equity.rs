@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::{find_winners, HandState, Table};
+
+/// Most board cards the preview will enumerate. One card to come is at most
+/// 46 runouts; a flop preview would be ~1000 and blow the compute budget
+pub const MAX_EQUITY_CARDS_TO_COME: usize = 1;
+
+/// Read-only equity preview for an all-in runout
+/// Remaining accounts should be the seat accounts of every player still in the hand
+#[derive(Accounts)]
+pub struct AllInEquity<'info> {
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump
+    )]
+    pub hand_state: Account<'info, HandState>,
+}
+
+/// One seat's results over every possible runout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeatEquity {
+    pub seat_index: u8,
+    /// Runouts this seat wins outright
+    pub wins: u32,
+    /// Runouts this seat splits with at least one other seat
+    pub ties: u32,
+}
+
+/// Equity preview returned to clients while an all-in runout is pending
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EquityPreview {
+    /// Number of runouts enumerated
+    pub runouts: u32,
+    pub seats: Vec<SeatEquity>,
+}
+
+impl EquityPreview {
+    /// Enumerate every way to complete `board` from the cards nobody holds
+    /// and count each seat's high-hand wins and ties
+    pub fn enumerate(hands: &[(u8, [u8; 2])], board: &[u8]) -> Self {
+        let mut dead: u64 = 0;
+        for &card in board.iter().chain(hands.iter().flat_map(|(_, cards)| cards)) {
+            dead |= 1 << card;
+        }
+        let live: Vec<u8> = (0..DECK_SIZE as u8).filter(|&card| dead & (1 << card) == 0).collect();
+
+        let mut preview = Self {
+            runouts: 0,
+            seats: hands
+                .iter()
+                .map(|&(seat_index, _)| SeatEquity { seat_index, wins: 0, ties: 0 })
+                .collect(),
+        };
+
+        let mut runout = board.to_vec();
+        preview.deal(hands, &live, 0, &mut runout);
+        preview
+    }
+
+    /// Recursively deal the remaining board from `live[from..]`, scoring each full board
+    fn deal(&mut self, hands: &[(u8, [u8; 2])], live: &[u8], from: usize, board: &mut Vec<u8>) {
+        if board.len() == COMMUNITY_CARD_COUNT {
+            let player_cards: Vec<(u8, [u8; 7])> = hands
+                .iter()
+                .map(|&(seat_index, [c1, c2])| {
+                    (seat_index, [c1, c2, board[0], board[1], board[2], board[3], board[4]])
+                })
+                .collect();
+            let winners = find_winners(&player_cards);
+            for seat in self.seats.iter_mut().filter(|s| winners.contains(&s.seat_index)) {
+                if winners.len() == 1 {
+                    seat.wins += 1;
+                } else {
+                    seat.ties += 1;
+                }
+            }
+            self.runouts += 1;
+            return;
+        }
+
+        for i in from..live.len() {
+            board.push(live[i]);
+            self.deal(hands, live, i + 1, board);
+            board.pop();
+        }
+    }
+}
+
+/// Returns each seat's win/tie count over every remaining runout.
+/// Only available once betting is closed with players all-in and every
+/// remaining hand tabled via reveal_cards - the preview never sees hidden cards
+pub fn handler(ctx: Context<AllInEquity>) -> Result<EquityPreview> {
+    let hand_state = &ctx.accounts.hand_state;
+
+    require!(
+        hand_state.is_all_in_runout(),
+        HiddenHandError::InvalidPhase
+    );
+
+    let board = hand_state.revealed_community_cards();
+    let cards_to_come = COMMUNITY_CARD_COUNT.saturating_sub(board.len());
+    require!(
+        hand_state.community_card_count as usize == COMMUNITY_CARD_COUNT
+            && cards_to_come <= MAX_EQUITY_CARDS_TO_COME,
+        HiddenHandError::EquityPreviewUnavailable
+    );
+
+    let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
+    for account in ctx.remaining_accounts.iter() {
+        require!(seen_keys.insert(*account.key), HiddenHandError::DuplicateAccount);
+    }
+
+    let program_id = crate::ID;
+    let table_key = ctx.accounts.table.key();
+    let mut hands: Vec<(u8, [u8; 2])> = Vec::with_capacity(SEAT_BITMAP_SIZE);
+    for account_info in ctx.remaining_accounts.iter().take(SEAT_BITMAP_SIZE) {
+        let Some(seat) = validate_seat_account(account_info, &table_key, &program_id) else {
+            continue;
+        };
+        if !(seat.is_in_hand() && hand_state.is_player_active(seat.seat_index)) {
+            continue;
+        }
+        require!(seat.cards_revealed, HiddenHandError::PlayersNotRevealed);
+        hands.push((seat.seat_index, [seat.revealed_card_1, seat.revealed_card_2]));
+    }
+
+    // Every player contesting the pot must be in the preview
+    require!(
+        hands.len() == hand_state.active_count as usize,
+        HiddenHandError::PlayersNotRevealed
+    );
+
+    let preview = EquityPreview::enumerate(&hands, &board);
+
+    msg!(
+        "Equity over {} runouts: {:?}",
+        preview.runouts,
+        preview
+            .seats
+            .iter()
+            .map(|s| (s.seat_index, s.wins, s.ties))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hand_state;
+
+    /// Test the all-in equity preview over a fully enumerable turn-to-river spot
+    #[test]
+    fn test_all_in_equity_turn_runout() {
+        use crate::state::GamePhase;
+
+        let card = |rank: u8, suit: u8| suit * 13 + rank;
+
+        // Board 2c 7d 9s Kc. Seat 0: Ts 8s (open-ended), seat 3: 9h 9d (set)
+        // Seat 0 gets there with any of the eight jacks and sixes; 44 unseen river cards
+        let board = [card(0, 0), card(5, 1), card(7, 3), card(11, 0)];
+        let hands = [(0, [card(8, 3), card(6, 3)]), (3, [card(7, 2), card(7, 1)])];
+        let preview = EquityPreview::enumerate(&hands, &board);
+        assert_eq!(preview.runouts, 44);
+        assert_eq!((preview.seats[0].seat_index, preview.seats[0].wins, preview.seats[0].ties), (0, 8, 0));
+        assert_eq!((preview.seats[1].seat_index, preview.seats[1].wins, preview.seats[1].ties), (3, 36, 0));
+
+        // Ac Kd Qh Jc: both tens make broadway and chop every river
+        let board = [card(12, 0), card(11, 1), card(10, 2), card(9, 0)];
+        let hands = [(1, [card(8, 3), card(0, 2)]), (2, [card(8, 1), card(1, 2)])];
+        let preview = EquityPreview::enumerate(&hands, &board);
+        assert_eq!(preview.runouts, 44);
+        assert!(preview.seats.iter().all(|s| s.wins == 0 && s.ties == 44));
+
+        // Hands can only be tabled once betting is closed on an all-in runout
+        let mut hand = hand_state(GamePhase::Turn);
+        hand.all_in_players = 0b0001;
+        assert!(!hand.is_all_in_runout(), "Betting still open");
+        hand.awaiting_community_reveal = true;
+        assert!(hand.is_all_in_runout(), "One player left with chips can't bet alone");
+        hand.all_in_players = 0;
+        assert!(!hand.is_all_in_runout(), "Normal street change");
+    }
+}
@@ -0,0 +1,53 @@
+//! Archive a table that has outlived its maximum lifetime
+//!
+//! Once TABLE_MAX_LIFETIME_SLOTS have passed since a table was created,
+//! anyone can close it if nobody is seated, returning the rent to the
+//! authority that created it.
+//!
+//! Requirements:
+//! - No hand in progress
+//! - No seated players (funded-but-idle tables go through close_inactive_table)
+//! - Table must have existed for the lifetime period
+//!
+//! This stops stale table PDAs from lingering forever.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::Table;
+
+#[derive(Accounts)]
+pub struct ArchiveTable<'info> {
+    /// Anyone can call this once the table has expired
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump,
+        has_one = authority @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Original table authority - receives the table's rent
+    #[account(mut)]
+    pub authority: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ArchiveTable>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let clock = Clock::get()?;
+
+    table.check_archivable(clock.slot)?;
+
+    msg!(
+        "Archiving table {:?} after {} slots, rent returned to {}",
+        table.table_id,
+        clock.slot.saturating_sub(table.created_slot),
+        table.authority
+    );
+
+    Ok(())
+}
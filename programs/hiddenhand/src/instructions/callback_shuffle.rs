@@ -3,8 +3,11 @@ use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::inco_cpi::{self, INCO_PROGRAM_ID};
-use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::events::BlindsPosted;
+use crate::inco_cpi::{self, EncryptedCard, INCO_PROGRAM_ID};
+use crate::instructions::deal_cards::{abort_misdeal, seats_present};
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::{seats_from, seed_commitment, shuffle_deck, DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 /// VRF callback for card shuffling - ATOMIC SHUFFLE + ENCRYPT
 ///
@@ -32,7 +35,8 @@ pub struct CallbackShuffle<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -48,29 +52,84 @@ pub struct CallbackShuffle<'info> {
     // [2..] = player seat accounts
 }
 
+/// Whether a seat passed to the callback can still be dealt in. Seats can change
+/// while the VRF request is in flight, so it must still hold its place at the
+/// table (`seated`), have chips and not be away
+pub(crate) fn is_dealable(seat: &PlayerSeat, seated: u8) -> bool {
+    seated & (1 << seat.seat_index) != 0 && seat.can_be_dealt() && seat.player != Pubkey::default()
+}
+
+/// Number of accounts in `seat_accounts` that are this table's seats and can
+/// be dealt in - checked by request_shuffle and again by the callback
+pub(crate) fn dealable_seat_count(
+    seat_accounts: &[AccountInfo],
+    table_key: &Pubkey,
+    program_id: &Pubkey,
+    seated: u8,
+) -> usize {
+    seat_accounts
+        .iter()
+        .filter_map(|info| validate_seat_account(info, table_key, program_id))
+        .filter(|seat| is_dealable(seat, seated))
+        .count()
+}
+
 /// VRF callback - receives randomness and ATOMICALLY shuffles + encrypts cards
 ///
 /// SECURITY: The VRF seed is NEVER stored in account state!
 /// It only exists in memory during this transaction's execution.
 /// This eliminates the account state leak vector.
+/// Give every dealt seat back what it posted for a misdealt hand. The refund
+/// comes from the pot's record of each seat, which a seat passed twice can't
+/// throw off
+fn refund_misdeal(
+    seat_accounts: &[AccountInfo],
+    table_key: &Pubkey,
+    program_id: &Pubkey,
+    hand_state: &mut HandState,
+    dealt_seats: u8,
+) -> Result<()> {
+    for account_info in seat_accounts {
+        let Some(mut seat) = validate_seat_account(account_info, table_key, program_id) else {
+            continue;
+        };
+        if dealt_seats & (1 << seat.seat_index) == 0 {
+            continue;
+        }
+        let refund = hand_state.take_from_pot(seat.seat_index, u64::MAX)?;
+        seat.refund_misdeal(refund);
+        let mut data = account_info.try_borrow_mut_data()?;
+        seat.try_serialize(&mut *data)?;
+        if refund > 0 {
+            msg!("Seat {} gets {} back", seat.seat_index, refund);
+        }
+    }
+    Ok(())
+}
+
 pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()> {
     let clock = Clock::get()?;
 
     // Extract all needed values before mutable borrows
     let table_key = ctx.accounts.table.key();
+    let table_id = ctx.accounts.table.table_id;
     let hand_number = ctx.accounts.table.hand_number;
-    let dealer_pos = ctx.accounts.table.dealer_position;
     let max_players = ctx.accounts.table.max_players;
     let small_blind = ctx.accounts.table.small_blind;
     let big_blind = ctx.accounts.table.big_blind;
     let table_status = ctx.accounts.table.status;
-    let current_players = ctx.accounts.table.current_players;
     let occupied_seats = ctx.accounts.table.occupied_seats;
 
     let deck_bump = ctx.accounts.deck_state.bump;
     let deck_is_shuffled = ctx.accounts.deck_state.is_shuffled;
+    let burn_cards = ctx.accounts.deck_state.burn_cards;
 
     let initial_active_players = ctx.accounts.hand_state.active_players;
+    let sb_pos = ctx.accounts.hand_state.sb_seat;
+    let bb_pos = ctx.accounts.hand_state.bb_seat;
+    // Only heads-up does the dealer post the small blind
+    let is_heads_up = sb_pos == ctx.accounts.hand_state.dealer_position;
+    let community_count = ctx.accounts.hand_state.community_card_count as usize;
 
     // Get account info for CPI before mutable borrows
     let deck_state_info = ctx.accounts.deck_state.to_account_info();
@@ -119,33 +178,48 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
     // Get program ID for validation
     let program_id = crate::ID;
 
+    // Anyone who left the table since start_hand is out of the hand. Their seat
+    // account is closed, which must not read as a misdeal below
+    let seated = initial_active_players & occupied_seats;
+    if seated != initial_active_players {
+        msg!(
+            "Seats left before the deal: {:#08b}",
+            initial_active_players & !seated
+        );
+        ctx.accounts.hand_state.active_players = seated;
+    }
+
+    // Misdeal check: the callback can't be retried, so a seat missing from the
+    // request aborts the hand (before any blind is posted) instead of stalling it
+    let seats_present = seats_present(&[], seat_accounts, &table_key, &program_id);
+    if ctx.accounts.hand_state.is_misdeal(seats_present) {
+        abort_misdeal(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.hand_state,
+            seats_present,
+            clock.unix_timestamp,
+        );
+        return Ok(());
+    }
+
+    // Fail before any encryption if the seats that changed leave too few to deal
+    require!(
+        dealable_seat_count(seat_accounts, &table_key, &program_id, seated) >= MIN_PLAYERS as usize,
+        HiddenHandError::NotEnoughPlayers
+    );
+
     // ============================================================
     // SHUFFLE THE DECK IN MEMORY (seed never stored!)
     // ============================================================
     msg!("Shuffling deck using VRF randomness...");
 
-    // Initialize deck with cards 0-51
-    let mut deck: [u8; 52] = core::array::from_fn(|i| i as u8);
+    let deck = shuffle_deck(&randomness);
+    // A shuffle bug must never deal the same card twice
+    DeckState::verify_permutation(&deck)?;
 
-    // Convert randomness to u64 seed for Fisher-Yates shuffle
-    let mut seed = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
-
-    // Fisher-Yates shuffle using VRF randomness
-    for i in (1..52).rev() {
-        // Use different parts of randomness for each iteration
-        if i % 4 == 0 && i < 28 {
-            // Mix in more randomness periodically
-            let offset = (i / 4) * 8;
-            if offset + 8 <= 32 {
-                seed ^= u64::from_le_bytes(randomness[offset..offset + 8].try_into().unwrap());
-            }
-        }
-
-        // LCG step with VRF-seeded state
-        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-        let j = (seed % (i as u64 + 1)) as usize;
-        deck.swap(i, j);
-    }
+    // One-way commitment to the seed, so the shuffle can be replayed and
+    // checked once the hand is over (verify_fairness)
+    let commitment = seed_commitment(&randomness);
 
     msg!("Deck shuffled. Now encrypting ALL cards (community + hole cards) via Inco FHE...");
 
@@ -166,62 +240,38 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
         &bump_bytes,
     ];
 
-    let mut active_players = initial_active_players;
+    let mut active_players = seated;
     let mut active_count = 0u8;
-    let mut total_blinds_posted = 0u64;
+    let mut dealt_seats = 0u8;
+    let mut sb_posted = 0u64;
+    let mut bb_posted = 0u64;
+    // Seats a forced bet put all-in (only acted on for bomb pot antes)
+    let mut all_in_seats = 0u8;
+    let bomb_pot_ante = ctx.accounts.hand_state.bomb_pot_ante;
 
     // ============================================================
     // ENCRYPT COMMUNITY CARDS (cards 0-4) - PRIVACY FIX
     // These are encrypted so no one can read them before reveal
+    // Variants with a smaller board leave the unused slots undealt (CARD_SENTINEL)
     // ============================================================
-    msg!("Encrypting 5 community cards...");
-    let mut encrypted_community: [u128; 5] = [0; 5];
-    for i in 0..5 {
+    msg!("Encrypting {} community cards...", community_count);
+    let mut encrypted_community: [u128; COMMUNITY_CARD_COUNT] = [CARD_SENTINEL as u128; COMMUNITY_CARD_COUNT];
+    for i in 0..community_count {
         let encrypted = inco_cpi::encrypt_card_with_pda(
             &deck_state_info,
             deck_seeds,
-            deck[i],
+            deck[DeckState::community_index_for(burn_cards, i)],
         )?;
-        encrypted_community[i] = encrypted.unwrap();
+        encrypted_community[i] = encrypted.handle()?;
         msg!("Community card {} encrypted: handle {}", i, encrypted_community[i]);
     }
-    msg!("All 5 community cards encrypted!");
-
-    // Helper to check if seat is occupied using bitmask
-    let is_seat_occupied = |seat: u8| -> bool {
-        (occupied_seats & (1 << seat)) != 0
-    };
-
-    // Find SB and BB positions (matching start_hand.rs logic)
-    // In heads-up (2 players): dealer = SB, other player = BB
-    // In 3+ players: SB is left of dealer, BB is left of SB
-    let is_heads_up = current_players == 2;
-    let (sb_pos, bb_pos) = if is_heads_up {
-        // Heads-up: dealer is SB
-        let sb = dealer_pos;
-        let mut bb = (dealer_pos + 1) % max_players;
-        while !is_seat_occupied(bb) && bb != dealer_pos {
-            bb = (bb + 1) % max_players;
-        }
-        (sb, bb)
-    } else {
-        // Standard: SB is left of dealer
-        let mut sb = (dealer_pos + 1) % max_players;
-        while !is_seat_occupied(sb) {
-            sb = (sb + 1) % max_players;
-        }
-
-        let mut bb = (sb + 1) % max_players;
-        while !is_seat_occupied(bb) {
-            bb = (bb + 1) % max_players;
-        }
-        (sb, bb)
-    };
+    msg!("All {} community cards encrypted!", community_count);
 
+    // Blind seats were fixed by start_hand
     msg!("Blind positions: SB=seat {}, BB=seat {} (heads_up={})", sb_pos, bb_pos, is_heads_up);
 
-    // Reserve first 5 cards for community cards (indices 0-4)
-    let mut deal_idx = 5usize;
+    // Hole cards are dealt after the reserved community slots
+    let mut hole_slot = 0usize;
 
     // Collect encryption results before updating deck_state
     let mut encrypted_cards: Vec<(usize, u128, u128)> = Vec::new();
@@ -254,32 +304,37 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
             }
 
             let seat_index = seat.seat_index;
-            let has_chips = seat.chips > 0;
-            let player_pubkey = seat.player;
+            let dealable = is_dealable(&seat, seated);
             drop(data);
 
             // Now borrow mutably to update
             let mut data = account_info.try_borrow_mut_data()?;
             let mut seat = PlayerSeat::try_deserialize(&mut &data[..])?;
 
-            if has_chips && player_pubkey != Pubkey::default() {
-                // Reset bet tracking for new hand
-                seat.current_bet = 0;
-                seat.total_bet_this_hand = 0;
-                seat.has_acted = false;
-                seat.cards_revealed = false;
-                seat.revealed_card_1 = 255;
-                seat.revealed_card_2 = 255;
-
-                // Post blinds if applicable
-                if seat_index == sb_pos {
-                    let sb_amount = seat.place_bet(small_blind);
-                    total_blinds_posted += sb_amount;
-                    msg!("SB (seat {}) posts {}", seat_index, sb_amount);
-                } else if seat_index == bb_pos {
-                    let bb_amount = seat.place_bet(big_blind);
-                    total_blinds_posted += bb_amount;
-                    msg!("BB (seat {}) posts {}", seat_index, bb_amount);
+            if dealable {
+                // Clear last hand's bets and cards before posting blinds
+                seat.clear_for_new_hand();
+
+                // Post blinds if applicable - or the ante from every seat on a bomb pot
+                let forced_bet = ctx
+                    .accounts
+                    .hand_state
+                    .forced_bet_for_seat(seat_index, small_blind, big_blind);
+                if forced_bet > 0 {
+                    let posted = seat.place_bet(forced_bet)?;
+                    ctx.accounts.hand_state.add_to_pot(seat_index, posted)?;
+                    if bomb_pot_ante > 0 {
+                        msg!("Seat {} antes {}", seat_index, posted);
+                    } else if seat_index == sb_pos {
+                        sb_posted = posted;
+                        msg!("SB (seat {}) posts {}", seat_index, posted);
+                    } else {
+                        bb_posted = posted;
+                        msg!("BB (seat {}) posts {}", seat_index, posted);
+                    }
+                }
+                if seat.chips == 0 {
+                    all_in_seats |= 1 << seat_index;
                 }
 
                 // ENCRYPT cards using deck_state PDA as signer
@@ -287,61 +342,94 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
                 let encrypted1 = inco_cpi::encrypt_card_with_pda(
                     &deck_state_info,
                     deck_seeds,
-                    deck[deal_idx],
+                    deck[DeckState::hole_index_for(burn_cards, hole_slot)],
                 )?;
                 let encrypted2 = inco_cpi::encrypt_card_with_pda(
                     &deck_state_info,
                     deck_seeds,
-                    deck[deal_idx + 1],
+                    deck[DeckState::hole_index_for(burn_cards, hole_slot + 1)],
                 )?;
 
-                seat.hole_card_1 = encrypted1.unwrap();
-                seat.hole_card_2 = encrypted2.unwrap();
-                seat.status = PlayerStatus::Playing;
+                let handle1 = encrypted1.handle()?;
+                let handle2 = encrypted2.handle()?;
+
+                seat.hole_card_1 = handle1;
+                seat.hole_card_2 = handle2;
+                seat.deal_in();
 
                 // Store for later deck_state update
-                encrypted_cards.push((deal_idx, encrypted1.unwrap(), encrypted2.unwrap()));
+                encrypted_cards.push((hole_slot, handle1, handle2));
 
-                deal_idx += 2;
+                hole_slot += HOLE_CARDS;
                 active_count += 1;
+                dealt_seats |= 1 << seat_index;
                 msg!("Dealt encrypted cards to seat {}", seat_index);
             } else {
-                // Player has no chips or empty seat
+                // Player is away, has no chips, or left the table since start_hand
                 active_players &= !(1 << seat_index);
-                seat.status = PlayerStatus::Sitting;
+                seat.sit_out_hand();
             }
 
             seat.try_serialize(&mut *data)?;
         }
     }
 
+    // The callback can't be retried, so a deal that handed out the wrong cards
+    // gives the blinds back and aborts the hand rather than playing it corrupt
+    if ctx.accounts.hand_state.is_wrong_deal(dealt_seats, hole_slot) {
+        msg!("Misdeal: {} hole cards dealt to seats {:#08b}", hole_slot, dealt_seats);
+        refund_misdeal(
+            seat_accounts,
+            &table_key,
+            &program_id,
+            &mut ctx.accounts.hand_state,
+            dealt_seats,
+        )?;
+        abort_misdeal(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.hand_state,
+            seats_present,
+            clock.unix_timestamp,
+        );
+        return Ok(());
+    }
+
     // Now update deck_state and hand_state
     let deck_state = &mut ctx.accounts.deck_state;
     let hand_state = &mut ctx.accounts.hand_state;
 
     // Store ENCRYPTED community cards (first 5 slots)
     // These can only be decrypted by authority when revealing flop/turn/river
-    for i in 0..5 {
-        deck_state.cards[i] = encrypted_community[i];
+    for (i, handle) in encrypted_community.iter().enumerate() {
+        deck_state.set_community_handle(i, *handle);
     }
 
     // Store encrypted hole cards
-    for (idx, enc1, enc2) in encrypted_cards {
-        deck_state.cards[idx] = enc1;
-        deck_state.cards[idx + 1] = enc2;
+    for (slot, enc1, enc2) in encrypted_cards {
+        deck_state.set_hole_handle(slot, enc1);
+        deck_state.set_hole_handle(slot + 1, enc2);
+    }
+
+    // Every dealt handle should be unique - flag a broken encryption
+    let dealt_handles: Vec<EncryptedCard> = (0..community_count)
+        .map(|i| deck_state.community_handle(i))
+        .chain((0..hole_slot).map(|slot| deck_state.hole_handle(slot)))
+        .map(EncryptedCard::wrap)
+        .collect();
+    if let Some(repeated) = inco_cpi::find_repeated_handle(&dealt_handles) {
+        msg!("WARNING: handle {} was dealt more than once - encryption may have failed", repeated.unwrap());
     }
 
     // Update deck state
     deck_state.is_shuffled = true;
-    deck_state.deal_index = deal_idx as u8;
+    deck_state.seed_commitment = commitment;
+    deck_state.deal_index = deck_state.hole_index(hole_slot) as u8;
     // NOTE: vrf_seed is NOT stored! The seed only existed in memory.
 
     // Update hand state
     hand_state.active_players = active_players;
     hand_state.active_count = active_count;
-    // Use actual blinds posted (tracked during seat processing) instead of assuming both were posted
-    hand_state.pot = hand_state.pot.saturating_add(total_blinds_posted);
-    hand_state.community_cards = vec![255, 255, 255, 255, 255];
+    hand_state.community_cards = vec![CARD_SENTINEL; COMMUNITY_CARD_COUNT];
     hand_state.community_revealed = 0;
 
     // Verify we have enough players
@@ -356,14 +444,9 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
     let action_pos = if is_heads_up {
         sb_pos
     } else {
-        let mut pos = (bb_pos + 1) % max_players;
-        for _ in 0..max_players {
-            if (active_players & (1 << pos)) != 0 {
-                break;
-            }
-            pos = (pos + 1) % max_players;
-        }
-        pos
+        seats_from(bb_pos + 1, max_players)
+            .find(|&seat| active_players & (1 << seat) != 0)
+            .unwrap_or((bb_pos + 1) % max_players)
     };
     hand_state.action_on = action_pos;
 
@@ -372,6 +455,23 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
     hand_state.last_action_time = clock.unix_timestamp;
     hand_state.all_in_players = 0;
 
+    if hand_state.is_bomb_pot() {
+        hand_state.skip_preflop_betting(all_in_seats);
+        msg!("Bomb pot shuffled and dealt. Pot: {}. Awaiting flop reveal", hand_state.pot);
+        return Ok(());
+    }
+
+    emit!(BlindsPosted {
+        table_id,
+        hand_number,
+        small_blind_seat: sb_pos,
+        small_blind: sb_posted,
+        big_blind_seat: bb_pos,
+        big_blind: bb_posted,
+        first_to_act: hand_state.action_on,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
         "ATOMIC shuffle + encrypt complete! Pot: {}. Phase: PreFlop. Action on seat {}. Active: {}",
         hand_state.pot,
@@ -385,3 +485,78 @@ pub fn handler(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{hand_state, player_seat};
+
+    /// Test a seat that empties between request_shuffle and the callback is
+    /// excluded from the deal rather than dealt to or treated as a misdeal
+    #[test]
+    fn test_callback_excludes_seat_that_left() {
+        // Three seated at start_hand; seat 2 (busted) leaves before the callback
+        let mut hand = hand_state(GamePhase::Dealing);
+        hand.active_players = 0b111;
+        let occupied_at_callback = 0b011;
+        let seated = hand.active_players & occupied_at_callback;
+        assert_eq!(seated, 0b011);
+
+        // Its closed account no longer shows up, which would have been a misdeal
+        let present = 0b011;
+        assert!(hand.is_misdeal(present));
+        hand.active_players = seated;
+        assert!(!hand.is_misdeal(present));
+
+        // A stale copy of the departed seat is never dealt to, even with chips
+        let mut left = player_seat(2, 1_000);
+        assert!(!is_dealable(&left, seated));
+        left.chips = 0;
+        assert!(!is_dealable(&left, 0b111));
+
+        let seats = [player_seat(0, 1_000), player_seat(1, 1_000)];
+        assert!(seats.iter().all(|seat| is_dealable(seat, seated)));
+
+        // One still-valid seat is not enough to deal
+        let seated = 0b001;
+        assert_eq!(seats.iter().filter(|seat| is_dealable(seat, seated)).count(), 1);
+    }
+
+    /// Test request_shuffle only counts this table's dealable seats, so a
+    /// request with one real seat fails before the VRF is asked
+    #[test]
+    fn test_dealable_seat_count() {
+        let program_id = crate::ID;
+        let table_key = Pubkey::new_unique();
+        let seat_data = |seat_index: u8, table: Pubkey, chips: u64| {
+            let mut seat = player_seat(seat_index, chips);
+            seat.table = table;
+            let mut data = Vec::new();
+            seat.try_serialize(&mut data).unwrap();
+            data
+        };
+        let seat_key = |seat_index: u8| {
+            Pubkey::find_program_address(&[SEAT_SEED, table_key.as_ref(), &[seat_index]], &program_id).0
+        };
+
+        let keys = [seat_key(0), seat_key(1), seat_key(2), Pubkey::new_unique()];
+        let mut data = [
+            seat_data(0, table_key, 1_000),
+            seat_data(1, Pubkey::new_unique(), 1_000), // another table's seat
+            seat_data(2, table_key, 0),                // busted
+            seat_data(3, table_key, 1_000),            // not at its seat PDA
+        ];
+        let mut lamports = [1u64; 4];
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(data.iter_mut())
+            .zip(lamports.iter_mut())
+            .map(|((key, data), lamports)| {
+                AccountInfo::new(key, false, true, lamports, data, &program_id, false, 0)
+            })
+            .collect();
+
+        assert_eq!(dealable_seat_count(&infos, &table_key, &program_id, 0b1111), 1);
+        assert_eq!(dealable_seat_count(&infos[..1], &table_key, &program_id, 0b0000), 0);
+    }
+}
@@ -0,0 +1,71 @@
+//! Close a settled hand's accounts and reclaim their rent
+//!
+//! start_hand creates a fresh HandState and DeckState for every hand. Once
+//! the hand is over, anyone can close both and return the rent to the table
+//! authority.
+//!
+//! Requirements:
+//! - Hand must be Settled
+//! - If it is the table's current hand, the table must be back in Waiting
+//!   (a fold-out is Settled before showdown pays the pot)
+//!
+//! A closed hand can no longer be checked with verify_fairness.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{DeckState, HandState, Table};
+
+#[derive(Accounts)]
+#[instruction(hand_number: u64)]
+pub struct CloseHand<'info> {
+    /// Anyone can close a settled hand
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump,
+        has_one = authority @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand_number.to_le_bytes()],
+        bump = hand_state.bump
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [DECK_SEED, table.key().as_ref(), &hand_number.to_le_bytes()],
+        bump = deck_state.bump
+    )]
+    pub deck_state: Account<'info, DeckState>,
+
+    /// Table authority - receives the rent of both accounts
+    #[account(mut)]
+    pub authority: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseHand>, hand_number: u64) -> Result<()> {
+    let table = &ctx.accounts.table;
+
+    require!(
+        ctx.accounts
+            .hand_state
+            .is_closable(table.hand_number, table.status),
+        HiddenHandError::HandInProgress
+    );
+
+    msg!(
+        "Closing hand #{}, rent returned to {}",
+        hand_number,
+        table.authority
+    );
+
+    Ok(())
+}
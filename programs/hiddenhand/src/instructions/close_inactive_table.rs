@@ -11,9 +11,11 @@
 //! This prevents SOL from being stuck in abandoned tables.
 
 use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
+use crate::instructions::showdown::validate_seat_account;
 use crate::state::{PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
@@ -41,6 +43,17 @@ pub struct CloseInactiveTable<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Total owed to the seats being paid out, if the vault holds enough to
+/// cover all of it
+pub fn check_vault_covers(vault_lamports: u64, owed: &[u64]) -> Result<u64> {
+    let total = owed
+        .iter()
+        .try_fold(0u64, |total, &chips| total.checked_add(chips))
+        .ok_or(HiddenHandError::ArithmeticOverflow)?;
+    require!(vault_lamports >= total, HiddenHandError::InsufficientVaultBalance);
+    Ok(total)
+}
+
 /// Close an inactive table and return funds to all players
 /// remaining_accounts should contain all player seats and their corresponding wallet accounts
 /// Format: [seat0, wallet0, seat1, wallet1, ...]
@@ -56,10 +69,10 @@ pub fn handler(ctx: Context<CloseInactiveTable>) -> Result<()> {
     );
 
     // Check timeout - must be inactive for TABLE_INACTIVE_TIMEOUT_SECONDS
-    let elapsed = clock.unix_timestamp - table.last_ready_time;
+    let elapsed = table.seconds_since_ready(clock.unix_timestamp);
     require!(
         elapsed >= TABLE_INACTIVE_TIMEOUT_SECONDS,
-        HiddenHandError::TimeoutNotReached
+        HiddenHandError::TableNotInactive
     );
 
     msg!(
@@ -77,95 +90,65 @@ pub fn handler(ctx: Context<CloseInactiveTable>) -> Result<()> {
     let table_key = table.key();
     let program_id = crate::ID;
 
-    let mut total_returned: u64 = 0;
-
+    // Work out every payout before moving any lamports, so a vault that
+    // can't cover them all fails up front instead of paying only some players
+    let mut payouts: Vec<(&AccountInfo, &AccountInfo, PlayerSeat)> = Vec::new();
+    let mut seen_seats: BTreeSet<Pubkey> = BTreeSet::new();
     for chunk in remaining.chunks(2) {
         let seat_info = &chunk[0];
         let wallet_info = &chunk[1];
 
-        // Security check 1: Verify seat account is owned by our program
-        if seat_info.owner != &program_id {
+        // A seat listed twice would otherwise be paid twice
+        require!(seen_seats.insert(*seat_info.key), HiddenHandError::DuplicateAccount);
+
+        let Some(seat) = validate_seat_account(seat_info, &table_key, &program_id) else {
+            continue;
+        };
+
+        // Verify wallet matches seat player
+        if seat.player != *wallet_info.key {
+            msg!(
+                "Warning: Wallet mismatch for seat {}. Expected {}, got {}",
+                seat.seat_index,
+                seat.player,
+                wallet_info.key
+            );
             continue;
         }
 
-        // Deserialize the seat
-        let seat_data = seat_info.try_borrow_data()?;
-        if seat_data.len() >= 8 {
-            // Check discriminator and deserialize
-            let seat = PlayerSeat::try_deserialize(&mut &seat_data[..])?;
-
-            // Security check 2: Verify seat belongs to this table
-            if seat.table != table_key {
-                drop(seat_data);
-                continue;
-            }
-
-            // Security check 3: Verify PDA derivation
-            let (expected_pda, _) = Pubkey::find_program_address(
-                &[SEAT_SEED, table_key.as_ref(), &[seat.seat_index]],
-                &program_id,
+        payouts.push((seat_info, wallet_info, seat));
+    }
+
+    let owed: Vec<u64> = payouts.iter().map(|(_, _, seat)| seat.chips).collect();
+    let total_returned = check_vault_covers(vault.lamports(), &owed)?;
+
+    for (seat_info, wallet_info, mut seat) in payouts {
+        // Return chips to player
+        if seat.chips > 0 {
+            let transfer_amount = seat.chips;
+
+            // Transfer from vault to player wallet using direct lamport manipulation.
+            // This is safe because:
+            // 1. Solana transactions are atomic - if any operation fails, all changes rollback
+            // 2. The vault is a PDA we control (verified by seeds constraint)
+            // 3. The vault balance was checked against every payout above
+            // 4. We verify the wallet matches the seat owner before transfer
+            **vault.try_borrow_mut_lamports()? -= transfer_amount;
+            **wallet_info.try_borrow_mut_lamports()? += transfer_amount;
+
+            msg!(
+                "Returned {} lamports to player {} from seat {}",
+                transfer_amount,
+                seat.player,
+                seat.seat_index
             );
-            if *seat_info.key != expected_pda {
-                drop(seat_data);
-                continue;
-            }
-
-            // Verify wallet matches seat player
-            if seat.player != *wallet_info.key {
-                msg!(
-                    "Warning: Wallet mismatch for seat {}. Expected {}, got {}",
-                    seat.seat_index,
-                    seat.player,
-                    wallet_info.key
-                );
-                drop(seat_data);
-                continue;
-            }
-
-            // Return chips to player
-            if seat.chips > 0 {
-                let transfer_amount = seat.chips;
-
-                // Transfer from vault to player wallet using direct lamport manipulation.
-                // This is safe because:
-                // 1. Solana transactions are atomic - if any operation fails, all changes rollback
-                // 2. The vault is a PDA we control (verified by seeds constraint)
-                // 3. Adding lamports to a wallet account cannot fail
-                // 4. We verify the wallet matches the seat owner before transfer
-                **vault.try_borrow_mut_lamports()? -= transfer_amount;
-                **wallet_info.try_borrow_mut_lamports()? += transfer_amount;
-
-                total_returned += transfer_amount;
-
-                msg!(
-                    "Returned {} lamports to player {} from seat {}",
-                    transfer_amount,
-                    seat.player,
-                    seat.seat_index
-                );
-            }
         }
-        drop(seat_data);
 
-        // Clear the seat (re-validate ownership and PDA)
-        if seat_info.owner != &program_id {
-            continue;
-        }
+        // Clear the seat
+        seat.chips = 0;
+        seat.player = Pubkey::default();
         let mut seat_data = seat_info.try_borrow_mut_data()?;
-        if seat_data.len() >= 8 {
-            let mut seat = PlayerSeat::try_deserialize(&mut &seat_data[..])?;
-            if seat.table == table_key {
-                let (expected_pda, _) = Pubkey::find_program_address(
-                    &[SEAT_SEED, table_key.as_ref(), &[seat.seat_index]],
-                    &program_id,
-                );
-                if *seat_info.key == expected_pda {
-                    seat.chips = 0;
-                    seat.player = Pubkey::default();
-                    seat.try_serialize(&mut *seat_data)?;
-                }
-            }
-        }
+        seat.try_serialize(&mut *seat_data)?;
     }
 
     // Mark table as closed
@@ -180,3 +163,23 @@ pub fn handler(ctx: Context<CloseInactiveTable>) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test an under-funded vault is caught before any player is paid
+    #[test]
+    fn test_close_inactive_table_vault_check() {
+        assert_eq!(check_vault_covers(3_000, &[1_000, 2_000]).unwrap(), 3_000);
+        assert_eq!(check_vault_covers(5_000, &[1_000, 0, 2_000]).unwrap(), 3_000);
+        assert_eq!(check_vault_covers(0, &[]).unwrap(), 0);
+
+        // Accounting drift: one lamport short fails the whole close
+        let err = check_vault_covers(2_999, &[1_000, 2_000]).unwrap_err();
+        assert_eq!(err, HiddenHandError::InsufficientVaultBalance.into());
+
+        let err = check_vault_covers(u64::MAX, &[u64::MAX, 1]).unwrap_err();
+        assert_eq!(err, HiddenHandError::ArithmeticOverflow.into());
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{Table, TableConfig, TableStatus};
+
+/// Update table options (authority only, between hands)
+#[derive(Accounts)]
+pub struct ConfigureTable<'info> {
+    #[account(
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+pub fn handler(ctx: Context<ConfigureTable>, config: TableConfig) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+
+    // Options take effect from the next hand, so never change them mid-hand
+    require!(
+        table.status == TableStatus::Waiting,
+        HiddenHandError::HandInProgress
+    );
+
+    require!(
+        config.min_players_to_start >= MIN_PLAYERS
+            && config.min_players_to_start <= table.max_players,
+        HiddenHandError::InvalidMinPlayersToStart
+    );
+
+    table.apply_config(&config);
+
+    msg!(
+        "Table configured: game type {:?} ({} community cards)",
+        table.game_type,
+        table.game_type.community_card_count()
+    );
+
+    Ok(())
+}
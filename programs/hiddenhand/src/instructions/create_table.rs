@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::state::{Table, TableStatus};
+use crate::events::TableCreated;
+use crate::state::{Table, TableConfig, TableStatus};
 
 #[derive(Accounts)]
 #[instruction(table_id: [u8; 32])]
@@ -30,35 +31,54 @@ pub struct CreateTable<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<CreateTable>,
-    table_id: [u8; 32],
+/// Sanity-check a table's stakes, with a distinct error for each misconfiguration
+pub fn validate_stakes(
     small_blind: u64,
     big_blind: u64,
     min_buy_in: u64,
     max_buy_in: u64,
-    max_players: u8,
 ) -> Result<()> {
     require!(
-        max_players >= MIN_PLAYERS && max_players <= MAX_PLAYERS,
-        HiddenHandError::InvalidSeatIndex
+        big_blind >= small_blind,
+        HiddenHandError::BigBlindBelowSmallBlind
     );
 
     require!(
-        big_blind >= small_blind,
-        HiddenHandError::InvalidBuyIn
+        min_buy_in <= max_buy_in,
+        HiddenHandError::MinBuyInAboveMax
     );
 
     require!(
-        min_buy_in <= max_buy_in,
-        HiddenHandError::InvalidBuyIn
+        max_buy_in >= big_blind,
+        HiddenHandError::MaxBuyInBelowBigBlind
     );
 
     require!(
-        min_buy_in >= big_blind * 10, // Minimum 10 big blinds
+        big_blind
+            .checked_mul(10)
+            .is_some_and(|ten_bb| min_buy_in >= ten_bb), // Minimum 10 big blinds
         HiddenHandError::InvalidBuyIn
     );
 
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<CreateTable>,
+    table_id: [u8; 32],
+    small_blind: u64,
+    big_blind: u64,
+    min_buy_in: u64,
+    max_buy_in: u64,
+    max_players: u8,
+) -> Result<()> {
+    require!(
+        max_players >= MIN_PLAYERS && max_players <= MAX_PLAYERS,
+        HiddenHandError::InvalidSeatIndex
+    );
+
+    validate_stakes(small_blind, big_blind, min_buy_in, max_buy_in)?;
+
     let table = &mut ctx.accounts.table;
     let clock = Clock::get()?;
 
@@ -75,9 +95,53 @@ pub fn handler(
     table.occupied_seats = 0;
     table.dealer_position = 0;
     table.last_ready_time = clock.unix_timestamp;
+    table.created_slot = clock.slot;
     table.bump = ctx.bumps.table;
+    table.apply_config(&TableConfig::default());
+
+    emit!(TableCreated {
+        table_id,
+        authority: table.authority,
+        small_blind,
+        big_blind,
+        max_players,
+    });
 
     msg!("Table created: {:?}", table_id);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test each stake misconfiguration gets its own error
+    #[test]
+    fn test_validate_stakes() {
+        assert!(validate_stakes(10, 20, 200, 2_000).is_ok());
+        assert!(validate_stakes(20, 20, 200, 200).is_ok(), "Equal blinds allowed");
+
+        assert_eq!(
+            validate_stakes(20, 10, 200, 2_000).unwrap_err(),
+            HiddenHandError::BigBlindBelowSmallBlind.into()
+        );
+        assert_eq!(
+            validate_stakes(10, 20, 2_000, 200).unwrap_err(),
+            HiddenHandError::MinBuyInAboveMax.into()
+        );
+        assert_eq!(
+            validate_stakes(10, 20, 10, 15).unwrap_err(),
+            HiddenHandError::MaxBuyInBelowBigBlind.into()
+        );
+        assert_eq!(
+            validate_stakes(10, 20, 100, 2_000).unwrap_err(),
+            HiddenHandError::InvalidBuyIn.into()
+        );
+        assert_eq!(
+            validate_stakes(1, u64::MAX, u64::MAX, u64::MAX).unwrap_err(),
+            HiddenHandError::InvalidBuyIn.into(),
+            "10 BB minimum can't overflow"
+        );
+    }
+}
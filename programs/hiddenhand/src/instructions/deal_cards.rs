@@ -3,7 +3,9 @@ use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::events::{BlindsPosted, Misdeal};
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::{seats_from, DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
 pub struct DealAllCards<'info> {
@@ -21,7 +23,8 @@ pub struct DealAllCards<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -53,7 +56,7 @@ pub struct DealAllCards<'info> {
 /// Authority can call immediately, anyone else must wait for timeout
 /// remaining_accounts should contain all OTHER player seats (not SB/BB)
 pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
-    let table = &ctx.accounts.table;
+    let table = &mut ctx.accounts.table;
     let hand_state = &mut ctx.accounts.hand_state;
     let deck_state = &mut ctx.accounts.deck_state;
     let sb_seat = &mut ctx.accounts.sb_seat;
@@ -64,10 +67,10 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
     // Authorization check: authority can call immediately, others must wait for timeout
     let is_authority = table.authority == caller.key();
     if !is_authority {
-        let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+        let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
         require!(
             elapsed >= DEAL_TIMEOUT_SECONDS,
-            HiddenHandError::UnauthorizedAuthority
+            HiddenHandError::DealNotTimedOut
         );
         msg!("Non-authority dealing cards after {} seconds timeout", elapsed);
     }
@@ -78,7 +81,8 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
         HiddenHandError::DuplicateAccount
     );
 
-    // Security: Check for duplicate accounts in remaining_accounts
+    // Security: Check for duplicate accounts in remaining_accounts,
+    // including a repeat of the SB or BB account
     let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
     seen_keys.insert(sb_seat.key());
     seen_keys.insert(bb_seat.key());
@@ -88,6 +92,9 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
         }
     }
 
+    // Blinds go to the seats start_hand recorded, whichever way round they were passed
+    let (sb_seat, bb_seat) = order_blind_seats(hand_state, sb_seat, bb_seat)?;
+
     // Get program ID for validation
     let program_id = crate::ID;
     let table_key = table.key();
@@ -108,6 +115,15 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
         HiddenHandError::HandNotInProgress
     );
 
+    // Misdeal check, before any blind is posted
+    let seats_present = seats_present(
+        &[sb_seat.seat_index, bb_seat.seat_index],
+        ctx.remaining_accounts,
+        &table_key,
+        &program_id,
+    );
+    check_deal(hand_state, seats_present)?;
+
     // Generate pseudorandom seed from slot hashes
     // In production, this would use Inco's e_rand()
     let slot_hash = clock.slot;
@@ -130,63 +146,76 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
     }
     deck_state.is_shuffled = true;
 
+    // Variants with a smaller board leave the unused community slots undealt
+    for i in hand_state.community_card_count as usize..COMMUNITY_CARD_COUNT {
+        deck_state.set_community_handle(i, CARD_SENTINEL as u128);
+    }
+
     // Store community cards in deck_state (first 5 cards)
     // They remain hidden in hand_state until revealed during phase transitions
-    // Cards 0-4 are community cards, stored in deck_state.cards[0..5]
-    // hand_state.community_cards uses 255 to indicate hidden cards
-    hand_state.community_cards = vec![255, 255, 255, 255, 255];
+    // Community cards are read back with deck_state.community_handle(0..COMMUNITY_CARD_COUNT)
+    // hand_state.community_cards uses CARD_SENTINEL to indicate hidden cards
+    hand_state.community_cards = vec![CARD_SENTINEL; COMMUNITY_CARD_COUNT];
     hand_state.community_revealed = 0;
-    deck_state.deal_index = 5; // Community cards reserved at indices 0-4
+    deck_state.deal_index = deck_state.hole_index(0) as u8; // Community (and burn) cards reserved ahead of the hole cards
 
     // Track seat indices and active player count
     let sb_index = sb_seat.seat_index;
     let bb_index = bb_seat.seat_index;
     let mut active_players = hand_state.active_players;
     let mut active_count = 0u8;
-    let mut deal_idx = deck_state.deal_index as usize;
-
-    // Deal to SB if they have chips
-    if sb_seat.chips > 0 {
-        // Reset bet tracking for new hand before posting blind
-        sb_seat.current_bet = 0;
-        sb_seat.total_bet_this_hand = 0;
-        sb_seat.has_acted = false;
-
-        let sb_amount = sb_seat.place_bet(table.small_blind);
-        hand_state.pot = hand_state.pot.saturating_add(sb_amount);
-        sb_seat.status = PlayerStatus::Playing;
-        sb_seat.hole_card_1 = deck[deal_idx] as u128;
-        sb_seat.hole_card_2 = deck[deal_idx + 1] as u128;
-        deal_idx += 2;
+    let mut hole_slot = 0usize;
+    // Seats a forced bet put all-in (only acted on for bomb pot antes)
+    let mut all_in_seats = 0u8;
+    let mut sb_posted = 0u64;
+    let mut bb_posted = 0u64;
+
+    // Deal to SB if they have chips and aren't away
+    if sb_seat.can_be_dealt() {
+        // Clear last hand's bets and cards before posting blind
+        sb_seat.clear_for_new_hand();
+
+        let sb_amount = sb_seat.place_bet(hand_state.forced_bet(table.small_blind))?;
+        hand_state.add_to_pot(sb_index, sb_amount)?;
+        sb_posted = sb_amount;
+        if sb_seat.chips == 0 {
+            all_in_seats |= 1 << sb_index;
+        }
+        sb_seat.deal_in();
+        sb_seat.hole_card_1 = deck_state.hole_handle(hole_slot);
+        sb_seat.hole_card_2 = deck_state.hole_handle(hole_slot + 1);
+        hole_slot += HOLE_CARDS;
         active_count += 1;
         msg!("SB (seat {}) posts {} and receives cards", sb_index, sb_amount);
     } else {
-        // Remove from active players - no chips
+        // Remove from active players - away or no chips
         active_players &= !(1 << sb_index);
-        sb_seat.status = PlayerStatus::Sitting;
-        msg!("SB (seat {}) has no chips - sitting out", sb_index);
+        sb_seat.sit_out_hand();
+        msg!("SB (seat {}) is away or has no chips - sitting out", sb_index);
     }
 
-    // Deal to BB if they have chips
-    if bb_seat.chips > 0 {
-        // Reset bet tracking for new hand before posting blind
-        bb_seat.current_bet = 0;
-        bb_seat.total_bet_this_hand = 0;
-        bb_seat.has_acted = false;
-
-        let bb_amount = bb_seat.place_bet(table.big_blind);
-        hand_state.pot = hand_state.pot.saturating_add(bb_amount);
-        bb_seat.status = PlayerStatus::Playing;
-        bb_seat.hole_card_1 = deck[deal_idx] as u128;
-        bb_seat.hole_card_2 = deck[deal_idx + 1] as u128;
-        deal_idx += 2;
+    // Deal to BB if they have chips and aren't away
+    if bb_seat.can_be_dealt() {
+        // Clear last hand's bets and cards before posting blind
+        bb_seat.clear_for_new_hand();
+
+        let bb_amount = bb_seat.place_bet(hand_state.forced_bet(table.big_blind))?;
+        hand_state.add_to_pot(bb_index, bb_amount)?;
+        bb_posted = bb_amount;
+        if bb_seat.chips == 0 {
+            all_in_seats |= 1 << bb_index;
+        }
+        bb_seat.deal_in();
+        bb_seat.hole_card_1 = deck_state.hole_handle(hole_slot);
+        bb_seat.hole_card_2 = deck_state.hole_handle(hole_slot + 1);
+        hole_slot += HOLE_CARDS;
         active_count += 1;
         msg!("BB (seat {}) posts {} and receives cards", bb_index, bb_amount);
     } else {
-        // Remove from active players - no chips
+        // Remove from active players - away or no chips
         active_players &= !(1 << bb_index);
-        bb_seat.status = PlayerStatus::Sitting;
-        msg!("BB (seat {}) has no chips - sitting out", bb_index);
+        bb_seat.sit_out_hand();
+        msg!("BB (seat {}) is away or has no chips - sitting out", bb_index);
     }
 
     // Deal to other players via remaining_accounts
@@ -201,10 +230,13 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
             let seat = PlayerSeat::try_deserialize(&mut &data[..])?;
 
             // Security check 2: Verify this seat belongs to this table
-            // Skip SB and BB (already handled)
-            if seat.table == table_key &&
-               seat.seat_index != sb_index &&
-               seat.seat_index != bb_index {
+            if seat.table == table_key {
+                // SB and BB are dealt from their named accounts - a remaining
+                // account for either seat is a client error, not something to skip
+                require!(
+                    seat.seat_index != sb_index && seat.seat_index != bb_index,
+                    HiddenHandError::DuplicateAccount
+                );
 
                 // Security check 3: Verify PDA derivation
                 let (expected_pda, _) = Pubkey::find_program_address(
@@ -217,27 +249,34 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
                 }
 
                 let seat_index = seat.seat_index;
-                let has_chips = seat.chips > 0;
+                let dealable = seat.can_be_dealt();
                 drop(data);
 
                 let mut data = account_info.try_borrow_mut_data()?;
                 let mut seat = PlayerSeat::try_deserialize(&mut &data[..])?;
 
-                if has_chips {
-                    // Player has chips - deal cards
-                    seat.hole_card_1 = deck[deal_idx] as u128;
-                    seat.hole_card_2 = deck[deal_idx + 1] as u128;
-                    seat.status = PlayerStatus::Playing;
-                    seat.current_bet = 0;
-                    seat.total_bet_this_hand = 0;
-                    deal_idx += 2;
+                if dealable {
+                    // Player has chips and is here - deal cards
+                    seat.clear_for_new_hand();
+                    if hand_state.is_bomb_pot() {
+                        let ante = seat.place_bet(hand_state.bomb_pot_ante)?;
+                        hand_state.add_to_pot(seat_index, ante)?;
+                        if seat.chips == 0 {
+                            all_in_seats |= 1 << seat_index;
+                        }
+                        msg!("Seat {} antes {}", seat_index, ante);
+                    }
+                    seat.hole_card_1 = deck_state.hole_handle(hole_slot);
+                    seat.hole_card_2 = deck_state.hole_handle(hole_slot + 1);
+                    seat.deal_in();
+                    hole_slot += HOLE_CARDS;
                     active_count += 1;
                     msg!("Dealt hole cards to seat {}", seat_index);
                 } else {
-                    // Player has no chips - sit them out
+                    // Player is away or has no chips - sit them out
                     active_players &= !(1 << seat_index);
-                    seat.status = PlayerStatus::Sitting;
-                    msg!("Seat {} has no chips - sitting out", seat_index);
+                    seat.sit_out_hand();
+                    msg!("Seat {} is away or has no chips - sitting out", seat_index);
                 }
 
                 seat.try_serialize(&mut *data)?;
@@ -248,7 +287,7 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
     // Update hand state with actual active players
     hand_state.active_players = active_players;
     hand_state.active_count = active_count;
-    deck_state.deal_index = deal_idx as u8;
+    deck_state.deal_index = deck_state.hole_index(hole_slot) as u8;
 
     // Verify we have enough active players
     require!(
@@ -257,20 +296,32 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
     );
 
     // Find first active player to act (may need to skip players with no chips)
-    let mut action_pos = hand_state.action_on;
-    for _ in 0..table.max_players {
-        if (active_players & (1 << action_pos)) != 0 {
-            break;
-        }
-        action_pos = (action_pos + 1) % table.max_players;
-    }
-    hand_state.action_on = action_pos;
+    hand_state.action_on = seats_from(hand_state.action_on, table.max_players)
+        .find(|&seat| active_players & (1 << seat) != 0)
+        .unwrap_or(hand_state.action_on);
 
     // Advance to PreFlop
     hand_state.phase = GamePhase::PreFlop;
     hand_state.last_action_time = clock.unix_timestamp;
     hand_state.all_in_players = 0; // No one is all-in yet
 
+    if hand_state.is_bomb_pot() {
+        hand_state.skip_preflop_betting(all_in_seats);
+        msg!("Bomb pot dealt. Pot: {}. Awaiting flop reveal", hand_state.pot);
+        return Ok(());
+    }
+
+    emit!(BlindsPosted {
+        table_id: table.table_id,
+        hand_number: hand_state.hand_number,
+        small_blind_seat: sb_index,
+        small_blind: sb_posted,
+        big_blind_seat: bb_index,
+        big_blind: bb_posted,
+        first_to_act: hand_state.action_on,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
         "Cards dealt. Pot: {}. Phase: PreFlop. Action on seat {}. Active players: {}",
         hand_state.pot,
@@ -280,3 +331,150 @@ pub fn handler(ctx: Context<DealAllCards>) -> Result<()> {
 
     Ok(())
 }
+
+/// Put the two blind seat accounts in (small blind, big blind) order as
+/// start_hand recorded them. Either order is accepted; any other pair isn't
+pub(crate) fn order_blind_seats<'a, 'info>(
+    hand_state: &HandState,
+    first: &'a mut Account<'info, PlayerSeat>,
+    second: &'a mut Account<'info, PlayerSeat>,
+) -> Result<(&'a mut Account<'info, PlayerSeat>, &'a mut Account<'info, PlayerSeat>)> {
+    let (sb_seat, bb_seat) = if first.seat_index == hand_state.bb_seat {
+        (second, first)
+    } else {
+        (first, second)
+    };
+    require!(
+        sb_seat.seat_index == hand_state.sb_seat && bb_seat.seat_index == hand_state.bb_seat,
+        HiddenHandError::BlindSeatMismatch
+    );
+    Ok((sb_seat, bb_seat))
+}
+
+/// Bitmap of this table's seats passed to a deal (explicit blind seats plus valid seat accounts)
+pub(crate) fn seats_present(
+    blind_seats: &[u8],
+    seat_accounts: &[AccountInfo],
+    table_key: &Pubkey,
+    program_id: &Pubkey,
+) -> u8 {
+    let mut present = blind_seats.iter().fold(0u8, |bits, seat| bits | (1 << seat));
+    for account_info in seat_accounts {
+        if let Some(seat) = validate_seat_account(account_info, table_key, program_id) {
+            present |= 1 << seat.seat_index;
+        }
+    }
+    present
+}
+
+/// Check a deal from deal_cards or deal_cards_encrypted can go ahead. A seat in
+/// the hand missing from the accounts is the caller's mistake, so the deal fails
+/// and can be retried with every seat
+pub(crate) fn check_deal(hand_state: &HandState, seats_present: u8) -> Result<()> {
+    let missing_seats = hand_state.misdeal_seats(seats_present);
+    if missing_seats != 0 {
+        msg!("Deal is missing seats {:#08b} - pass every seat in the hand", missing_seats);
+        return Err(HiddenHandError::MissingSeatAccounts.into());
+    }
+    Ok(())
+}
+
+/// Abort a misdealt hand and return the table to Waiting so a new hand can start.
+/// Any blinds posted must already be back with the seats
+pub(crate) fn abort_misdeal(table: &mut Table, hand_state: &mut HandState, seats_present: u8, now: i64) {
+    let missing_seats = hand_state.misdeal_seats(seats_present);
+    hand_state.abort_misdeal();
+
+    table.status = TableStatus::Waiting;
+    table.last_ready_time = now;
+
+    emit!(Misdeal {
+        table_id: table.table_id,
+        hand_number: hand_state.hand_number,
+        missing_seats,
+        timestamp: now,
+    });
+
+    msg!(
+        "Misdeal on hand #{} (seats {:#08b} not dealt) - hand aborted, blinds returned",
+        hand_state.hand_number,
+        missing_seats
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{hand_state, player_seat, table_state};
+
+    /// Test a partial deal is detected as a misdeal, and a deal that hands out
+    /// the wrong cards returns the blinds and aborts cleanly
+    #[test]
+    fn test_misdeal_detection() {
+        use crate::state::PlayerStatus;
+
+        let mut hand = hand_state(GamePhase::Dealing);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+
+        // All three seats passed to the deal
+        assert!(!hand.is_misdeal(0b111));
+        // Extra (non-playing) seats don't matter
+        assert!(!hand.is_misdeal(0b1111));
+
+        // Seat 2 left out of the deal
+        assert!(hand.is_misdeal(0b011));
+        assert_eq!(hand.misdeal_seats(0b011), 0b100);
+
+        // Two hole cards to each seat dealt is a good deal, even short of a seat
+        // that couldn't be dealt in
+        assert!(!hand.is_wrong_deal(0b111, 6));
+        assert!(!hand.is_wrong_deal(0b011, 4));
+        // A seat outside the hand was dealt
+        assert!(hand.is_wrong_deal(0b1011, 8));
+
+        // The big blind's account is passed twice: it is cleared and posts
+        // again, and the deal runs two cards long
+        let mut table = table_state();
+        table.status = TableStatus::Playing;
+        let mut seats = [player_seat(0, 1_000), player_seat(1, 1_000), player_seat(2, 1_000)];
+        for (seat, posts) in seats.iter_mut().zip([vec![], vec![1], vec![2, 2]]) {
+            seat.clear_for_new_hand();
+            for blind in posts {
+                seat.clear_for_new_hand();
+                let posted = seat.place_bet(blind).unwrap();
+                hand.add_to_pot(seat.seat_index, posted).unwrap();
+            }
+            seat.deal_in();
+        }
+        let hole_cards_dealt = 8;
+        assert_eq!(hand.pot, 5);
+        assert_eq!(seats[2].total_bet_this_hand, 2);
+        assert!(hand.is_wrong_deal(0b111, hole_cards_dealt));
+
+        // Refunds come from the pot's record, once per seat
+        let mut refunds = vec![];
+        for seat in seats.iter_mut() {
+            let refund = hand.take_from_pot(seat.seat_index, u64::MAX).unwrap();
+            seat.refund_misdeal(refund);
+            refunds.push(refund);
+        }
+        assert_eq!(refunds, vec![0, 1, 4]);
+        assert_eq!(hand.take_from_pot(2, u64::MAX).unwrap(), 0);
+        abort_misdeal(&mut table, &mut hand, 0b111, 1_000);
+
+        for seat in &seats {
+            assert_eq!(seat.chips, 1_000);
+            assert_eq!(seat.total_bet_this_hand, 0);
+            assert_eq!(seat.status, PlayerStatus::Sitting);
+            assert_eq!(seat.hands_played, 0);
+            assert!(seat.cards_not_dealt());
+        }
+        assert_eq!(hand.phase, GamePhase::Settled);
+        assert_eq!(hand.pot, 0);
+        assert_eq!(hand.active_count, 0);
+        assert!(hand.contributions().is_empty());
+        assert_eq!(table.status, TableStatus::Waiting);
+        assert_eq!(table.last_ready_time, 1_000);
+    }
+}
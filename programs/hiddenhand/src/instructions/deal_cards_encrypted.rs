@@ -17,8 +17,10 @@ use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::inco_cpi::{self, INCO_PROGRAM_ID};
-use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::events::BlindsPosted;
+use crate::inco_cpi::{self, EncryptedCard, INCO_PROGRAM_ID};
+use crate::instructions::deal_cards::{check_deal, order_blind_seats, seats_present};
+use crate::state::{seats_from, DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
 pub struct DealCardsEncrypted<'info> {
@@ -36,7 +38,8 @@ pub struct DealCardsEncrypted<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -74,7 +77,7 @@ pub struct DealCardsEncrypted<'info> {
 /// Deal cards with atomic encryption - cards are NEVER plaintext on-chain
 /// remaining_accounts should contain all OTHER player seats (not SB/BB)
 pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
-    let table = &ctx.accounts.table;
+    let table = &mut ctx.accounts.table;
     let hand_state = &mut ctx.accounts.hand_state;
     let deck_state = &mut ctx.accounts.deck_state;
     let sb_seat = &mut ctx.accounts.sb_seat;
@@ -85,10 +88,10 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
     // Authorization check: authority can call immediately, others must wait for timeout
     let is_authority = table.authority == caller.key();
     if !is_authority {
-        let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+        let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
         require!(
             elapsed >= DEAL_TIMEOUT_SECONDS,
-            HiddenHandError::UnauthorizedAuthority
+            HiddenHandError::DealNotTimedOut
         );
         msg!("Non-authority dealing cards after {} seconds timeout", elapsed);
     }
@@ -99,7 +102,8 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
         HiddenHandError::DuplicateAccount
     );
 
-    // Security: Check for duplicate accounts in remaining_accounts
+    // Security: Check for duplicate accounts in remaining_accounts,
+    // including a repeat of the SB or BB account
     let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
     seen_keys.insert(sb_seat.key());
     seen_keys.insert(bb_seat.key());
@@ -109,6 +113,9 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
         }
     }
 
+    // Blinds go to the seats start_hand recorded, whichever way round they were passed
+    let (sb_seat, bb_seat) = order_blind_seats(hand_state, sb_seat, bb_seat)?;
+
     // Get program ID for validation
     let program_id = crate::ID;
     let table_key = table.key();
@@ -129,6 +136,15 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
         HiddenHandError::HandNotInProgress
     );
 
+    // Misdeal check before any blind is posted or card encrypted
+    let seats_present = seats_present(
+        &[sb_seat.seat_index, bb_seat.seat_index],
+        ctx.remaining_accounts,
+        &table_key,
+        &program_id,
+    );
+    check_deal(hand_state, seats_present)?;
+
     msg!("Dealing cards with atomic Inco encryption...");
 
     // Generate pseudorandom seed from slot hashes
@@ -147,19 +163,23 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
     // Store shuffled deck as encrypted handles
     // Community cards (indices 0-4) will be encrypted too for consistency
     deck_state.is_shuffled = true;
-    hand_state.community_cards = vec![255, 255, 255, 255, 255];
+    hand_state.community_cards = vec![CARD_SENTINEL; COMMUNITY_CARD_COUNT];
     hand_state.community_revealed = 0;
-    deck_state.deal_index = 5; // Community cards reserved at indices 0-4
+    deck_state.deal_index = deck_state.hole_index(0) as u8; // Community (and burn) cards reserved ahead of the hole cards
 
     // Get signer for Inco CPI
     let caller_info = ctx.accounts.caller.to_account_info();
 
     // Store community cards as PLAINTEXT in low byte of u128
     // Community cards are revealed to everyone (flop/turn/river), so no encryption needed
-    // This allows player_action.rs to extract them with (deck_state.cards[i] & 0xFF) as u8
+    // player_action reveals them inline via DeckState::plaintext_cards, and reveal_community
+    // checks them directly rather than expecting an Inco attestation
+    // Variants with a smaller board leave the unused community slots undealt
     msg!("Storing community cards (plaintext - they'll be public when revealed)...");
-    for i in 0..5 {
-        deck_state.cards[i] = deck[i] as u128;
+    let community_count = hand_state.community_card_count as usize;
+    for i in 0..COMMUNITY_CARD_COUNT {
+        let card = deck[deck_state.community_index(i)];
+        deck_state.set_community_handle(i, if i < community_count { card as u128 } else { CARD_SENTINEL as u128 });
     }
 
     // Track seat indices and active player count
@@ -167,71 +187,75 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
     let bb_index = bb_seat.seat_index;
     let mut active_players = hand_state.active_players;
     let mut active_count = 0u8;
-    let mut deal_idx = 5usize; // Start after community cards
-
-    // Deal to SB if they have chips
-    if sb_seat.chips > 0 {
-        sb_seat.current_bet = 0;
-        sb_seat.total_bet_this_hand = 0;
-        sb_seat.has_acted = false;
-        sb_seat.cards_revealed = false;
-        sb_seat.revealed_card_1 = 255;
-        sb_seat.revealed_card_2 = 255;
-
-        let sb_amount = sb_seat.place_bet(table.small_blind);
-        hand_state.pot = hand_state.pot.saturating_add(sb_amount);
-        sb_seat.status = PlayerStatus::Playing;
+    let mut hole_slot = 0usize; // Hole cards start after the community slots
+    // Seats a forced bet put all-in (only acted on for bomb pot antes)
+    let mut all_in_seats = 0u8;
+    let mut sb_posted = 0u64;
+    let mut bb_posted = 0u64;
+
+    // Deal to SB if they have chips and aren't away
+    if sb_seat.can_be_dealt() {
+        // Clear last hand's bets and cards before posting blind
+        sb_seat.clear_for_new_hand();
+
+        let sb_amount = sb_seat.place_bet(hand_state.forced_bet(table.small_blind))?;
+        hand_state.add_to_pot(sb_index, sb_amount)?;
+        sb_posted = sb_amount;
+        if sb_seat.chips == 0 {
+            all_in_seats |= 1 << sb_index;
+        }
+        sb_seat.deal_in();
 
         // ATOMIC ENCRYPTION: Encrypt cards immediately
         msg!("Encrypting cards for SB (seat {})...", sb_index);
-        let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx])?;
-        let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx + 1])?;
-        sb_seat.hole_card_1 = encrypted1.unwrap();
-        sb_seat.hole_card_2 = encrypted2.unwrap();
+        let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot)])?.handle()?;
+        let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot + 1)])?.handle()?;
+        sb_seat.hole_card_1 = encrypted1;
+        sb_seat.hole_card_2 = encrypted2;
 
         // Also store encrypted in deck for consistency
-        deck_state.cards[deal_idx] = encrypted1.unwrap();
-        deck_state.cards[deal_idx + 1] = encrypted2.unwrap();
+        deck_state.set_hole_handle(hole_slot, encrypted1);
+        deck_state.set_hole_handle(hole_slot + 1, encrypted2);
 
-        deal_idx += 2;
+        hole_slot += HOLE_CARDS;
         active_count += 1;
         msg!("SB (seat {}) posts {} and receives encrypted cards", sb_index, sb_amount);
     } else {
         active_players &= !(1 << sb_index);
-        sb_seat.status = PlayerStatus::Sitting;
-        msg!("SB (seat {}) has no chips - sitting out", sb_index);
+        sb_seat.sit_out_hand();
+        msg!("SB (seat {}) is away or has no chips - sitting out", sb_index);
     }
 
-    // Deal to BB if they have chips
-    if bb_seat.chips > 0 {
-        bb_seat.current_bet = 0;
-        bb_seat.total_bet_this_hand = 0;
-        bb_seat.has_acted = false;
-        bb_seat.cards_revealed = false;
-        bb_seat.revealed_card_1 = 255;
-        bb_seat.revealed_card_2 = 255;
+    // Deal to BB if they have chips and aren't away
+    if bb_seat.can_be_dealt() {
+        // Clear last hand's bets and cards before posting blind
+        bb_seat.clear_for_new_hand();
 
-        let bb_amount = bb_seat.place_bet(table.big_blind);
-        hand_state.pot = hand_state.pot.saturating_add(bb_amount);
-        bb_seat.status = PlayerStatus::Playing;
+        let bb_amount = bb_seat.place_bet(hand_state.forced_bet(table.big_blind))?;
+        hand_state.add_to_pot(bb_index, bb_amount)?;
+        bb_posted = bb_amount;
+        if bb_seat.chips == 0 {
+            all_in_seats |= 1 << bb_index;
+        }
+        bb_seat.deal_in();
 
         // ATOMIC ENCRYPTION: Encrypt cards immediately
         msg!("Encrypting cards for BB (seat {})...", bb_index);
-        let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx])?;
-        let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx + 1])?;
-        bb_seat.hole_card_1 = encrypted1.unwrap();
-        bb_seat.hole_card_2 = encrypted2.unwrap();
+        let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot)])?.handle()?;
+        let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot + 1)])?.handle()?;
+        bb_seat.hole_card_1 = encrypted1;
+        bb_seat.hole_card_2 = encrypted2;
 
-        deck_state.cards[deal_idx] = encrypted1.unwrap();
-        deck_state.cards[deal_idx + 1] = encrypted2.unwrap();
+        deck_state.set_hole_handle(hole_slot, encrypted1);
+        deck_state.set_hole_handle(hole_slot + 1, encrypted2);
 
-        deal_idx += 2;
+        hole_slot += HOLE_CARDS;
         active_count += 1;
         msg!("BB (seat {}) posts {} and receives encrypted cards", bb_index, bb_amount);
     } else {
         active_players &= !(1 << bb_index);
-        bb_seat.status = PlayerStatus::Sitting;
-        msg!("BB (seat {}) has no chips - sitting out", bb_index);
+        bb_seat.sit_out_hand();
+        msg!("BB (seat {}) is away or has no chips - sitting out", bb_index);
     }
 
     // Deal to other players via remaining_accounts
@@ -246,9 +270,13 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
             let seat = PlayerSeat::try_deserialize(&mut &data[..])?;
 
             // Security check 2: Verify this seat belongs to this table
-            if seat.table == table_key &&
-               seat.seat_index != sb_index &&
-               seat.seat_index != bb_index {
+            if seat.table == table_key {
+                // SB and BB are dealt from their named accounts - a remaining
+                // account for either seat is a client error, not something to skip
+                require!(
+                    seat.seat_index != sb_index && seat.seat_index != bb_index,
+                    HiddenHandError::DuplicateAccount
+                );
 
                 // Security check 3: Verify PDA derivation
                 let (expected_pda, _) = Pubkey::find_program_address(
@@ -261,39 +289,42 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
                 }
 
                 let seat_index = seat.seat_index;
-                let has_chips = seat.chips > 0;
+                let dealable = seat.can_be_dealt();
                 drop(data);
 
                 let mut data = account_info.try_borrow_mut_data()?;
                 let mut seat = PlayerSeat::try_deserialize(&mut &data[..])?;
 
-                if has_chips {
+                if dealable {
                     // ATOMIC ENCRYPTION: Encrypt cards immediately
                     msg!("Encrypting cards for seat {}...", seat_index);
-                    let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx])?;
-                    let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deal_idx + 1])?;
-
-                    seat.hole_card_1 = encrypted1.unwrap();
-                    seat.hole_card_2 = encrypted2.unwrap();
-                    seat.status = PlayerStatus::Playing;
-                    seat.current_bet = 0;
-                    seat.total_bet_this_hand = 0;
-                    seat.has_acted = false;
-                    seat.cards_revealed = false;
-                    seat.revealed_card_1 = 255;
-                    seat.revealed_card_2 = 255;
+                    let encrypted1 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot)])?.handle()?;
+                    let encrypted2 = inco_cpi::encrypt_card(&caller_info, deck[deck_state.hole_index(hole_slot + 1)])?.handle()?;
+
+                    seat.clear_for_new_hand();
+                    if hand_state.is_bomb_pot() {
+                        let ante = seat.place_bet(hand_state.bomb_pot_ante)?;
+                        hand_state.add_to_pot(seat_index, ante)?;
+                        if seat.chips == 0 {
+                            all_in_seats |= 1 << seat_index;
+                        }
+                        msg!("Seat {} antes {}", seat_index, ante);
+                    }
+                    seat.hole_card_1 = encrypted1;
+                    seat.hole_card_2 = encrypted2;
+                    seat.deal_in();
 
                     // Store in deck too
-                    deck_state.cards[deal_idx] = encrypted1.unwrap();
-                    deck_state.cards[deal_idx + 1] = encrypted2.unwrap();
+                    deck_state.set_hole_handle(hole_slot, encrypted1);
+                    deck_state.set_hole_handle(hole_slot + 1, encrypted2);
 
-                    deal_idx += 2;
+                    hole_slot += HOLE_CARDS;
                     active_count += 1;
                     msg!("Dealt encrypted hole cards to seat {}", seat_index);
                 } else {
                     active_players &= !(1 << seat_index);
-                    seat.status = PlayerStatus::Sitting;
-                    msg!("Seat {} has no chips - sitting out", seat_index);
+                    seat.sit_out_hand();
+                    msg!("Seat {} is away or has no chips - sitting out", seat_index);
                 }
 
                 seat.try_serialize(&mut *data)?;
@@ -301,10 +332,18 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
         }
     }
 
+    // Every dealt handle should be unique - flag a broken encryption
+    let dealt_handles: Vec<EncryptedCard> = (0..hole_slot)
+        .map(|slot| EncryptedCard::wrap(deck_state.hole_handle(slot)))
+        .collect();
+    if let Some(repeated) = inco_cpi::find_repeated_handle(&dealt_handles) {
+        msg!("WARNING: handle {} was dealt more than once - encryption may have failed", repeated.unwrap());
+    }
+
     // Update hand state with actual active players
     hand_state.active_players = active_players;
     hand_state.active_count = active_count;
-    deck_state.deal_index = deal_idx as u8;
+    deck_state.deal_index = deck_state.hole_index(hole_slot) as u8;
 
     // Verify we have enough active players
     require!(
@@ -313,20 +352,32 @@ pub fn handler(ctx: Context<DealCardsEncrypted>) -> Result<()> {
     );
 
     // Find first active player to act
-    let mut action_pos = hand_state.action_on;
-    for _ in 0..table.max_players {
-        if (active_players & (1 << action_pos)) != 0 {
-            break;
-        }
-        action_pos = (action_pos + 1) % table.max_players;
-    }
-    hand_state.action_on = action_pos;
+    hand_state.action_on = seats_from(hand_state.action_on, table.max_players)
+        .find(|&seat| active_players & (1 << seat) != 0)
+        .unwrap_or(hand_state.action_on);
 
     // Advance to PreFlop
     hand_state.phase = GamePhase::PreFlop;
     hand_state.last_action_time = clock.unix_timestamp;
     hand_state.all_in_players = 0;
 
+    if hand_state.is_bomb_pot() {
+        hand_state.skip_preflop_betting(all_in_seats);
+        msg!("Bomb pot dealt with encryption. Pot: {}. Awaiting flop reveal", hand_state.pot);
+        return Ok(());
+    }
+
+    emit!(BlindsPosted {
+        table_id: table.table_id,
+        hand_number: hand_state.hand_number,
+        small_blind_seat: sb_index,
+        small_blind: sb_posted,
+        big_blind_seat: bb_index,
+        big_blind: bb_posted,
+        first_to_act: hand_state.action_on,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
         "Cards dealt with encryption. Pot: {}. Phase: PreFlop. Action on seat {}. Active: {}",
         hand_state.pot,
@@ -19,8 +19,8 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::inco_cpi::{self, INCO_PROGRAM_ID};
-use crate::state::{GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::inco_cpi::{self, EncryptedCard, INCO_PROGRAM_ID};
+use crate::state::{GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 /// Phase 1: Encrypt a player's hole cards
 ///
@@ -55,7 +55,7 @@ pub struct EncryptHoleCards<'info> {
         mut,
         seeds = [SEAT_SEED, table.key().as_ref(), &[seat_index]],
         bump = player_seat.bump,
-        constraint = player_seat.status == PlayerStatus::Playing @ HiddenHandError::PlayerFolded
+        constraint = player_seat.is_in_hand() @ HiddenHandError::PlayerFolded
     )]
     pub player_seat: Account<'info, PlayerSeat>,
 
@@ -78,6 +78,12 @@ pub fn handler(ctx: Context<EncryptHoleCards>, _seat_index: u8) -> Result<()> {
         HiddenHandError::HandNotInProgress
     );
 
+    // The not-dealt sentinel (255) would otherwise pass for an encrypted handle
+    require!(
+        !player_seat.cards_not_dealt(),
+        HiddenHandError::CardsNotDealt
+    );
+
     // Check if cards look like plaintext (0-51) vs encrypted handle (large number)
     let card1 = player_seat.hole_card_1;
     let card2 = player_seat.hole_card_2;
@@ -99,14 +105,18 @@ pub fn handler(ctx: Context<EncryptHoleCards>, _seat_index: u8) -> Result<()> {
     let authority_info = ctx.accounts.authority.to_account_info();
 
     // Encrypt card 1
-    let encrypted1 = inco_cpi::encrypt_card(&authority_info, card1 as u8)?;
+    let encrypted1 = inco_cpi::encrypt_card(&authority_info, card1 as u8)?.handle()?;
 
     // Encrypt card 2
-    let encrypted2 = inco_cpi::encrypt_card(&authority_info, card2 as u8)?;
+    let encrypted2 = inco_cpi::encrypt_card(&authority_info, card2 as u8)?.handle()?;
+
+    if EncryptedCard::wrap(encrypted1).same_handle(&EncryptedCard::wrap(encrypted2)) {
+        msg!("WARNING: both hole cards got handle {} - encryption may have failed", encrypted1);
+    }
 
     // Update seat with encrypted handles
-    player_seat.hole_card_1 = encrypted1.unwrap();
-    player_seat.hole_card_2 = encrypted2.unwrap();
+    player_seat.hole_card_1 = encrypted1;
+    player_seat.hole_card_2 = encrypted2;
 
     msg!(
         "Encrypted cards for seat {}: {} -> handle {}, {} -> handle {}",
@@ -148,9 +158,9 @@ pub struct GrantCardAllowance<'info> {
     #[account(
         seeds = [SEAT_SEED, table.key().as_ref(), &[seat_index]],
         bump = player_seat.bump,
-        constraint = player_seat.status == PlayerStatus::Playing @ HiddenHandError::PlayerFolded,
+        constraint = player_seat.is_in_hand() @ HiddenHandError::PlayerFolded,
         // Cards must be encrypted (handles > 51)
-        constraint = player_seat.hole_card_1 > 51 @ HiddenHandError::CardsNotDealt,
+        constraint = !player_seat.cards_not_dealt() && player_seat.hole_card_1 > 51 @ HiddenHandError::CardsNotDealt,
     )]
     pub player_seat: Account<'info, PlayerSeat>,
 
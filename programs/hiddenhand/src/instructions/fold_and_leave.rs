@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::events::{PlayerLeaving, SeatAvailable};
+use crate::instructions::player_action::{apply_fold, finish_action};
+use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
+
+#[derive(Accounts)]
+pub struct FoldAndLeave<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump,
+        has_one = player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+
+    /// Vault to withdraw from (SystemAccount validates System Program ownership)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Current hand - required while a hand is in progress
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
+    )]
+    pub hand_state: Option<Account<'info, HandState>>,
+
+    /// Current deck - required while a hand is in progress
+    #[account(
+        seeds = [DECK_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = deck_state.bump
+    )]
+    pub deck_state: Option<Account<'info, DeckState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Quit in one transaction: fold the seat if it is still live in the hand (in
+/// turn or not), then cash out. A folded seat's chips are no longer at stake,
+/// so the stack is returned right away rather than at the end of the hand.
+/// If this empties the hand before the flop, the hand is cancelled and the
+/// seat's bet is returned along with the stack
+pub fn handler(ctx: Context<FoldAndLeave>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player_seat = &mut ctx.accounts.player_seat;
+    let seat_index = player_seat.seat_index;
+    let clock = Clock::get()?;
+
+    let mut folded = false;
+    let mut refund = 0u64;
+    if table.status == TableStatus::Playing {
+        let hand_state = ctx
+            .accounts
+            .hand_state
+            .as_mut()
+            .ok_or(HiddenHandError::HandAccountsRequired)?;
+
+        if hand_state.is_player_active(seat_index) {
+            // Only a live seat in a betting round can fold - not while dealing,
+            // at showdown, or all-in with chips already committed
+            require!(
+                matches!(
+                    hand_state.phase,
+                    GamePhase::PreFlop | GamePhase::Flop | GamePhase::Turn | GamePhase::River
+                ) && player_seat.can_act(),
+                HiddenHandError::CannotLeaveDuringHand
+            );
+            let deck_state = ctx
+                .accounts
+                .deck_state
+                .as_ref()
+                .ok_or(HiddenHandError::HandAccountsRequired)?;
+
+            let on_the_action =
+                hand_state.action_on == seat_index && !hand_state.awaiting_community_reveal;
+            hand_state.mark_departed(seat_index);
+            apply_fold(hand_state, player_seat);
+            if on_the_action {
+                // Same as a fold through player_action: the action moves on
+                finish_action(
+                    hand_state,
+                    deck_state,
+                    player_seat,
+                    table.max_players,
+                    table.big_blind,
+                    clock.unix_timestamp,
+                )?;
+            }
+            folded = true;
+
+            // Last departure before the flop leaves one player with nobody to
+            // play: the hand is cancelled, so this seat's bet comes back too.
+            // The remaining player's bet returns when the hand settles
+            if hand_state.is_cancelled_by_departures() {
+                refund = hand_state.take_from_pot(seat_index, player_seat.total_bet_this_hand)?;
+                msg!("Hand cancelled - everyone else left - refunding {} to seat {}", refund, seat_index);
+            }
+        }
+    }
+
+    let chips_to_return = player_seat
+        .chips
+        .checked_add(refund)
+        .ok_or(HiddenHandError::ArithmeticOverflow)?;
+    let table_key = table.key();
+
+    // Transfer chips back to player from vault using CPI with PDA signer
+    if chips_to_return > 0 {
+        let vault_bump = ctx.bumps.vault;
+        let vault_seeds: &[&[u8]] = &[
+            VAULT_SEED,
+            table_key.as_ref(),
+            &[vault_bump],
+        ];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.player.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            chips_to_return,
+        )?;
+    }
+
+    // Update table
+    table.vacate_seat(seat_index);
+
+    emit!(PlayerLeaving {
+        table_id: table.table_id,
+        player: ctx.accounts.player.key(),
+        seat_index,
+        folded,
+        chips_returned: chips_to_return,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Offer the freed seat to the next waitlisted player
+    if let Some(next_player) = table.pop_waitlist() {
+        emit!(SeatAvailable {
+            table_id: table.table_id,
+            player: next_player,
+            seat_index,
+        });
+        msg!("Seat {} offered to waitlisted player {}", seat_index, next_player);
+    }
+
+    msg!(
+        "Player {} {}left seat {}, returned {} chips",
+        ctx.accounts.player.key(),
+        if folded { "folded and " } else { "" },
+        seat_index,
+        chips_to_return
+    );
+
+    Ok(())
+}
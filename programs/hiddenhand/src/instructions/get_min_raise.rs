@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{HandState, PlayerSeat, Table};
+
+/// Read-only view of the minimum legal raise for a seat
+#[derive(Accounts)]
+pub struct GetMinRaise<'info> {
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    /// The seat considering a raise (normally the seat the action is on)
+    #[account(
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+}
+
+/// Returns the minimum total bet the seat must reach to raise
+/// Uses the same rule player_action enforces, so clients and program agree
+pub fn handler(ctx: Context<GetMinRaise>) -> Result<u64> {
+    let hand_state = &ctx.accounts.hand_state;
+    let player_seat = &ctx.accounts.player_seat;
+
+    let min_total = hand_state.min_raise_to(player_seat.current_bet, player_seat.chips);
+
+    msg!(
+        "Seat {} minimum raise to {} (current bet {}, min raise {})",
+        player_seat.seat_index,
+        min_total,
+        hand_state.current_bet,
+        hand_state.min_raise
+    );
+
+    Ok(min_total)
+}
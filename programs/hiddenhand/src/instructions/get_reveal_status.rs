@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{GamePhase, HandState, PlayerSeat, Table};
+
+/// Read-only view of a seat's showdown reveal status
+#[derive(Accounts)]
+pub struct GetRevealStatus<'info> {
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    #[account(
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+}
+
+/// Reveal status returned to clients for showdown UIs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RevealStatus {
+    pub seat_index: u8,
+    pub cards_revealed: bool,
+    /// Revealed hole cards (None until reveal_cards succeeds)
+    pub revealed_card_1: Option<u8>,
+    pub revealed_card_2: Option<u8>,
+    /// Still owes a reveal at showdown
+    pub awaiting_reveal: bool,
+    /// REVEAL_TIMEOUT_SECONDS has passed, so timeout_reveal can muck this seat
+    pub can_be_mucked: bool,
+}
+
+impl RevealStatus {
+    /// Build the status for a seat at `now`
+    /// Mirrors the checks timeout_reveal enforces
+    pub fn for_seat(seat: &PlayerSeat, hand_state: &HandState, now: i64) -> Self {
+        let in_showdown = hand_state.phase == GamePhase::Showdown;
+        let awaiting_reveal = in_showdown && seat.is_in_hand() && !seat.cards_revealed;
+        let elapsed = hand_state.seconds_since_last_action(now);
+
+        let revealed = |card: u8| (seat.cards_revealed && card < 52).then_some(card);
+
+        Self {
+            seat_index: seat.seat_index,
+            cards_revealed: seat.cards_revealed,
+            revealed_card_1: revealed(seat.revealed_card_1),
+            revealed_card_2: revealed(seat.revealed_card_2),
+            awaiting_reveal,
+            can_be_mucked: awaiting_reveal && elapsed >= REVEAL_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+/// Returns whether the seat has revealed, its cards if so, and whether it can be mucked
+pub fn handler(ctx: Context<GetRevealStatus>) -> Result<RevealStatus> {
+    let clock = Clock::get()?;
+    let status = RevealStatus::for_seat(
+        &ctx.accounts.player_seat,
+        &ctx.accounts.hand_state,
+        clock.unix_timestamp,
+    );
+
+    msg!(
+        "Seat {} revealed: {}, awaiting reveal: {}, muckable: {}",
+        status.seat_index,
+        status.cards_revealed,
+        status.awaiting_reveal,
+        status.can_be_mucked
+    );
+
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{hand_state, player_seat};
+
+    /// Test reveal status for revealed, unrevealed and mucked-by-timeout seats
+    #[test]
+    fn test_reveal_status() {
+        use crate::state::PlayerStatus;
+
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.last_action_time = 1_000;
+
+        let mut revealed = player_seat(0, 1000);
+        revealed.cards_revealed = true;
+        revealed.revealed_card_1 = 12;
+        revealed.revealed_card_2 = 51;
+
+        let status = RevealStatus::for_seat(&revealed, &hand, 1_000 + REVEAL_TIMEOUT_SECONDS);
+        assert!(status.cards_revealed);
+        assert_eq!(status.revealed_card_1, Some(12));
+        assert_eq!(status.revealed_card_2, Some(51));
+        assert!(!status.awaiting_reveal);
+        assert!(!status.can_be_mucked);
+
+        let unrevealed = player_seat(1, 1000);
+        let status = RevealStatus::for_seat(&unrevealed, &hand, 1_000);
+        assert!(!status.cards_revealed);
+        assert_eq!(status.revealed_card_1, None);
+        assert_eq!(status.revealed_card_2, None);
+        assert!(status.awaiting_reveal);
+        assert!(!status.can_be_mucked);
+
+        // Muckable once the reveal timeout passes
+        let status = RevealStatus::for_seat(&unrevealed, &hand, 1_000 + REVEAL_TIMEOUT_SECONDS);
+        assert!(status.can_be_mucked);
+
+        // Folded seats have nothing to reveal
+        let mut folded = player_seat(2, 1000);
+        folded.status = PlayerStatus::Folded;
+        let status = RevealStatus::for_seat(&folded, &hand, 1_000 + REVEAL_TIMEOUT_SECONDS);
+        assert!(!status.awaiting_reveal);
+        assert!(!status.can_be_mucked);
+
+        // Outside showdown nobody owes a reveal
+        let preflop = hand_state(GamePhase::PreFlop);
+        let status = RevealStatus::for_seat(&unrevealed, &preflop, 1_000_000);
+        assert!(!status.awaiting_reveal);
+        assert!(!status.can_be_mucked);
+    }
+}
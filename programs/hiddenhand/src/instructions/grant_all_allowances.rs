@@ -0,0 +1,117 @@
+//! Grant hole card allowances to every active player in one transaction
+//!
+//! Batched version of `grant_card_allowance`: instead of one authority
+//! transaction per player, all seats are granted in a single call.
+//!
+//! remaining_accounts, 4 per player:
+//!   [seat_pda, allowance_card1, allowance_card2, player_wallet, ...]
+//! Allowance PDAs are derived from the seat's handles: [handle_bytes, player_pubkey]
+
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::inco_cpi::{self, INCO_PROGRAM_ID};
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::Table;
+
+/// Accounts passed per player in remaining_accounts
+pub const ACCOUNTS_PER_GRANT: usize = 4;
+
+#[derive(Accounts)]
+pub struct GrantAllAllowances<'info> {
+    /// The table authority - only authority can grant allowances
+    #[account(
+        mut,
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump,
+    )]
+    pub table: Account<'info, Table>,
+
+    /// The Inco Lightning program
+    /// CHECK: Verified by address constraint
+    #[account(address = INCO_PROGRAM_ID)]
+    pub inco_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: [seat, allowance1, allowance2, player] per player
+}
+
+/// Grant hole card allowances for all active players
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GrantAllAllowances<'info>>) -> Result<()> {
+    let table_key = ctx.accounts.table.key();
+    let program_id = crate::ID;
+
+    let groups = ctx.remaining_accounts.chunks_exact(ACCOUNTS_PER_GRANT);
+    require!(
+        groups.len() > 0 && groups.remainder().is_empty(),
+        HiddenHandError::InvalidRemainingAccounts
+    );
+
+    // Security: each seat may only be granted once per call
+    let mut seen_seats: BTreeSet<Pubkey> = BTreeSet::new();
+
+    let authority_info = ctx.accounts.authority.to_account_info();
+    let system_info = ctx.accounts.system_program.to_account_info();
+    let mut granted: u8 = 0;
+
+    for group in groups {
+        let [seat_info, allowance1_info, allowance2_info, player_info] = group else {
+            return Err(HiddenHandError::InvalidRemainingAccounts.into());
+        };
+
+        if !seen_seats.insert(*seat_info.key) {
+            return Err(HiddenHandError::DuplicateAccount.into());
+        }
+
+        let seat = validate_seat_account(seat_info, &table_key, &program_id)
+            .ok_or(HiddenHandError::InvalidRemainingAccounts)?;
+
+        require!(
+            seat.player == player_info.key(),
+            HiddenHandError::PlayerNotAtTable
+        );
+
+        // Only players still in the hand with encrypted cards (handles > 51)
+        if !seat.is_in_hand() || seat.cards_not_dealt() || seat.hole_card_1 <= 51 {
+            msg!("Seat {} not active with encrypted cards, skipping", seat.seat_index);
+            continue;
+        }
+
+        for (allowance_info, handle) in [
+            (allowance1_info, seat.hole_card_1),
+            (allowance2_info, seat.hole_card_2),
+        ] {
+            inco_cpi::grant_allowance_with_pubkey(
+                &authority_info,
+                allowance_info,
+                &seat.player,
+                &system_info,
+                handle,
+                &[
+                    allowance_info.clone(),
+                    authority_info.clone(),
+                    player_info.clone(),
+                    system_info.clone(),
+                ],
+            )?;
+        }
+
+        granted += 1;
+        msg!(
+            "Allowances granted for seat {} (player {})",
+            seat.seat_index,
+            seat.player
+        );
+    }
+
+    msg!("Granted hole card allowances for {} player(s)", granted);
+
+    Ok(())
+}
@@ -10,7 +10,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::error::HiddenHandError;
 use crate::inco_cpi::{self, INCO_PROGRAM_ID};
-use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 /// Grant community card allowances to a single player
 ///
@@ -21,20 +21,21 @@ use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Ta
 #[derive(Accounts)]
 #[instruction(seat_index: u8)]
 pub struct GrantCommunityAllowances<'info> {
-    /// Authority granting allowances (only authority can grant)
+    /// Authority granting allowances (table authority or this hand's operator)
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
         seeds = [TABLE_SEED, table.table_id.as_ref()],
-        bump = table.bump,
-        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+        bump = table.bump
     )]
     pub table: Account<'info, Table>,
 
     #[account(
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = table.authority == authority.key()
+            || hand_state.is_operator(&authority.key()) @ HiddenHandError::UnauthorizedAuthority
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -48,7 +49,7 @@ pub struct GrantCommunityAllowances<'info> {
     #[account(
         seeds = [SEAT_SEED, table.key().as_ref(), &[seat_index]],
         bump = player_seat.bump,
-        constraint = player_seat.status == PlayerStatus::Playing || player_seat.status == PlayerStatus::AllIn @ HiddenHandError::PlayerNotActive
+        constraint = player_seat.is_in_hand() @ HiddenHandError::PlayerNotActive
     )]
     pub player_seat: Account<'info, PlayerSeat>,
 
@@ -104,10 +105,10 @@ pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GrantCommunityAllowance
 
     // Grant allowance for each of the 5 community cards
     for (i, allowance_account) in ctx.remaining_accounts.iter().enumerate() {
-        let handle = deck_state.cards[i];
+        let handle = deck_state.community_handle(i);
 
-        // Verify handle is encrypted (> 255 indicates Inco handle)
-        if handle <= 255 {
+        // Verify handle is encrypted (above the sentinel indicates Inco handle)
+        if handle <= CARD_SENTINEL as u128 {
             msg!("Community card {} not encrypted (handle={}), skipping", i, handle);
             continue;
         }
@@ -11,7 +11,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::error::HiddenHandError;
 use crate::inco_cpi::{self, INCO_PROGRAM_ID};
-use crate::state::{GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::state::{GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
 #[instruction(seat_index: u8)]
@@ -79,17 +79,22 @@ pub fn handler(ctx: Context<GrantOwnAllowance>, _seat_index: u8) -> Result<()> {
 
     // Validate player is actively in the hand
     require!(
-        player_seat.status == PlayerStatus::Playing || player_seat.status == PlayerStatus::AllIn,
+        player_seat.is_in_hand(),
         HiddenHandError::PlayerNotActive
     );
 
     // Check timeout - allow self-grant after ALLOWANCE_TIMEOUT_SECONDS
-    let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+    let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
     require!(
         elapsed >= ALLOWANCE_TIMEOUT_SECONDS,
         HiddenHandError::TimeoutNotReached
     );
 
+    require!(
+        !player_seat.cards_not_dealt(),
+        HiddenHandError::CardsNotDealt
+    );
+
     // Verify cards are encrypted (handles > 51)
     let handle1 = player_seat.hole_card_1;
     let handle2 = player_seat.hole_card_2;
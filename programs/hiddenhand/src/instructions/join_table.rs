@@ -3,7 +3,8 @@ use anchor_lang::system_program;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::state::{PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::events::PlayerJoined;
+use crate::state::{PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
 #[instruction(seat_index: u8)]
@@ -38,29 +39,51 @@ pub struct JoinTable<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinTable>, seat_index: u8, buy_in: u64) -> Result<()> {
-    let table = &mut ctx.accounts.table;
+pub fn handler(
+    ctx: Context<JoinTable>,
+    seat_index: u8,
+    buy_in: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let seat_bump = ctx.bumps.player_seat;
+    let accounts = ctx.accounts;
+    take_seat(
+        &mut accounts.table,
+        &mut accounts.player_seat,
+        &accounts.player,
+        &accounts.vault,
+        &accounts.system_program,
+        seat_index,
+        buy_in,
+        seat_bump,
+        &allowlist_proof,
+    )
+}
 
+/// Seat a player with their buy-in: shared by join_table and join_table_auto
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn take_seat<'info>(
+    table: &mut Account<'info, Table>,
+    player_seat: &mut Account<'info, PlayerSeat>,
+    player: &Signer<'info>,
+    vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    seat_index: u8,
+    buy_in: u64,
+    seat_bump: u8,
+    allowlist_proof: &[[u8; 32]],
+) -> Result<()> {
     // Validate table state
     require!(
         table.status == TableStatus::Waiting,
         HiddenHandError::TableNotWaiting
     );
 
-    require!(
-        seat_index < table.max_players,
-        HiddenHandError::InvalidSeatIndex
-    );
+    // Private tables only seat players on the allowlist
+    table.check_allowlisted(&player.key(), allowlist_proof)?;
 
-    require!(
-        !table.is_seat_occupied(seat_index),
-        HiddenHandError::SeatOccupied
-    );
-
-    require!(
-        table.current_players < table.max_players,
-        HiddenHandError::TableFull
-    );
+    // Seat must be within this table's range and free
+    table.check_seat_available(seat_index)?;
 
     // Validate buy-in
     require!(
@@ -71,35 +94,37 @@ pub fn handler(ctx: Context<JoinTable>, seat_index: u8, buy_in: u64) -> Result<(
     // Transfer buy-in to vault
     system_program::transfer(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             system_program::Transfer {
-                from: ctx.accounts.player.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
+                from: player.to_account_info(),
+                to: vault.to_account_info(),
             },
         ),
         buy_in,
     )?;
 
     // Update table
-    table.occupy_seat(seat_index);
+    table.occupy_seat(seat_index, player.key());
+    table.remove_from_waitlist(&player.key());
 
     // Initialize player seat
-    let player_seat = &mut ctx.accounts.player_seat;
     player_seat.table = table.key();
-    player_seat.player = ctx.accounts.player.key();
+    player_seat.player = player.key();
     player_seat.seat_index = seat_index;
     player_seat.chips = buy_in;
-    player_seat.current_bet = 0;
-    player_seat.total_bet_this_hand = 0;
-    player_seat.hole_card_1 = 255; // Sentinel: not dealt yet
-    player_seat.hole_card_2 = 255; // Sentinel: not dealt yet
-    player_seat.status = PlayerStatus::Sitting;
-    player_seat.has_acted = false;
-    player_seat.bump = ctx.bumps.player_seat;
+    player_seat.clear_for_new_hand();
+    player_seat.bump = seat_bump;
+
+    emit!(PlayerJoined {
+        table_id: table.table_id,
+        player: player.key(),
+        seat_index,
+        buy_in,
+    });
 
     msg!(
         "Player {} joined table at seat {} with {} chips",
-        ctx.accounts.player.key(),
+        player.key(),
         seat_index,
         buy_in
     );
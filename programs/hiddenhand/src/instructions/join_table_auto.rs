@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::instructions::join_table::take_seat;
+use crate::state::{PlayerSeat, Table};
+
+/// Join at the lowest free seat, picked by the program rather than the client.
+///
+/// The seat PDA has to be known to create it, so the client derives it with
+/// the same rule: the lowest unoccupied seat in the table account it just
+/// fetched. If another player takes that seat first, the program's pick moves
+/// on and the passed PDA no longer matches its seeds - the join fails without
+/// moving any chips, and the client refetches the table and retries
+#[derive(Accounts)]
+pub struct JoinTableAuto<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Seat for `table.find_empty_seat()`. A full table derives an
+    /// out-of-range index, rejected in the handler as TableFull
+    #[account(
+        init,
+        payer = player,
+        space = PlayerSeat::SIZE,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[table.find_empty_seat().unwrap_or(u8::MAX)]],
+        bump
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+
+    /// Vault to receive buy-in (SystemAccount validates System Program ownership)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<JoinTableAuto>, buy_in: u64, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+    let seat_index = ctx
+        .accounts
+        .table
+        .find_empty_seat()
+        .ok_or(HiddenHandError::TableFull)?;
+
+    let seat_bump = ctx.bumps.player_seat;
+    let accounts = ctx.accounts;
+    take_seat(
+        &mut accounts.table,
+        &mut accounts.player_seat,
+        &accounts.player,
+        &accounts.vault,
+        &accounts.system_program,
+        seat_index,
+        buy_in,
+        seat_bump,
+        &allowlist_proof,
+    )
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::Table;
+
+#[derive(Accounts)]
+pub struct JoinWaitlist<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+/// Queue for a seat at a full table
+/// When a seat frees up, the front of the waitlist is popped and a SeatAvailable event is emitted
+pub fn handler(ctx: Context<JoinWaitlist>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player = ctx.accounts.player.key();
+
+    require!(
+        table.current_players >= table.max_players,
+        HiddenHandError::TableHasOpenSeats
+    );
+
+    require!(
+        !table.is_on_waitlist(&player),
+        HiddenHandError::AlreadyOnWaitlist
+    );
+
+    require!(
+        table.waitlist.len() < MAX_WAITLIST,
+        HiddenHandError::WaitlistFull
+    );
+
+    table.waitlist.push(player);
+
+    msg!(
+        "Player {} joined waitlist at position {}",
+        player,
+        table.waitlist.len()
+    );
+
+    Ok(())
+}
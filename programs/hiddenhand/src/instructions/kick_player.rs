@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::events::{PlayerKicked, SeatAvailable};
+use crate::state::{PlayerSeat, Table, TableStatus};
+
+/// Remove a player from the table (authority only, between hands)
+/// Used for colluding or stalling players that timeouts don't cover
+#[derive(Accounts)]
+#[instruction(seat_index: u8)]
+pub struct KickPlayer<'info> {
+    #[account(
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// The seat being cleared - rent goes back to the kicked player
+    #[account(
+        mut,
+        close = player_wallet,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[seat_index]],
+        bump = player_seat.bump
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+
+    /// The kicked player's wallet (receives their chips)
+    /// CHECK: Validated against the seat's player
+    #[account(
+        mut,
+        address = player_seat.player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_wallet: UncheckedAccount<'info>,
+
+    /// Vault to withdraw from (SystemAccount validates System Program ownership)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<KickPlayer>, seat_index: u8) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player_seat = &ctx.accounts.player_seat;
+    let clock = Clock::get()?;
+
+    // Never kick mid-hand - the seat's bets are still part of the pot
+    require!(
+        table.status == TableStatus::Waiting,
+        HiddenHandError::HandInProgress
+    );
+
+    let chips_to_return = player_seat.chips;
+    let player = player_seat.player;
+    let table_key = table.key();
+
+    // Return chips to the player from vault using CPI with PDA signer
+    if chips_to_return > 0 {
+        let vault_bump = ctx.bumps.vault;
+        let vault_seeds: &[&[u8]] = &[
+            VAULT_SEED,
+            table_key.as_ref(),
+            &[vault_bump],
+        ];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.player_wallet.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            chips_to_return,
+        )?;
+    }
+
+    // Update table
+    table.vacate_seat(seat_index);
+
+    emit!(PlayerKicked {
+        table_id: table.table_id,
+        player,
+        seat_index,
+        chips_returned: chips_to_return,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Offer the freed seat to the next waitlisted player
+    if let Some(next_player) = table.pop_waitlist() {
+        emit!(SeatAvailable {
+            table_id: table.table_id,
+            player: next_player,
+            seat_index,
+        });
+        msg!("Seat {} offered to waitlisted player {}", seat_index, next_player);
+    }
+
+    msg!(
+        "Player {} kicked from seat {}, returned {} chips",
+        player,
+        seat_index,
+        chips_to_return
+    );
+
+    Ok(())
+}
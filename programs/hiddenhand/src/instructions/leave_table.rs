@@ -3,7 +3,8 @@ use anchor_lang::system_program;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::state::{PlayerSeat, Table, TableStatus};
+use crate::events::{PlayerLeft, SeatAvailable};
+use crate::state::{HandState, PlayerSeat, Table, TableStatus};
 
 #[derive(Accounts)]
 pub struct LeaveTable<'info> {
@@ -34,22 +35,58 @@ pub struct LeaveTable<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Current hand - pass it to leave a hand that was abandoned before the flop
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
+    )]
+    pub hand_state: Option<Account<'info, HandState>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<LeaveTable>) -> Result<()> {
     let table = &mut ctx.accounts.table;
     let player_seat = &ctx.accounts.player_seat;
+    let seat_index = player_seat.seat_index;
+
+    // Last player left in a hand abandoned before the flop: nothing is contested,
+    // so their posted blind comes back with the pot instead of being forfeited
+    let abandoned_refund = match ctx.accounts.hand_state.as_mut() {
+        Some(hand_state)
+            if table.status == TableStatus::Playing
+                && hand_state.is_abandoned()
+                && hand_state.is_player_active(seat_index) =>
+        {
+            Some(hand_state.settle_abandoned())
+        }
+        _ => None,
+    };
 
     // Cannot leave during active hand UNLESS player has 0 chips (not participating)
+    // or the hand was abandoned
     require!(
-        table.status != TableStatus::Playing || player_seat.chips == 0,
+        table.status != TableStatus::Playing
+            || player_seat.chips == 0
+            || abandoned_refund.is_some(),
         HiddenHandError::CannotLeaveDuringHand
     );
 
-    let chips_to_return = player_seat.chips;
-    let seat_index = player_seat.seat_index;
+    if let Some(refund) = abandoned_refund {
+        // Hand is over - the table can start the next one
+        table.status = TableStatus::Waiting;
+        table.last_ready_time = Clock::get()?.unix_timestamp;
+        msg!("Abandoned hand settled - refunding {} from the pot to seat {}", refund, seat_index);
+    }
+
+    let chips_to_return = player_seat
+        .chips
+        .checked_add(abandoned_refund.unwrap_or(0))
+        .ok_or(HiddenHandError::ArithmeticOverflow)?;
     let table_key = table.key();
+    let clock = Clock::get()?;
 
     // Transfer chips back to player from vault using CPI with PDA signer
     if chips_to_return > 0 {
@@ -76,6 +113,24 @@ pub fn handler(ctx: Context<LeaveTable>) -> Result<()> {
     // Update table
     table.vacate_seat(seat_index);
 
+    emit!(PlayerLeft {
+        table_id: table.table_id,
+        player: ctx.accounts.player.key(),
+        seat_index,
+        chips_returned: chips_to_return,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Offer the freed seat to the next waitlisted player
+    if let Some(next_player) = table.pop_waitlist() {
+        emit!(SeatAvailable {
+            table_id: table.table_id,
+            player: next_player,
+            seat_index,
+        });
+        msg!("Seat {} offered to waitlisted player {}", seat_index, next_player);
+    }
+
     msg!(
         "Player {} left table, returned {} chips",
         ctx.accounts.player.key(),
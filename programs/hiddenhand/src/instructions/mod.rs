@@ -1,7 +1,12 @@
+pub mod configure_table;
 pub mod create_table;
 pub mod deal_cards;
 pub mod join_table;
+pub mod join_waitlist;
+pub mod add_on;
+pub mod kick_player;
 pub mod leave_table;
+pub mod fold_and_leave;
 pub mod player_action;
 pub mod showdown;
 pub mod start_hand;
@@ -15,6 +20,7 @@ pub mod callback_shuffle;
 
 // Timeout handling
 pub mod timeout_player;
+pub mod use_time_bank;
 
 // Inco encryption (Phase 2)
 pub mod encrypt_hole_cards;
@@ -32,10 +38,29 @@ pub mod close_inactive_table;
 
 // Community card allowances - enable any player to reveal if authority AFK
 pub mod grant_community_allowances;
+pub mod grant_all_allowances;
+
+// Read-only views for clients
+pub mod get_min_raise;
+pub mod get_reveal_status;
+pub mod all_in_equity;
+pub mod verify_fairness;
+pub mod archive_table;
+pub mod start_bomb_pot;
+pub mod sit_in;
+pub mod join_table_auto;
+pub mod close_hand;
+pub mod muck;
+pub mod update_blinds;
+pub mod pause_table;
+pub mod resume_table;
+pub mod settle_and_start;
 
 // Re-export everything for convenience
 // The `handler` name conflicts are expected and handled by Anchor's program macro
 #[allow(ambiguous_glob_reexports)]
+pub use configure_table::*;
+#[allow(ambiguous_glob_reexports)]
 pub use create_table::*;
 #[allow(ambiguous_glob_reexports)]
 pub use deal_cards::*;
@@ -44,8 +69,16 @@ pub use deal_cards_encrypted::*;
 #[allow(ambiguous_glob_reexports)]
 pub use join_table::*;
 #[allow(ambiguous_glob_reexports)]
+pub use join_waitlist::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_on::*;
+#[allow(ambiguous_glob_reexports)]
+pub use kick_player::*;
+#[allow(ambiguous_glob_reexports)]
 pub use leave_table::*;
 #[allow(ambiguous_glob_reexports)]
+pub use fold_and_leave::*;
+#[allow(ambiguous_glob_reexports)]
 pub use player_action::*;
 #[allow(ambiguous_glob_reexports)]
 pub use showdown::*;
@@ -58,6 +91,8 @@ pub use callback_shuffle::*;
 #[allow(ambiguous_glob_reexports)]
 pub use timeout_player::*;
 #[allow(ambiguous_glob_reexports)]
+pub use use_time_bank::*;
+#[allow(ambiguous_glob_reexports)]
 pub use encrypt_hole_cards::*;
 #[allow(ambiguous_glob_reexports)]
 pub use reveal_cards::*;
@@ -71,3 +106,31 @@ pub use timeout_reveal::*;
 pub use close_inactive_table::*;
 #[allow(ambiguous_glob_reexports)]
 pub use grant_community_allowances::*;
+#[allow(ambiguous_glob_reexports)]
+pub use grant_all_allowances::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_min_raise::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_reveal_status::*;
+#[allow(ambiguous_glob_reexports)]
+pub use all_in_equity::*;
+#[allow(ambiguous_glob_reexports)]
+pub use verify_fairness::*;
+#[allow(ambiguous_glob_reexports)]
+pub use archive_table::*;
+#[allow(ambiguous_glob_reexports)]
+pub use sit_in::*;
+#[allow(ambiguous_glob_reexports)]
+pub use join_table_auto::*;
+#[allow(ambiguous_glob_reexports)]
+pub use close_hand::*;
+#[allow(ambiguous_glob_reexports)]
+pub use muck::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_blinds::*;
+#[allow(ambiguous_glob_reexports)]
+pub use pause_table::*;
+#[allow(ambiguous_glob_reexports)]
+pub use resume_table::*;
+#[allow(ambiguous_glob_reexports)]
+pub use settle_and_start::*;
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::instructions::player_action::apply_fold;
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::{GamePhase, HandState, PlayerSeat, Table, TableStatus};
+
+/// Give up a losing hand at showdown without showing it
+/// Remaining accounts should include the seat account of a player still in
+/// the hand who has already revealed
+#[derive(Accounts)]
+pub struct Muck<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    #[account(
+        mut,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump,
+        has_one = player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+}
+
+/// A seat may muck once some other seat still contesting the pot has shown:
+/// the pot then has a claimant, and the mucked hand only forfeits its own share
+pub fn another_hand_shown(hand_state: &HandState, seat_index: u8, shown_seats: &[u8]) -> bool {
+    shown_seats
+        .iter()
+        .any(|&seat| seat != seat_index && hand_state.is_player_active(seat))
+}
+
+/// Fold the caller's unrevealed hand at showdown, forfeiting its claim to
+/// the pot without exposing the cards
+pub fn handler(ctx: Context<Muck>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand_state = &mut ctx.accounts.hand_state;
+    let player_seat = &mut ctx.accounts.player_seat;
+    let seat_index = player_seat.seat_index;
+
+    require!(
+        table.status == TableStatus::Playing,
+        HiddenHandError::HandNotInProgress
+    );
+    require!(
+        hand_state.phase == GamePhase::Showdown,
+        HiddenHandError::InvalidPhase
+    );
+    require!(
+        player_seat.is_in_hand() && hand_state.is_player_active(seat_index),
+        HiddenHandError::PlayerNotActive
+    );
+    require!(
+        !player_seat.cards_revealed,
+        HiddenHandError::CardsAlreadyRevealed
+    );
+
+    let program_id = crate::ID;
+    let table_key = table.key();
+    let shown_seats: Vec<u8> = ctx
+        .remaining_accounts
+        .iter()
+        .take(SEAT_BITMAP_SIZE)
+        .filter_map(|account_info| validate_seat_account(account_info, &table_key, &program_id))
+        .filter(|seat| seat.is_in_hand() && seat.cards_revealed)
+        .map(|seat| seat.seat_index)
+        .collect();
+    require!(
+        another_hand_shown(hand_state, seat_index, &shown_seats),
+        HiddenHandError::NoHandShown
+    );
+
+    apply_fold(hand_state, player_seat);
+    msg!(
+        "Seat {} mucks. Active players remaining: {}",
+        seat_index,
+        hand_state.active_count
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hand_state;
+
+    /// Test a hand can only be mucked once another hand has been shown
+    #[test]
+    fn test_muck_needs_another_shown_hand() {
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.active_players = 0b0111;
+        hand.active_count = 3;
+
+        // Nothing shown, or only the mucking seat itself
+        assert!(!another_hand_shown(&hand, 0, &[]));
+        assert!(!another_hand_shown(&hand, 0, &[0]));
+        // A folded seat's shown cards don't count
+        assert!(!another_hand_shown(&hand, 0, &[3]));
+        assert!(another_hand_shown(&hand, 0, &[2]));
+    }
+}
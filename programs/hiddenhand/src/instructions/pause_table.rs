@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::Table;
+
+/// Halt new hands at a table (authority only, between hands)
+#[derive(Accounts)]
+pub struct PauseTable<'info> {
+    #[account(
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+/// Stop new hands from starting. Seated players keep their seats and chips,
+/// and can still leave
+pub fn handler(ctx: Context<PauseTable>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    table.pause()?;
+
+    msg!("Table paused after hand #{}", table.hand_number);
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
+use crate::instructions::timeout_player::advance_phase_with_cards;
 use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, Table, TableStatus};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -9,8 +10,35 @@ pub enum Action {
     Fold,
     Check,
     Call,
+    /// Raise over an existing bet (amount = chips added to this seat's bet)
     Raise { amount: u64 },
     AllIn,
+    /// Open the betting on a street with no bet yet (amount >= big blind)
+    Bet { amount: u64 },
+}
+
+impl Action {
+    /// Map legacy opening raises onto Bet, and stack-sized bets onto AllIn
+    /// Older clients send Raise for the first bet of a street, so a Raise with
+    /// nothing to raise over is treated as a Bet of the same amount. A Bet or
+    /// Raise of the whole stack (or more) is an all-in, and has to go through
+    /// the all-in rules - a short one must not reopen the betting
+    pub fn normalize(self, current_bet: u64, stack: u64) -> Action {
+        let action = match self {
+            Action::Raise { amount } if current_bet == 0 => Action::Bet { amount },
+            action => action,
+        };
+        match action {
+            Action::Bet { amount } if current_bet == 0 && amount >= stack => Action::AllIn,
+            Action::Raise { amount } if amount >= stack => Action::AllIn,
+            action => action,
+        }
+    }
+
+    /// A Bet or Raise that puts no chips in
+    pub fn is_zero_amount(self) -> bool {
+        matches!(self, Action::Bet { amount: 0 } | Action::Raise { amount: 0 })
+    }
 }
 
 #[derive(Accounts)]
@@ -27,7 +55,8 @@ pub struct PlayerAction<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -74,56 +103,105 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
         HiddenHandError::AwaitingCommunityReveal
     );
 
-    require!(
-        hand_state.action_on == player_seat.seat_index,
-        HiddenHandError::NotPlayersTurn
-    );
+    // Usually a client acting on a stale view - log where the hand actually
+    // is so it can resync
+    if hand_state.action_on != player_seat.seat_index {
+        msg!(
+            "Not seat {}'s turn: action is on seat {} ({:?}, current bet {})",
+            player_seat.seat_index,
+            hand_state.action_on,
+            hand_state.phase,
+            hand_state.current_bet
+        );
+        return Err(HiddenHandError::NotPlayersTurn.into());
+    }
 
     require!(
         player_seat.can_act(),
         HiddenHandError::PlayerFolded
     );
 
+    // A sized action has to move chips - reject before any raise arithmetic
+    require!(!action.is_zero_amount(), HiddenHandError::InvalidAction);
+
+    // Acting in time ends any run of timeouts
+    player_seat.consecutive_timeouts = 0;
+
     // Calculate amount to call
-    let to_call = hand_state
-        .current_bet
-        .saturating_sub(player_seat.current_bet);
-
-    match action {
-        Action::Fold => {
-            player_seat.fold();
-            hand_state.fold_player(player_seat.seat_index);
-
-            msg!("Player at seat {} folds", player_seat.seat_index);
-
-            // Check if only one player remains
-            if hand_state.active_count == 1 {
-                // Hand ends, winner takes pot
-                hand_state.phase = GamePhase::Settled;
-                msg!("Hand ends - only one player remaining");
-            }
-        }
+    let to_call = hand_state.amount_to_call(player_seat.current_bet);
+
+    match action.normalize(hand_state.current_bet, player_seat.chips) {
+        Action::Fold => apply_fold(hand_state, player_seat),
 
         Action::Check => {
-            require!(to_call == 0, HiddenHandError::CannotCheck);
+            require!(
+                hand_state.can_check(player_seat.current_bet),
+                HiddenHandError::CannotCheck
+            );
             msg!("Player at seat {} checks", player_seat.seat_index);
         }
 
         Action::Call => {
             require!(to_call > 0, HiddenHandError::InvalidAction);
 
-            let actual_bet = player_seat.place_bet(to_call);
-            hand_state.pot = hand_state.pot.saturating_add(actual_bet);
+            let actual_bet = player_seat.place_bet(to_call)?;
+            hand_state.add_to_pot(player_seat.seat_index, actual_bet)?;
+            hand_state.any_bet_made_this_street = true;
+            hand_state.record_matched(player_seat.current_bet);
+
+            if actual_bet < to_call {
+                // Short stack: the call was clamped to their chips, so they're all-in
+                // for less. They are done for the hand and never need to match the full bet.
+                hand_state.mark_all_in(player_seat.seat_index);
+                msg!(
+                    "Player at seat {} calls all-in for {} ({} short, pot: {})",
+                    player_seat.seat_index,
+                    actual_bet,
+                    to_call - actual_bet,
+                    hand_state.pot
+                );
+            } else {
+                msg!(
+                    "Player at seat {} calls {} (pot: {})",
+                    player_seat.seat_index,
+                    actual_bet,
+                    hand_state.pot
+                );
+            }
+        }
+
+        Action::Bet { amount } => {
+            // Opening bets only - once there's a bet, it must be raised
+            require!(hand_state.current_bet == 0, HiddenHandError::InvalidAction);
+            require!(amount >= table.big_blind, HiddenHandError::BetTooSmall);
+            hand_state.check_raise_cap()?;
+            hand_state.check_fixed_limit(table.fixed_bet_size(hand_state.phase), amount, false)?;
+
+            let actual_bet = player_seat.place_bet(amount)?;
+            hand_state.add_to_pot(player_seat.seat_index, actual_bet)?;
+            hand_state.any_bet_made_this_street = true;
+
+            // The opening bet sets the size of the next raise
+            let new_bet = player_seat.current_bet;
+            hand_state.record_full_raise(new_bet);
+            hand_state.last_aggressor = player_seat.seat_index;
 
             msg!(
-                "Player at seat {} calls {} (pot: {})",
+                "Player at seat {} bets {} (pot: {})",
                 player_seat.seat_index,
-                actual_bet,
+                new_bet,
                 hand_state.pot
             );
         }
 
         Action::Raise { amount } => {
+            require!(hand_state.current_bet > 0, HiddenHandError::InvalidAction);
+            require!(
+                hand_state.can_raise(player_seat.seat_index),
+                HiddenHandError::RaiseNotReopened
+            );
+            hand_state.check_raise_cap()?;
+
             // Raise must be at least min_raise above current bet
             let total_bet = player_seat.current_bet.saturating_add(amount);
             let raise_amount = total_bet.saturating_sub(hand_state.current_bet);
@@ -132,17 +210,18 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
                 raise_amount >= hand_state.min_raise,
                 HiddenHandError::RaiseTooSmall
             );
+            hand_state.check_fixed_limit(table.fixed_bet_size(hand_state.phase), total_bet, false)?;
 
-            let actual_bet = player_seat.place_bet(amount);
-            hand_state.pot = hand_state.pot.saturating_add(actual_bet);
+            let actual_bet = player_seat.place_bet(amount)?;
+            hand_state.add_to_pot(player_seat.seat_index, actual_bet)?;
+            hand_state.any_bet_made_this_street = true;
 
             // Update current bet and min raise
             let new_bet = player_seat.current_bet;
             if new_bet > hand_state.current_bet {
-                hand_state.min_raise = new_bet.saturating_sub(hand_state.current_bet);
-                hand_state.current_bet = new_bet;
                 // Reset acted flags since there's a new bet to respond to
-                hand_state.acted_this_round = 0;
+                hand_state.record_full_raise(new_bet);
+                hand_state.last_aggressor = player_seat.seat_index;
             }
 
             msg!(
@@ -155,14 +234,36 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
 
         Action::AllIn => {
             let all_in_amount = player_seat.chips;
-            let actual_bet = player_seat.place_bet(all_in_amount);
-            hand_state.pot = hand_state.pot.saturating_add(actual_bet);
+            let new_bet = player_seat.current_bet.saturating_add(all_in_amount);
 
-            let new_bet = player_seat.current_bet;
+            // Going all-in over the bet is a raise, which a seat whose option
+            // was closed by a short all-in can't make
+            require!(
+                new_bet <= hand_state.current_bet || hand_state.can_raise(player_seat.seat_index),
+                HiddenHandError::RaiseNotReopened
+            );
             if new_bet > hand_state.current_bet {
-                hand_state.min_raise = new_bet.saturating_sub(hand_state.current_bet);
-                hand_state.current_bet = new_bet;
-                hand_state.acted_this_round = 0;
+                hand_state.check_fixed_limit(table.fixed_bet_size(hand_state.phase), new_bet, true)?;
+            }
+            // A full-raise all-in counts against the cap; a short one doesn't reopen betting
+            if new_bet > hand_state.current_bet
+                && new_bet - hand_state.current_bet >= hand_state.min_raise
+            {
+                hand_state.check_raise_cap()?;
+            }
+
+            let actual_bet = player_seat.place_bet(all_in_amount)?;
+            hand_state.add_to_pot(player_seat.seat_index, actual_bet)?;
+            hand_state.any_bet_made_this_street = true;
+
+            if new_bet > hand_state.current_bet {
+                hand_state.last_aggressor = player_seat.seat_index;
+                if !hand_state.record_all_in_raise(new_bet) {
+                    msg!("All-in is less than a full raise - betting is not reopened");
+                }
+            } else {
+                // All-in for no more than the current bet is a call
+                hand_state.record_matched(new_bet);
             }
 
             // Mark player as all-in in hand state
@@ -182,14 +283,54 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
         hand_state.mark_all_in(player_seat.seat_index);
     }
 
+    finish_action(
+        hand_state,
+        deck_state,
+        player_seat,
+        table.max_players,
+        table.big_blind,
+        clock.unix_timestamp,
+    )
+}
+
+/// Fold a seat out of the hand, ending the hand if only one player remains
+pub(crate) fn apply_fold(hand_state: &mut HandState, player_seat: &mut PlayerSeat) {
+    player_seat.fold();
+    hand_state.fold_player(player_seat.seat_index);
+
+    msg!("Player at seat {} folds", player_seat.seat_index);
+
+    // Check if only one player remains
+    if hand_state.active_count == 1 {
+        // Hand ends, winner takes pot. Their uncalled bet on this street
+        // was never in contention, so it comes straight back out of the pot
+        let uncalled = hand_state.return_uncalled_bet();
+        if uncalled > 0 {
+            msg!("Uncalled bet of {} returned from the pot", uncalled);
+        }
+        hand_state.phase = GamePhase::Settled;
+        msg!("Hand ends - only one player remaining");
+    }
+}
+
+/// Record that the seat on the action has acted and pass the action on,
+/// closing the betting round when nobody else needs to act
+pub(crate) fn finish_action(
+    hand_state: &mut HandState,
+    deck_state: &DeckState,
+    player_seat: &mut PlayerSeat,
+    max_players: u8,
+    big_blind: u64,
+    now: i64,
+) -> Result<()> {
     // Mark player as acted and update timeout timestamp
     hand_state.mark_acted(player_seat.seat_index);
     player_seat.has_acted = true;
-    hand_state.last_action_time = clock.unix_timestamp;
+    hand_state.last_action_time = now;
 
     // Find next player who needs to act in this betting round
     // (active, not all-in, hasn't acted yet or needs to respond to a raise)
-    if let Some(next_player) = find_next_player_who_can_act(hand_state, player_seat.seat_index, table.max_players) {
+    if let Some(next_player) = hand_state.next_to_act(player_seat.seat_index, max_players) {
         // Another player still needs to act - give them the action
         hand_state.action_on = next_player;
         msg!("Action moves to seat {}", next_player);
@@ -198,7 +339,7 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
         // Check if there's any more betting possible in the hand
         if hand_state.can_anyone_bet() {
             // At least 2 players can still bet - advance to next phase
-            advance_to_next_phase(hand_state, deck_state, table.max_players)?;
+            advance_to_next_phase(hand_state, deck_state, max_players, big_blind)?;
         } else {
             // No more betting possible (all remaining players are all-in,
             // or only 1 player has chips and they've completed their action)
@@ -211,42 +352,17 @@ pub fn handler(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-fn advance_to_next_phase(hand_state: &mut HandState, _deck_state: &DeckState, _max_players: u8) -> Result<()> {
-    // Community cards are now ENCRYPTED in deck_state
-    // We can't reveal them directly - authority must call reveal_community
-    // Set the flag to signal that we're waiting for community card reveal
-
-    match hand_state.phase {
-        GamePhase::PreFlop | GamePhase::Flop | GamePhase::Turn => {
-            // Signal that we need community cards revealed before advancing
-            hand_state.awaiting_community_reveal = true;
-            msg!("Betting round complete - awaiting community card reveal from authority");
-            msg!("Authority must call reveal_community instruction");
-        }
-        GamePhase::River => {
-            // No community cards to reveal - go directly to showdown
-            hand_state.phase = GamePhase::Showdown;
-            msg!("Advancing to Showdown");
-        }
-        _ => {}
-    }
-
-    Ok(())
-}
-
-/// Find next player who needs to act (not folded, not all-in, hasn't acted this round)
-fn find_next_player_who_can_act(hand_state: &HandState, after_seat: u8, max_players: u8) -> Option<u8> {
-    let mut next = (after_seat + 1) % max_players;
-    for _ in 0..max_players {
-        if hand_state.is_player_active(next)
-            && !hand_state.is_player_all_in(next)
-            && !hand_state.has_player_acted(next) {
-            return Some(next);
-        }
-        next = (next + 1) % max_players;
-    }
-    None
+/// Close the betting round and open the next street. A plaintext board is
+/// revealed inline, saving the extra transaction; an encrypted board (handles
+/// above 51) waits for the authority's attested reveal_community
+fn advance_to_next_phase(
+    hand_state: &mut HandState,
+    deck_state: &DeckState,
+    max_players: u8,
+    big_blind: u64,
+) -> Result<()> {
+    // Same path as a timed-out round, which already reveals plaintext boards
+    advance_phase_with_cards(hand_state, deck_state, max_players, big_blind)
 }
 
 /// Signal that we need to run out all remaining community cards to showdown
@@ -256,7 +372,7 @@ fn run_out_to_showdown(hand_state: &mut HandState, _deck_state: &DeckState) -> R
     // Set the awaiting flag. The reveal_community instruction will detect
     // that all players are all-in and reveal all remaining cards at once.
 
-    if hand_state.phase == GamePhase::River {
+    if hand_state.phase == GamePhase::River || !hand_state.has_community_to_reveal() {
         // All community cards already revealed, go to showdown
         hand_state.phase = GamePhase::Showdown;
         msg!("Advancing to Showdown - all players all-in");
@@ -269,3 +385,167 @@ fn run_out_to_showdown(hand_state: &mut HandState, _deck_state: &DeckState) -> R
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{deck_state, hand_state, player_seat};
+
+    /// Test folding out of turn (fold_and_leave) leaves the action alone and
+    /// still ends the hand when one player is left
+    #[test]
+    fn test_out_of_turn_fold() {
+        use crate::state::PlayerStatus;
+
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.action_on = 0;
+
+        let mut leaver = player_seat(2, 1_000);
+        leaver.status = PlayerStatus::Playing;
+        apply_fold(&mut hand, &mut leaver);
+        assert_eq!(leaver.status, PlayerStatus::Folded);
+        assert!(!hand.is_player_active(2));
+        assert_eq!(hand.action_on, 0, "Action stays with the seat that has it");
+        assert_eq!(hand.phase, GamePhase::PreFlop);
+
+        let mut second = player_seat(1, 1_000);
+        apply_fold(&mut hand, &mut second);
+        assert_eq!(hand.phase, GamePhase::Settled, "Last player standing wins");
+    }
+
+    /// Test an all-in called on the flop runs out turn and river in one
+    /// reveal and leaves showdown a full board to evaluate
+    #[test]
+    fn test_flop_all_in_runout_to_showdown() {
+        use crate::state::find_winners;
+
+        let deck = deck_state(&[]);
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.active_players = 0b11;
+        hand.active_count = 2;
+        let mut seats = [player_seat(0, 1_000), player_seat(1, 1_000)];
+        for seat in seats.iter_mut() {
+            hand.pot += seat.place_bet(100).unwrap();
+        }
+
+        // Flop comes out and opens a new street
+        hand.awaiting_community_reveal = true;
+        let (start, count) = hand.next_community_reveal(!hand.can_anyone_bet()).unwrap();
+        assert_eq!((start, count), (0, 3));
+        hand.reveal_community_cards(start, &[12, 25, 38]).unwrap();
+        hand.finish_community_reveal(false, 0, 20);
+        assert_eq!(hand.phase, GamePhase::Flop);
+        assert!(!hand.awaiting_community_reveal);
+        for seat in seats.iter_mut() {
+            seat.reset_for_betting_round();
+        }
+
+        // Seat 0 shoves, seat 1 calls for the rest of its stack
+        let shove = seats[0].chips;
+        hand.pot += seats[0].place_bet(shove).unwrap();
+        assert!(hand.record_all_in_raise(seats[0].current_bet));
+        hand.mark_all_in(0);
+        finish_action(&mut hand, &deck, &mut seats[0], MAX_PLAYERS, 20, 0).unwrap();
+        assert_eq!(hand.action_on, 1);
+
+        let to_call = hand.amount_to_call(seats[1].current_bet);
+        hand.pot += seats[1].place_bet(to_call).unwrap();
+        hand.record_matched(seats[1].current_bet);
+        hand.mark_all_in(1);
+        finish_action(&mut hand, &deck, &mut seats[1], MAX_PLAYERS, 20, 0).unwrap();
+
+        // Betting is closed on the flop, waiting on the rest of the board
+        assert_eq!(hand.phase, GamePhase::Flop);
+        assert!(hand.is_all_in_runout());
+
+        // reveal_community: turn and river together, straight to showdown
+        let all_in_runout = !hand.can_anyone_bet();
+        let (start, count) = hand.next_community_reveal(all_in_runout).unwrap();
+        assert_eq!((start, count), (3, 2));
+        hand.reveal_community_cards(start, &[51, 0]).unwrap();
+        hand.finish_community_reveal(all_in_runout, 0, 20);
+        assert_eq!(hand.phase, GamePhase::Showdown);
+        assert!(!hand.awaiting_community_reveal);
+
+        // Showdown's board check passes and every hand can be evaluated
+        let board = hand.revealed_community_cards();
+        assert_eq!(board.len(), hand.community_card_count as usize);
+        assert_eq!(hand.pot, 2_000);
+        let winners = find_winners(&[
+            (0, [11, 24, board[0], board[1], board[2], board[3], board[4]]),
+            (1, [1, 2, board[0], board[1], board[2], board[3], board[4]]),
+        ]);
+        assert_eq!(winners, vec![0]);
+    }
+
+    /// Test closing a betting round reveals a plaintext flop inline and opens
+    /// the flop, while an encrypted board waits for reveal_community
+    #[test]
+    fn test_round_close_reveals_plaintext_board_inline() {
+        let close_preflop = |deck: &DeckState| {
+            let mut hand = hand_state(GamePhase::PreFlop);
+            hand.active_players = 0b11;
+            hand.active_count = 2;
+            let mut seats = [player_seat(0, 1_000), player_seat(1, 1_000)];
+            for seat in seats.iter_mut() {
+                hand.pot += seat.place_bet(20).unwrap();
+            }
+            hand.record_matched(20);
+            finish_action(&mut hand, deck, &mut seats[0], MAX_PLAYERS, 20, 0).unwrap();
+            assert_eq!(hand.action_on, 1);
+            finish_action(&mut hand, deck, &mut seats[1], MAX_PLAYERS, 20, 0).unwrap();
+            hand
+        };
+
+        // Plaintext board: the flop comes out with the call, no reveal needed
+        let hand = close_preflop(&deck_state(&[12, 25, 38, 51, 0]));
+        assert_eq!(hand.phase, GamePhase::Flop);
+        assert!(!hand.awaiting_community_reveal);
+        assert_eq!(hand.revealed_community_cards(), vec![12, 25, 38]);
+        assert_eq!(hand.current_bet, 0);
+        assert_eq!(hand.action_on, 1, "Post-flop action starts left of the dealer");
+
+        // Encrypted board: the round closes but the flop waits for the authority
+        let hand = close_preflop(&deck_state(&[1 << 100, 2 << 100, 3 << 100, 4 << 100, 5 << 100]));
+        assert_eq!(hand.phase, GamePhase::PreFlop);
+        assert!(hand.awaiting_community_reveal);
+        assert!(hand.revealed_community_cards().is_empty());
+    }
+
+    /// Test legacy opening raises map onto Bet, stack-sized bets and raises
+    /// onto AllIn, and real raises are untouched
+    #[test]
+    fn test_action_normalize() {
+        // No bet yet: Raise is an opening bet
+        assert_eq!(Action::Raise { amount: 50 }.normalize(0, 1_000), Action::Bet { amount: 50 });
+        assert_eq!(Action::Bet { amount: 50 }.normalize(0, 1_000), Action::Bet { amount: 50 });
+
+        // Facing a bet: Raise stays a raise, Bet is left for player_action to reject
+        assert_eq!(Action::Raise { amount: 50 }.normalize(20, 1_000), Action::Raise { amount: 50 });
+        assert_eq!(Action::Bet { amount: 50 }.normalize(20, 1_000), Action::Bet { amount: 50 });
+
+        // The whole stack or more is an all-in, however it was sent
+        assert_eq!(Action::Raise { amount: u64::MAX }.normalize(20, 30), Action::AllIn);
+        assert_eq!(Action::Raise { amount: 30 }.normalize(20, 30), Action::AllIn);
+        assert_eq!(Action::Bet { amount: 20 }.normalize(0, 15), Action::AllIn);
+        assert_eq!(Action::Raise { amount: 20 }.normalize(0, 15), Action::AllIn);
+        assert_eq!(Action::Bet { amount: 20 }.normalize(10, 15), Action::Bet { amount: 20 });
+
+        // Other actions are unaffected
+        assert_eq!(Action::Check.normalize(0, 1_000), Action::Check);
+        assert_eq!(Action::AllIn.normalize(20, 1_000), Action::AllIn);
+    }
+
+    /// Test zero-chip bets and raises are caught before normalizing
+    #[test]
+    fn test_action_zero_amount() {
+        assert!(Action::Raise { amount: 0 }.is_zero_amount());
+        assert!(Action::Bet { amount: 0 }.is_zero_amount());
+        assert!(!Action::Raise { amount: 1 }.is_zero_amount());
+        assert!(!Action::Bet { amount: 20 }.is_zero_amount());
+        assert!(!Action::Check.is_zero_amount());
+        assert!(!Action::AllIn.is_zero_amount());
+    }
+}
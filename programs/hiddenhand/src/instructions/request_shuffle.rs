@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
 use ephemeral_vrf_sdk::anchor::vrf;
 use ephemeral_vrf_sdk::instructions::{create_request_randomness_ix, RequestRandomnessParams};
 use ephemeral_vrf_sdk::types::SerializableAccountMeta;
@@ -6,6 +7,8 @@ use ephemeral_vrf_sdk::types::SerializableAccountMeta;
 use crate::constants::*;
 use crate::error::HiddenHandError;
 use crate::inco_cpi::INCO_PROGRAM_ID;
+use crate::instructions::callback_shuffle::dealable_seat_count;
+use crate::instructions::deal_cards::seats_present;
 use crate::state::{DeckState, GamePhase, HandState, Table, TableStatus};
 
 /// Request VRF randomness for card shuffling
@@ -31,7 +34,8 @@ pub struct RequestShuffle<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -60,6 +64,7 @@ pub struct RequestShuffle<'info> {
 /// and the callback will shuffle + encrypt cards atomically.
 ///
 /// IMPORTANT: Pass all player seat accounts as remaining_accounts!
+/// At least two of them must be seats that can be dealt in
 pub fn handler(ctx: Context<RequestShuffle>) -> Result<()> {
     let table = &ctx.accounts.table;
     let hand_state = &ctx.accounts.hand_state;
@@ -81,12 +86,29 @@ pub fn handler(ctx: Context<RequestShuffle>) -> Result<()> {
         HiddenHandError::HandNotInProgress
     );
 
-    // Verify we have seat accounts in remaining_accounts
+    // The callback rejects duplicate seats and a deal with fewer than two
+    // players - check both here, before the VRF request is spent
+    let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
+    for account in ctx.remaining_accounts.iter() {
+        require!(seen_keys.insert(*account.key), HiddenHandError::DuplicateAccount);
+    }
+
+    let seated = hand_state.active_players & table.occupied_seats;
+    let dealable = dealable_seat_count(ctx.remaining_accounts, &table.key(), &crate::ID, seated);
     require!(
-        !ctx.remaining_accounts.is_empty(),
+        dealable >= MIN_PLAYERS as usize,
         HiddenHandError::NotEnoughPlayers
     );
 
+    // A seat in the hand missing from the request makes the callback, which
+    // can't be retried, abort the hand - reject it while it can be resent
+    let present = seats_present(&[], ctx.remaining_accounts, &table.key(), &crate::ID);
+    let missing_seats = seated & !present;
+    if missing_seats != 0 {
+        msg!("Shuffle request is missing seats {:#08b} - pass every seat in the hand", missing_seats);
+        return Err(HiddenHandError::MissingSeatAccounts.into());
+    }
+
     // Use table_id + hand_number as unique seed for this shuffle
     let mut client_seed = [0u8; 32];
     client_seed[0..8].copy_from_slice(&table.hand_number.to_le_bytes());
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::Table;
+
+/// Reopen a paused table for new hands (authority only)
+#[derive(Accounts)]
+pub struct ResumeTable<'info> {
+    #[account(
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+/// Let hands start again on a paused table
+pub fn handler(ctx: Context<ResumeTable>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    table.resume()?;
+
+    msg!("Table resumed");
+
+    Ok(())
+}
@@ -15,7 +15,7 @@ pub const ED25519_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::state::{GamePhase, HandState, PlayerSeat, PlayerStatus, Table};
+use crate::state::{GamePhase, HandState, PlayerSeat, Table};
 
 /// Inco covalidator public key for signature verification
 /// This is the public key that signs decryption attestations
@@ -43,8 +43,10 @@ pub struct RevealCards<'info> {
     pub table: Account<'info, Table>,
 
     #[account(
+        mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -64,7 +66,7 @@ pub struct RevealCards<'info> {
 
 /// Create the message hash that Inco signs
 /// Format: SHA256(handle_hex_ascii || plaintext_u128_le)
-fn create_inco_message_hash(handle: u128, plaintext: u8) -> [u8; 32] {
+pub(crate) fn create_inco_message_hash(handle: u128, plaintext: u8) -> [u8; 32] {
     let mut hasher = Sha256::new();
 
     // Handle as hex string, each char converted to ASCII code
@@ -164,18 +166,21 @@ fn verify_ed25519_signature(
 
 /// Reveal cards with Ed25519 signature verification
 pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
+    let table = &ctx.accounts.table;
     let player_seat = &mut ctx.accounts.player_seat;
-    let hand_state = &ctx.accounts.hand_state;
+    let hand_state = &mut ctx.accounts.hand_state;
+    let clock = Clock::get()?;
 
-    // Validate game phase - can only reveal at Showdown
+    // Validate game phase - can only reveal at Showdown, or once betting is
+    // closed on an all-in runout (hands are tabled early for equity previews)
     require!(
-        hand_state.phase == GamePhase::Showdown,
+        hand_state.phase == GamePhase::Showdown || hand_state.is_all_in_runout(),
         HiddenHandError::InvalidPhase
     );
 
     // Player must still be active (not folded)
     require!(
-        player_seat.status == PlayerStatus::Playing || player_seat.status == PlayerStatus::AllIn,
+        player_seat.is_in_hand(),
         HiddenHandError::PlayerNotActive
     );
 
@@ -191,11 +196,13 @@ pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
         HiddenHandError::InvalidCard
     );
 
-    // Get current instruction index
-    let current_ix_index = load_current_index_checked(&ctx.accounts.instructions_sysvar)
-        .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
+    // Ordered-reveal tables show in turn. If the seat whose turn it is sits on
+    // it past the action timeout, the others may go ahead of it
+    if table.ordered_reveal {
+        hand_state.check_reveal_turn(player_seat.seat_index, table.max_players, clock.unix_timestamp)?;
+    }
 
-    // Get encrypted handles
+    // Dealt cards: Inco handles, or the cards themselves on a plaintext deal
     let handle1 = player_seat.hole_card_1;
     let handle2 = player_seat.hole_card_2;
 
@@ -208,11 +215,68 @@ pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
         handle2
     );
 
+    if player_seat.has_plaintext_cards() {
+        // Plaintext deal: there is no handle to attest, and an attestation
+        // check would be meaningless - the stored values are the cards
+        require!(
+            player_seat.plaintext_reveal_matches(card1, card2),
+            HiddenHandError::RevealMismatch
+        );
+        msg!("Plaintext deal - revealed cards match the dealt cards");
+    } else {
+        verify_hole_card_attestations(
+            &ctx.accounts.instructions_sysvar,
+            handle1,
+            handle2,
+            card1,
+            card2,
+        )?;
+    }
+
+    // Store revealed cards
+    player_seat.revealed_card_1 = card1;
+    player_seat.revealed_card_2 = card2;
+    player_seat.cards_revealed = true;
+    if table.ordered_reveal {
+        // Pass the turn on, with a fresh clock for the next seat to show
+        hand_state.record_ordered_reveal(player_seat.seat_index, table.max_players);
+        hand_state.last_action_time = clock.unix_timestamp;
+    } else {
+        hand_state.mark_revealed(player_seat.seat_index);
+    }
+
+    msg!(
+        "Cards revealed for seat {}: {} {}",
+        player_seat.seat_index,
+        card1,
+        card2
+    );
+    msg!(
+        "{} active seat(s) still to reveal",
+        hand_state.players_remaining_to_reveal()
+    );
+
+    Ok(())
+}
+
+/// Check the two Ed25519 instructions right before this one carry Inco
+/// covalidator attestations that `handle1`/`handle2` decrypt to `card1`/`card2`
+fn verify_hole_card_attestations(
+    instructions_sysvar: &AccountInfo,
+    handle1: u128,
+    handle2: u128,
+    card1: u8,
+    card2: u8,
+) -> Result<()> {
+    // Get current instruction index
+    let current_ix_index = load_current_index_checked(instructions_sysvar)
+        .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
+
     // Verify Ed25519 signatures for both cards
     // The client should include 2 Ed25519 instructions before our instruction
     // Check card1 signature (instruction at current_ix_index - 2)
     let verified1 = if current_ix_index >= 2 {
-        let ed25519_ix = load_instruction_at_checked((current_ix_index - 2) as usize, &ctx.accounts.instructions_sysvar)
+        let ed25519_ix = load_instruction_at_checked((current_ix_index - 2) as usize, instructions_sysvar)
             .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
 
         if ed25519_ix.program_id == ED25519_PROGRAM_ID {
@@ -226,7 +290,7 @@ pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
 
     // Check card2 signature (instruction at current_ix_index - 1)
     let verified2 = if current_ix_index >= 1 {
-        let ed25519_ix = load_instruction_at_checked((current_ix_index - 1) as usize, &ctx.accounts.instructions_sysvar)
+        let ed25519_ix = load_instruction_at_checked((current_ix_index - 1) as usize, instructions_sysvar)
             .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
 
         if ed25519_ix.program_id == ED25519_PROGRAM_ID {
@@ -249,18 +313,6 @@ pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
     );
     msg!("Ed25519 verification passed for both cards");
 
-    // Store revealed cards
-    player_seat.revealed_card_1 = card1;
-    player_seat.revealed_card_2 = card2;
-    player_seat.cards_revealed = true;
-
-    msg!(
-        "Cards revealed for seat {}: {} {}",
-        player_seat.seat_index,
-        card1,
-        card2
-    );
-
     Ok(())
 }
 
@@ -271,7 +323,7 @@ pub fn handler(ctx: Context<RevealCards>, card1: u8, card2: u8) -> Result<()> {
 /// - Bytes 16-47: Public key (32 bytes)
 /// - Bytes 48-111: Signature (64 bytes)
 /// - Bytes 112-143: Message hash (32 bytes)
-fn verify_ed25519_for_handle(data: &[u8], handle: u128, plaintext: u8) -> Result<bool> {
+pub(crate) fn verify_ed25519_for_handle(data: &[u8], handle: u128, plaintext: u8) -> Result<bool> {
     // Expected size: 16 (header) + 32 (pubkey) + 64 (sig) + 32 (msg) = 144
     if data.len() < 144 {
         return Ok(false);
@@ -293,3 +345,45 @@ fn verify_ed25519_for_handle(data: &[u8], handle: u128, plaintext: u8) -> Result
 
     Ok(actual_hash == expected_hash.as_slice())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::player_seat;
+
+    /// Test reveal_cards checks plaintext deals directly and only accepts
+    /// matching covalidator attestations for encrypted ones
+    #[test]
+    fn test_reveal_plaintext_and_encrypted_paths() {
+        // Plaintext deal: the stored values are the cards
+        let mut seat = player_seat(0, 1_000);
+        seat.hole_card_1 = 12;
+        seat.hole_card_2 = 51;
+        assert!(seat.has_plaintext_cards());
+        assert!(seat.plaintext_reveal_matches(12, 51));
+        assert!(!seat.plaintext_reveal_matches(51, 12), "Order matters");
+        assert!(!seat.plaintext_reveal_matches(12, 50));
+
+        // Encrypted deal: Inco handles are never treated as plaintext
+        let handle = 0x1234_5678_9abc_def0_u128 << 64;
+        seat.hole_card_1 = handle;
+        seat.hole_card_2 = handle + 1;
+        assert!(!seat.has_plaintext_cards());
+        assert!(!seat.plaintext_reveal_matches(12, 51));
+
+        // Cleared seat holds sentinels, which are neither
+        seat.clear_for_new_hand();
+        assert!(!seat.has_plaintext_cards());
+
+        // Attestation layout: 16 byte header, pubkey, signature, message hash
+        let mut data = vec![0u8; 144];
+        data[16..48].copy_from_slice(&INCO_COVALIDATOR_PUBKEY);
+        data[112..144].copy_from_slice(&create_inco_message_hash(handle, 12));
+        assert!(verify_ed25519_for_handle(&data, handle, 12).unwrap());
+        assert!(!verify_ed25519_for_handle(&data, handle, 13).unwrap(), "Claimed a different card");
+        assert!(!verify_ed25519_for_handle(&data, handle + 1, 12).unwrap(), "Another handle");
+
+        data[16] ^= 1;
+        assert!(!verify_ed25519_for_handle(&data, handle, 12).unwrap(), "Not the covalidator");
+    }
+}
@@ -5,7 +5,7 @@
 //! values came from Inco's TEE decryption.
 //!
 //! Authorization:
-//! - Authority can call immediately
+//! - Authority (or the hand operator on rotating-operator tables) can call immediately
 //! - Any player can call after COMMUNITY_REVEAL_TIMEOUT_SECONDS (60s)
 //!
 //! Flow:
@@ -14,15 +14,26 @@
 //! 3. Caller submits this instruction with Ed25519 attestation
 //! 4. Program verifies signatures and stores revealed cards
 //! 5. Phase advances and play continues
+//!
+//! Plaintext boards (deal_cards and deal_cards_encrypted store the community
+//! cards unencrypted) are normally revealed inline as betting closes. If one
+//! does reach this instruction, the cards are checked against the stored
+//! values directly - there is no handle to attest.
+//!
+//! Optionally, player seat accounts can be passed as remaining_accounts so the
+//! revealed board is cross-checked against any hole cards already revealed.
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::{
     self, load_current_index_checked, load_instruction_at_checked,
 };
 use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
+use crate::instructions::showdown::validate_seat_account;
+use crate::state::card_utils::find_duplicate_card;
 use crate::state::{DeckState, GamePhase, HandState, Table, TableStatus};
 
 /// Ed25519 program ID for signature verification
@@ -55,7 +66,8 @@ pub struct RevealCommunity<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -133,10 +145,17 @@ pub fn handler(ctx: Context<RevealCommunity>, cards: Vec<u8>) -> Result<()> {
         HiddenHandError::HandNotInProgress
     );
 
-    // Authorization check: authority can call immediately, others must wait for timeout
-    let is_authority = table.authority == caller.key();
+    // No reveal is ever more than the whole board - reject oversized input
+    // before any of the per-card work below
+    require!(
+        cards.len() <= COMMUNITY_CARD_COUNT,
+        HiddenHandError::InvalidCommunityCards
+    );
+
+    // Authorization check: authority/operator can call immediately, others must wait for timeout
+    let is_authority = table.authority == caller.key() || hand_state.is_operator(&caller.key());
     if !is_authority {
-        let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+        let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
         require!(
             elapsed >= ALLOWANCE_TIMEOUT_SECONDS,
             HiddenHandError::TimeoutNotReached
@@ -144,11 +163,15 @@ pub fn handler(ctx: Context<RevealCommunity>, cards: Vec<u8>) -> Result<()> {
         msg!("Non-authority revealing community cards after {} seconds timeout", elapsed);
     }
 
-    // Must be waiting for community reveal
-    require!(
-        hand_state.awaiting_community_reveal,
-        HiddenHandError::CommunityNotReady
-    );
+    // Must be waiting for community reveal. Past preflop, not waiting means this
+    // street's cards are already on the board (e.g. a client retrying a reveal)
+    if !hand_state.awaiting_community_reveal {
+        require!(
+            hand_state.phase == GamePhase::PreFlop,
+            HiddenHandError::CommunityAlreadyRevealed
+        );
+        return Err(HiddenHandError::CommunityNotReady.into());
+    }
 
     // Validate phase
     require!(
@@ -164,25 +187,12 @@ pub fn handler(ctx: Context<RevealCommunity>, cards: Vec<u8>) -> Result<()> {
         .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
 
     // Determine expected cards based on current phase and whether all players are all-in
+    // (flop: cards 0-2, turn: card 3, river: card 4, runouts reveal the rest of the board,
+    // limited to the variant's community card count)
     let all_in_runout = !hand_state.can_anyone_bet();
-    let (expected_card_count, start_idx) = match hand_state.phase {
-        GamePhase::PreFlop => {
-            if all_in_runout {
-                (5, 0) // All 5 community cards
-            } else {
-                (3, 0) // Flop: cards 0, 1, 2
-            }
-        }
-        GamePhase::Flop => {
-            if all_in_runout {
-                (2, 3) // Turn + River: cards 3, 4
-            } else {
-                (1, 3) // Turn: card 3
-            }
-        }
-        GamePhase::Turn => (1, 4), // River: card 4
-        _ => return Err(HiddenHandError::InvalidPhase.into()),
-    };
+    let (start_idx, expected_card_count) = hand_state
+        .next_community_reveal(all_in_runout)
+        .ok_or(HiddenHandError::InvalidPhase)?;
 
     // Validate card count
     require!(
@@ -195,133 +205,111 @@ pub fn handler(ctx: Context<RevealCommunity>, cards: Vec<u8>) -> Result<()> {
         require!(*card <= 51, HiddenHandError::InvalidCard);
     }
 
-    // Verify Ed25519 signatures for each card
-    // Ed25519 instructions should be before our instruction
-    msg!(
-        "Verifying {} Ed25519 signatures for community cards...",
-        expected_card_count
-    );
-
-    for (i, &card_value) in cards.iter().enumerate() {
-        let card_idx = start_idx + i;
-        let handle = deck_state.cards[card_idx];
-
-        // Ed25519 instruction for this card should be at (current_ix_index - expected_card_count + i)
-        let ed25519_ix_index = (current_ix_index as usize)
-            .checked_sub(expected_card_count)
-            .ok_or(HiddenHandError::Ed25519VerificationFailed)?
-            + i;
-
-        let ed25519_ix = load_instruction_at_checked(ed25519_ix_index, &ctx.accounts.instructions_sysvar)
-            .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
-
-        // Verify it's an Ed25519 program instruction
-        require!(
-            ed25519_ix.program_id == ED25519_PROGRAM_ID,
-            HiddenHandError::Ed25519VerificationFailed
+    if let Some(matches) = deck_state.plaintext_reveal_matches(start_idx, &cards) {
+        // Plaintext board: the stored values are the cards, so there is
+        // nothing to attest - they just have to match
+        require!(matches, HiddenHandError::RevealMismatch);
+        msg!("Plaintext board - revealed cards match the dealt cards");
+    } else {
+        // Verify Ed25519 signatures for each card
+        // Ed25519 instructions should be before our instruction
+        msg!(
+            "Verifying {} Ed25519 signatures for community cards...",
+            expected_card_count
         );
 
-        // Verify the signature data
-        let verified = verify_ed25519_for_handle(&ed25519_ix.data, handle, card_value)?;
-        require!(verified, HiddenHandError::Ed25519VerificationFailed);
+        for (i, &card_value) in cards.iter().enumerate() {
+            let card_idx = start_idx + i;
+            let handle = deck_state.community_handle(card_idx);
+
+            // Ed25519 instruction for this card should be at (current_ix_index - expected_card_count + i)
+            let ed25519_ix_index = (current_ix_index as usize)
+                .checked_sub(expected_card_count)
+                .ok_or(HiddenHandError::Ed25519VerificationFailed)?
+                + i;
+
+            let ed25519_ix = load_instruction_at_checked(ed25519_ix_index, &ctx.accounts.instructions_sysvar)
+                .map_err(|_| HiddenHandError::Ed25519VerificationFailed)?;
+
+            // Verify it's an Ed25519 program instruction
+            require!(
+                ed25519_ix.program_id == ED25519_PROGRAM_ID,
+                HiddenHandError::Ed25519VerificationFailed
+            );
+
+            // Verify the signature data
+            let verified = verify_ed25519_for_handle(&ed25519_ix.data, handle, card_value)?;
+            require!(verified, HiddenHandError::Ed25519VerificationFailed);
+
+            msg!(
+                "Card {} verified: handle {} -> value {}",
+                card_idx,
+                handle,
+                card_value
+            );
+        }
 
-        msg!(
-            "Card {} verified: handle {} -> value {}",
-            card_idx,
-            handle,
-            card_value
-        );
+        msg!("All community card signatures verified!");
     }
 
-    msg!("All community card signatures verified!");
+    // Cross-check against cards already on the board
+    require!(
+        find_duplicate_card(&cards, &hand_state.revealed_community_cards()).is_none(),
+        HiddenHandError::DuplicateCardRevealed
+    );
+
+    // Cross-check against revealed hole cards (optional - only if seats are passed)
+    let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
+    for account_info in ctx.remaining_accounts.iter() {
+        if !seen_keys.insert(*account_info.key) {
+            return Err(HiddenHandError::DuplicateAccount.into());
+        }
 
-    // Store revealed cards
-    for (i, &card_value) in cards.iter().enumerate() {
-        let card_idx = start_idx + i;
-        hand_state.community_cards[card_idx] = card_value;
+        if let Some(seat) = validate_seat_account(account_info, &table.key(), &crate::ID) {
+            if let Some(card) = find_duplicate_card(
+                &cards,
+                &[seat.revealed_card_1, seat.revealed_card_2],
+            ) {
+                msg!("Community card {} already revealed by seat {}", card, seat.seat_index);
+                return Err(HiddenHandError::DuplicateCardRevealed.into());
+            }
+        }
     }
 
-    // Update community revealed count
-    hand_state.community_revealed = (start_idx + expected_card_count) as u8;
+    // Store revealed cards and update community revealed count
+    hand_state.reveal_community_cards(start_idx, &cards)?;
 
     // Find first active player left of dealer for betting
-    let first_to_act = get_first_active_left_of_dealer(hand_state, table.max_players);
+    let first_to_act = hand_state.first_active_left_of_dealer(table.max_players);
 
     // Advance phase
-    if all_in_runout {
-        // All remaining players are all-in, go to showdown
-        hand_state.phase = GamePhase::Showdown;
-        msg!(
+    hand_state.finish_community_reveal(all_in_runout, first_to_act, table.big_blind);
+    match hand_state.phase {
+        GamePhase::Showdown => msg!(
             "All-in runout complete: {} community cards revealed. Advancing to Showdown",
             hand_state.community_revealed
-        );
-    } else {
-        // Normal phase advancement
-        match hand_state.phase {
-            GamePhase::PreFlop => {
-                hand_state.phase = GamePhase::Flop;
-                hand_state.reset_betting_round();
-                hand_state.action_on = first_to_act;
-                msg!(
-                    "Flop revealed: {}, {}, {}. Action on seat {}",
-                    hand_state.community_cards[0],
-                    hand_state.community_cards[1],
-                    hand_state.community_cards[2],
-                    first_to_act
-                );
-            }
-            GamePhase::Flop => {
-                hand_state.phase = GamePhase::Turn;
-                hand_state.reset_betting_round();
-                hand_state.action_on = first_to_act;
-                msg!(
-                    "Turn revealed: {}. Action on seat {}",
-                    hand_state.community_cards[3],
-                    first_to_act
-                );
-            }
-            GamePhase::Turn => {
-                hand_state.phase = GamePhase::River;
-                hand_state.reset_betting_round();
-                hand_state.action_on = first_to_act;
-                msg!(
-                    "River revealed: {}. Action on seat {}",
-                    hand_state.community_cards[4],
-                    first_to_act
-                );
-            }
-            _ => {}
-        }
+        ),
+        GamePhase::Flop => msg!(
+            "Flop revealed: {}, {}, {}. Action on seat {}",
+            hand_state.community_cards[0],
+            hand_state.community_cards[1],
+            hand_state.community_cards[2],
+            first_to_act
+        ),
+        GamePhase::Turn => msg!(
+            "Turn revealed: {}. Action on seat {}",
+            hand_state.community_cards[3],
+            first_to_act
+        ),
+        GamePhase::River => msg!(
+            "River revealed: {}. Action on seat {}",
+            hand_state.community_cards[4],
+            first_to_act
+        ),
+        _ => {}
     }
 
-    // Clear the awaiting flag
-    hand_state.awaiting_community_reveal = false;
     hand_state.last_action_time = clock.unix_timestamp;
 
     Ok(())
 }
-
-/// Find first active player to the left of dealer (for post-flop betting order)
-fn get_first_active_left_of_dealer(hand_state: &HandState, max_players: u8) -> u8 {
-    let dealer = hand_state.dealer_position;
-    let mut pos = (dealer + 1) % max_players;
-
-    for _ in 0..max_players {
-        if hand_state.is_player_active(pos) && !hand_state.is_player_all_in(pos) {
-            return pos;
-        }
-        pos = (pos + 1) % max_players;
-    }
-
-    // Fallback: return first active player even if all-in
-    pos = (dealer + 1) % max_players;
-    for _ in 0..max_players {
-        if hand_state.is_player_active(pos) {
-            return pos;
-        }
-        pos = (pos + 1) % max_players;
-    }
-
-    // Fallback (shouldn't happen with 2+ active players)
-    dealer
-}
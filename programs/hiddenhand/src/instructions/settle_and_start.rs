@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::instructions::showdown::{return_stack_excess, settle_hand};
+use crate::instructions::start_hand::open_hand;
+use crate::state::{DeckState, HandHistory, HandState, Table, TableStats};
+
+/// Settle the current hand and start the next one in one transaction (authority only)
+/// Remaining accounts should be all player seat accounts, as for showdown
+#[derive(Accounts)]
+pub struct SettleAndStart<'info> {
+    #[account(
+        mut,
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Hand being settled
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    /// Vault holding player chips
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Hand history record - pass only when the table has `record_history` on
+    #[account(
+        init,
+        payer = authority,
+        space = HandHistory::SIZE,
+        seeds = [HISTORY_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump
+    )]
+    pub hand_history: Option<Account<'info, HandHistory>>,
+
+    /// Table statistics - pass only when the table has `track_stats` on
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TableStats::SIZE,
+        seeds = [STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Option<Account<'info, TableStats>>,
+
+    /// Hand being started
+    #[account(
+        init,
+        payer = authority,
+        space = HandState::SIZE,
+        seeds = [HAND_SEED, table.key().as_ref(), &(table.hand_number + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_hand_state: Account<'info, HandState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DeckState::SIZE,
+        seeds = [DECK_SEED, table.key().as_ref(), &(table.hand_number + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_deck_state: Account<'info, DeckState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Showdown followed by start_hand, for fast games. Only a hand that has
+/// reached showdown (or been folded out) can be settled, so this fails
+/// without changing anything while players still have the action.
+/// The settled hand's accounts stay open for verify_fairness and close_hand.
+/// On rotating-operator tables the next dealer's seat must be among the seats passed
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAndStart<'info>>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let authority = accounts.authority.key();
+
+    let excess = settle_hand(
+        &mut accounts.table,
+        &mut accounts.hand_state,
+        &authority,
+        accounts
+            .hand_history
+            .as_mut()
+            .map(|history| (history, ctx.bumps.hand_history.unwrap_or_default())),
+        accounts
+            .table_stats
+            .as_mut()
+            .map(|stats| (stats, ctx.bumps.table_stats.unwrap_or_default())),
+        ctx.remaining_accounts,
+    )?;
+
+    return_stack_excess(
+        &accounts.vault,
+        ctx.bumps.vault,
+        &accounts.table.key(),
+        &accounts.system_program,
+        ctx.remaining_accounts,
+        &excess,
+    )?;
+
+    open_hand(
+        &mut accounts.table,
+        (&mut accounts.next_hand_state, ctx.bumps.next_hand_state),
+        (&mut accounts.next_deck_state, ctx.bumps.next_deck_state),
+        &authority,
+        ctx.remaining_accounts,
+        0,
+    )
+}
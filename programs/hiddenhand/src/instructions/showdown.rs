@@ -1,14 +1,32 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use std::collections::BTreeSet;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
-use crate::events::{HandCompleted, PlayerHandResult};
-use crate::state::{evaluate_hand, find_winners, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+use crate::events::{HandCompleted, PlayerHandResult, SettlementSummary};
+use crate::state::{
+    build_side_pots, evaluate_hand, evaluate_hole_cards, find_hole_card_winners,
+    find_low_winners, find_winners, split_hi_lo, split_pot, uncalled_excess, EvaluatedHand,
+    GamePhase, GameType, HandHistory, HandState, HistorySeat, PlayerSeat, PlayerStatus, Table,
+    TableStats, TableStatus,
+};
+
+/// Evaluate shown hole cards against the board: the full five cards, or the
+/// hole cards alone on a board-less variant. None for a partial board
+pub(crate) fn evaluate_shown(hole_1: u8, hole_2: u8, board: &[u8]) -> Option<EvaluatedHand> {
+    match board.len() {
+        COMMUNITY_CARD_COUNT => Some(evaluate_hand(&[
+            hole_1, hole_2, board[0], board[1], board[2], board[3], board[4],
+        ])),
+        0 => Some(evaluate_hole_cards(&[hole_1, hole_2])),
+        _ => None,
+    }
+}
 
 /// Helper to validate a seat account from remaining_accounts
 /// Returns Some(seat) if valid, None if should be skipped
-fn validate_seat_account(
+pub(crate) fn validate_seat_account(
     account_info: &AccountInfo,
     table_key: &Pubkey,
     program_id: &Pubkey,
@@ -44,6 +62,64 @@ fn validate_seat_account(
     Some(seat)
 }
 
+/// First seat still in the hand that hasn't revealed its hole cards.
+/// All-in seats count - being unable to act doesn't exempt them from revealing
+pub(crate) fn first_unrevealed_seat<'a>(
+    seats: impl IntoIterator<Item = &'a PlayerSeat>,
+    hand_state: &HandState,
+) -> Option<u8> {
+    seats
+        .into_iter()
+        .find(|seat| {
+            seat.is_in_hand()
+                && hand_state.is_player_active(seat.seat_index)
+                && !seat.cards_revealed
+        })
+        .map(|seat| seat.seat_index)
+}
+
+/// Bitmap of seats still active in the hand that weren't among `seats`
+pub(crate) fn missing_active_seats<'a>(
+    seats: impl IntoIterator<Item = &'a PlayerSeat>,
+    hand_state: &HandState,
+) -> u8 {
+    let provided = seats
+        .into_iter()
+        .filter(|seat| seat.is_in_hand())
+        .fold(0u8, |bitmap, seat| bitmap | (1 << seat.seat_index));
+    hand_state.active_players & !provided
+}
+
+/// Cross-check the seats' own investments against what the hand recorded for
+/// them. Seats that left mid-hand aren't passed, so only the seats given are
+/// compared. Returns `(invested, recorded)` when they don't agree
+pub(crate) fn pot_investment_mismatch<'a>(
+    seats: impl IntoIterator<Item = &'a PlayerSeat>,
+    hand_state: &HandState,
+) -> Option<(u64, u64)> {
+    let (invested, recorded) = seats.into_iter().fold((0u64, 0u64), |(invested, recorded), seat| {
+        let contributed = hand_state
+            .contributed
+            .get(seat.seat_index as usize)
+            .copied()
+            .unwrap_or(0);
+        (
+            invested.saturating_add(seat.total_bet_this_hand),
+            recorded.saturating_add(contributed),
+        )
+    });
+    (invested != recorded).then_some((invested, recorded))
+}
+
+/// Chips in the pot from seats with no result - players who left mid-hand
+pub(crate) fn departed_forfeit(contributions: &[(u8, u64)], results: &[PlayerHandResult]) -> u64 {
+    contributions
+        .iter()
+        .filter(|(seat_idx, _)| !results.iter().any(|result| result.seat_index == *seat_idx))
+        .map(|(_, amount)| *amount)
+        .sum()
+}
+
 #[derive(Accounts)]
 pub struct Showdown<'info> {
     /// Anyone can call showdown, but non-authority must wait for timeout
@@ -60,7 +136,8 @@ pub struct Showdown<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -71,32 +148,136 @@ pub struct Showdown<'info> {
         bump
     )]
     pub vault: SystemAccount<'info>,
+
+    /// Hand history record - pass only when the table has `record_history` on
+    #[account(
+        init,
+        payer = caller,
+        space = HandHistory::SIZE,
+        seeds = [HISTORY_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump
+    )]
+    pub hand_history: Option<Account<'info, HandHistory>>,
+
+    /// Table statistics - pass only when the table has `track_stats` on
+    /// Created on the first tracked showdown
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = TableStats::SIZE,
+        seeds = [STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Option<Account<'info, TableStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Showdown<'info>>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let excess = settle_hand(
+        &mut accounts.table,
+        &mut accounts.hand_state,
+        &accounts.caller.key(),
+        accounts
+            .hand_history
+            .as_mut()
+            .map(|history| (history, ctx.bumps.hand_history.unwrap_or_default())),
+        accounts
+            .table_stats
+            .as_mut()
+            .map(|stats| (stats, ctx.bumps.table_stats.unwrap_or_default())),
+        ctx.remaining_accounts,
+    )?;
+
+    return_stack_excess(
+        &accounts.vault,
+        ctx.bumps.vault,
+        &accounts.table.key(),
+        &accounts.system_program,
+        ctx.remaining_accounts,
+        &excess,
+    )
+}
+
+/// Pay trimmed stacks out of the vault: `excess` pairs an index into
+/// `remaining_accounts` (the player's wallet) with the lamports owed
+pub(crate) fn return_stack_excess<'info>(
+    vault: &SystemAccount<'info>,
+    vault_bump: u8,
+    table_key: &Pubkey,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &[AccountInfo<'info>],
+    excess: &[(usize, u64)],
+) -> Result<()> {
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, table_key.as_ref(), &[vault_bump]];
+
+    for &(wallet_idx, amount) in excess.iter() {
+        let wallet = &remaining_accounts[wallet_idx];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: vault.to_account_info(),
+                    to: wallet.clone(),
+                },
+                &[vault_seeds],
+            ),
+            amount,
+        )?;
+        msg!("Returned {} chips over the stack cap to {}", amount, wallet.key);
+    }
+
+    Ok(())
 }
 
-pub fn handler(ctx: Context<Showdown>) -> Result<()> {
-    let table = &mut ctx.accounts.table;
-    let hand_state = &mut ctx.accounts.hand_state;
-    let caller = &ctx.accounts.caller;
+/// Pay out the current hand from its seats (`remaining_accounts`) and return
+/// the table to Waiting - shared by showdown and settle_and_start. History and
+/// stats come with their PDA bumps, and must be passed exactly when the table
+/// records them. On cap_stack_per_hand tables, stacks over max_buy_in are
+/// trimmed and the excess is returned for the caller to pay out, keyed by the
+/// owner's wallet in `remaining_accounts` (passed after the seats)
+pub(crate) fn settle_hand(
+    table: &mut Account<Table>,
+    hand_state: &mut Account<HandState>,
+    caller: &Pubkey,
+    hand_history: Option<(&mut Account<HandHistory>, u8)>,
+    table_stats: Option<(&mut Account<TableStats>, u8)>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<(usize, u64)>> {
     let clock = Clock::get()?;
 
+    // A settled fold-out still passes the phase check below - never pay twice
+    require!(!hand_state.payouts_done, HiddenHandError::AlreadySettled);
+
     // Authorization check:
     // - Authority can call showdown immediately
     // - Anyone else can call after timeout (prevents authority from abandoning game)
-    let is_authority = table.authority == caller.key();
+    let is_authority = table.authority == *caller;
 
     if !is_authority {
-        let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+        let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
         require!(
             elapsed >= ACTION_TIMEOUT_SECONDS,
-            HiddenHandError::UnauthorizedAuthority
+            HiddenHandError::ShowdownNotTimedOut
         );
         msg!("Non-authority calling showdown after {} seconds timeout", elapsed);
     }
 
+    // History is opt-in: the record must be passed exactly when the table asks for it
+    require!(
+        hand_history.is_some() == table.record_history,
+        HiddenHandError::HandHistoryMismatch
+    );
+    require!(
+        table_stats.is_some() == table.track_stats,
+        HiddenHandError::TableStatsMismatch
+    );
+
     // Security: Check for duplicate accounts in remaining_accounts
     // This prevents an attacker from passing the same account twice to manipulate state
     let mut seen_keys: BTreeSet<Pubkey> = BTreeSet::new();
-    for account in ctx.remaining_accounts.iter() {
+    for account in remaining_accounts.iter() {
         if !seen_keys.insert(*account.key) {
             return Err(HiddenHandError::DuplicateAccount.into());
         }
@@ -109,116 +290,137 @@ pub fn handler(ctx: Context<Showdown>) -> Result<()> {
         HiddenHandError::InvalidPhase
     );
 
-    // Get community cards
-    let community_cards: Vec<u8> = hand_state.community_cards
-        .iter()
-        .filter(|&&c| c != 255)
-        .copied()
-        .collect();
+    // Get community cards (same source whether revealed by timeout or reveal_community)
+    let community_cards = hand_state.revealed_community_cards();
 
     require!(
-        community_cards.len() == 5 || hand_state.active_count == 1,
+        community_cards.len() == hand_state.community_card_count as usize
+            || hand_state.active_count == 1,
         HiddenHandError::InvalidPhase
     );
 
-    // Collect player seats from remaining accounts
-    // Store seat index and account index for later updates
-    let mut active_seats: Vec<(u8, usize)> = Vec::new();
+    // Deserialize each validated seat exactly once. Everything below works on
+    // these in-memory copies, and each seat is serialized back a single time at
+    // the end. Previously every pass (event data, side-pot bets, reveal check,
+    // awards, reset) re-borrowed and re-deserialized the seats, and the reset
+    // pass re-derived every seat PDA, which is what pushed full tables towards
+    // the compute limit. The `usize` is the seat's index in remaining_accounts
     let program_id = crate::ID;
+    let table_key = table.key();
+    let mut seats: Vec<(usize, PlayerSeat)> = remaining_accounts
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, account_info)| {
+            validate_seat_account(account_info, &table_key, &program_id).map(|seat| (idx, seat))
+        })
+        .take(SEAT_BITMAP_SIZE)
+        .collect();
 
-    // === EARLY: Collect ALL player data for event emission BEFORE any modifications ===
-    // This must happen first because modifying accounts can cause borrow issues
-    let mut event_results: [PlayerHandResult; 6] = Default::default();
-    let mut results_count: u8 = 0;
-
-    for (idx, account_info) in ctx.remaining_accounts.iter().enumerate() {
-        if results_count >= 6 {
-            break;
-        }
-        if let Some(seat) = validate_seat_account(account_info, &table.key(), &program_id) {
-            // Track active seats for later processing
-            if seat.status == PlayerStatus::Playing || seat.status == PlayerStatus::AllIn {
-                active_seats.push((seat.seat_index, idx));
-            }
-
-            // Collect event data for ALL seats (including folded)
-            let hole_1 = if seat.cards_revealed {
-                seat.revealed_card_1
-            } else if seat.status == PlayerStatus::Folded {
-                255 // Don't show folded player's cards
-            } else {
-                (seat.hole_card_1 & 0xFF) as u8
-            };
-            let hole_2 = if seat.cards_revealed {
-                seat.revealed_card_2
-            } else if seat.status == PlayerStatus::Folded {
-                255
-            } else {
-                (seat.hole_card_2 & 0xFF) as u8
-            };
+    // Positions in `seats` of players still contesting the pot
+    let active_seats: Vec<usize> = seats
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, seat))| {
+            seat.is_in_hand() && hand_state.is_player_active(seat.seat_index)
+        })
+        .map(|(pos, _)| pos)
+        .collect();
 
-            // Calculate hand rank if cards are shown and we have community cards
-            let hand_rank = if hole_1 != 255 && hole_2 != 255 && community_cards.len() == 5 {
-                let eval = evaluate_hand(&[
-                    hole_1, hole_2,
-                    community_cards[0], community_cards[1], community_cards[2],
-                    community_cards[3], community_cards[4],
-                ]);
-                eval.rank as u8
-            } else {
-                255 // Not evaluated
-            };
+    // Every player still in the hand must be passed - otherwise a caller could
+    // leave out the best hand and have the pot awarded to someone else
+    if active_seats.len() != hand_state.active_count as usize {
+        msg!(
+            "Showdown got {} of {} active seats, missing seats {:#08b}",
+            active_seats.len(),
+            hand_state.active_count,
+            missing_active_seats(active_seats.iter().map(|&pos| &seats[pos].1), hand_state)
+        );
+        return Err(HiddenHandError::MissingSeatAccounts.into());
+    }
 
-            let chips_bet = seat.total_bet_this_hand;
-
-            event_results[results_count as usize] = PlayerHandResult {
-                player: seat.player,
-                seat_index: seat.seat_index,
-                hole_card_1: hole_1,
-                hole_card_2: hole_2,
-                hand_rank,
-                chips_won: 0,
-                chips_bet,
-                folded: seat.status == PlayerStatus::Folded,
-                all_in: seat.status == PlayerStatus::AllIn,
-            };
-            results_count += 1;
+    // Hole cards as used for evaluation and the audit trail
+    // Uses revealed_card_1/2 from secure Ed25519-verified reveal, falling back
+    // to hole_card lower bits for non-encrypted games
+    let hole_cards = |seat: &PlayerSeat| -> (u8, u8) {
+        if seat.cards_revealed {
+            (seat.revealed_card_1, seat.revealed_card_2)
+        } else {
+            ((seat.hole_card_1 & 0xFF) as u8, (seat.hole_card_2 & 0xFF) as u8)
         }
+    };
+
+    // === EARLY: Collect ALL player data for event emission BEFORE any modifications ===
+    let mut event_results: Vec<PlayerHandResult> = Vec::with_capacity(seats.len());
+
+    for (_, seat) in seats.iter() {
+        // Don't show folded player's cards
+        let (hole_1, hole_2) = if seat.status == PlayerStatus::Folded && !seat.cards_revealed {
+            (CARD_SENTINEL, CARD_SENTINEL)
+        } else {
+            hole_cards(seat)
+        };
+
+        // Calculate hand rank if cards are shown and the board is complete
+        let hand_rank = if hole_1 != CARD_SENTINEL && hole_2 != CARD_SENTINEL {
+            evaluate_shown(hole_1, hole_2, &community_cards).map_or(255, |eval| eval.rank as u8)
+        } else {
+            255 // Not evaluated
+        };
+
+        event_results.push(PlayerHandResult {
+            player: seat.player,
+            seat_index: seat.seat_index,
+            hole_card_1: hole_1,
+            hole_card_2: hole_2,
+            hand_rank,
+            chips_won: 0,
+            chips_bet: seat.total_bet_this_hand,
+            folded: seat.status == PlayerStatus::Folded,
+            all_in: seat.status == PlayerStatus::AllIn,
+            net: 0,
+        });
     }
+    let results_count = event_results.len() as u8;
 
     let mut pot = hand_state.pot;
 
-    // Collect total bets from all active players to calculate side pots
-    let mut player_bets: Vec<(u8, usize, u64)> = Vec::new(); // (seat_idx, acc_idx, total_bet)
-
-    for (seat_idx, acc_idx) in active_seats.iter() {
-        if hand_state.is_player_active(*seat_idx) {
-            let account_info = &ctx.remaining_accounts[*acc_idx];
-            let data = account_info.try_borrow_data()?;
-            if let Ok(seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                player_bets.push((*seat_idx, *acc_idx, seat.total_bet_this_hand));
-            }
-        }
+    // Side pots come from the hand's own record of each seat's chips - flag
+    // any drift between that and the seats' total_bet_this_hand. Only warns
+    // unless built with strict-accounting
+    if let Some((invested, recorded)) =
+        pot_investment_mismatch(seats.iter().map(|(_, seat)| seat), hand_state)
+    {
+        msg!(
+            "WARNING: seats invested {} this hand but the hand recorded {}",
+            invested,
+            recorded
+        );
+        #[cfg(feature = "strict-accounting")]
+        return Err(HiddenHandError::PotAccountingMismatch.into());
     }
 
-    // Calculate effective pot and return excess to over-bettors
-    // The effective pot each player can win is limited by what others can match
-    if player_bets.len() >= 2 {
-        // Find minimum bet among active players
-        let min_bet = player_bets.iter().map(|(_, _, bet)| *bet).min().unwrap_or(0);
-
-        // Return excess to players who bet more than the minimum
-        for (seat_idx, acc_idx, total_bet) in player_bets.iter() {
-            if *total_bet > min_bet {
-                let excess = total_bet - min_bet;
-                let account_info = &ctx.remaining_accounts[*acc_idx];
-                let mut data = account_info.try_borrow_mut_data()?;
-                if let Ok(mut seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                    seat.award_chips(excess);
-                    seat.try_serialize(&mut *data)?;
-                    pot = pot.saturating_sub(excess);
-                    msg!("Returning {} excess chips to seat {} (uncallable bet)", excess, seat_idx);
+    // Return the part of the biggest bet nobody matched: whatever it put in
+    // above the next-biggest contribution. Everything below that is contested,
+    // layered into side pots by what each player could cover
+    if active_seats.len() >= 2 {
+        if let Some((seat_idx, excess)) = uncalled_excess(&hand_state.contributions()) {
+            // A folded (or departed) seat's unmatched chips stay in the pot
+            if let Some(&pos) = active_seats
+                .iter()
+                .find(|&&pos| seats[pos].1.seat_index == seat_idx)
+            {
+                let excess = hand_state.take_from_pot(seat_idx, excess)?;
+                let seat = &mut seats[pos].1;
+                seat.award_chips(excess);
+                seat.total_bet_this_hand -= excess;
+                pot = hand_state.pot;
+                if let Some(result) = event_results
+                    .iter_mut()
+                    .find(|result| result.seat_index == seat_idx)
+                {
+                    result.chips_bet = result.chips_bet.saturating_sub(excess);
                 }
+                msg!("Returning {} excess chips to seat {} (uncallable bet)", excess, seat_idx);
             }
         }
     }
@@ -226,127 +428,205 @@ pub fn handler(ctx: Context<Showdown>) -> Result<()> {
     // Check that all active players have revealed their cards (required for secure showdown)
     // Skip this check if only one player remains (they win by default)
     if hand_state.active_count > 1 {
-        for (seat_idx, acc_idx) in active_seats.iter() {
-            if hand_state.is_player_active(*seat_idx) {
-                let account_info = &ctx.remaining_accounts[*acc_idx];
-                let data = account_info.try_borrow_data()?;
-                if let Ok(seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                    if !seat.cards_revealed {
-                        msg!("Seat {} has not revealed cards yet", seat_idx);
-                        return Err(HiddenHandError::PlayersNotRevealed.into());
-                    }
-                }
-            }
+        let contesting = active_seats.iter().map(|&pos| &seats[pos].1);
+        if let Some(seat_idx) = first_unrevealed_seat(contesting, hand_state) {
+            msg!("Seat {} has not revealed cards yet", seat_idx);
+            return Err(HiddenHandError::PlayersNotRevealed.into());
         }
     }
 
+    // Chips paid to each seat, for the event and history record
+    let mut awarded: Vec<(u8, u64)> = Vec::new();
+
     // Handle single winner (everyone else folded)
     if hand_state.active_count == 1 {
-        // Find the single remaining player
-        for (seat_idx, acc_idx) in active_seats.iter() {
-            if hand_state.is_player_active(*seat_idx) {
-                // Award entire pot to winner
-                let account_info = &ctx.remaining_accounts[*acc_idx];
-                let mut data = account_info.try_borrow_mut_data()?;
-                if let Ok(mut seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                    seat.award_chips(pot);
-                    seat.try_serialize(&mut *data)?;
-                    msg!("Player at seat {} wins {} (all others folded)", seat_idx, pot);
+        // Award entire pot to the single remaining player
+        if let Some(&pos) = active_seats.first() {
+            let seat = &mut seats[pos].1;
+            let seat_idx = seat.seat_index;
+            // Uncalled bet was taken out of the pot at fold-out - hand it back
+            let uncalled = hand_state.uncalled_bet;
+            seat.award_chips(pot.saturating_add(uncalled));
+            awarded.push((seat_idx, pot));
+            if uncalled > 0 {
+                msg!("Returning uncalled bet of {} to seat {}", uncalled, seat_idx);
+                if let Some(result) = event_results
+                    .iter_mut()
+                    .find(|result| result.seat_index == seat_idx)
+                {
+                    result.chips_bet = result.chips_bet.saturating_sub(uncalled);
                 }
-                break;
             }
+            msg!("Player at seat {} wins {} (all others folded)", seat_idx, pot);
         }
     } else {
-        // Showdown - evaluate hands and find winners
-        let mut player_hands: Vec<(u8, [u8; 7])> = Vec::new();
-
-        for (seat_idx, acc_idx) in active_seats.iter() {
-            if hand_state.is_player_active(*seat_idx) {
-                let account_info = &ctx.remaining_accounts[*acc_idx];
-                let data = account_info.try_borrow_data()?;
-                if let Ok(seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                    // Build 7-card hand (2 hole cards + 5 community)
-                    // Use revealed_card_1/2 from secure Ed25519-verified reveal
-                    // Falls back to hole_card lower bits for non-encrypted games
-                    let hole_card_1 = if seat.cards_revealed {
-                        seat.revealed_card_1
-                    } else {
-                        (seat.hole_card_1 & 0xFF) as u8
-                    };
-                    let hole_card_2 = if seat.cards_revealed {
-                        seat.revealed_card_2
-                    } else {
-                        (seat.hole_card_2 & 0xFF) as u8
-                    };
+        // Showdown - evaluate hands and find winners. A contested pot is
+        // only ever paid on evaluated hands: the full board, or hole cards
+        // alone on a board-less variant
+        let board_less = community_cards.is_empty();
+        require!(
+            board_less || community_cards.len() == COMMUNITY_CARD_COUNT,
+            HiddenHandError::GameTypeUnsupported
+        );
 
+        let hole_hands: Vec<(u8, [u8; 2])> = active_seats
+            .iter()
+            .map(|&pos| {
+                let seat = &seats[pos].1;
+                let (hole_card_1, hole_card_2) = hole_cards(seat);
+                (seat.seat_index, [hole_card_1, hole_card_2])
+            })
+            .collect();
+
+        // Build 7-card hand (2 hole cards + 5 community)
+        let player_hands: Vec<(u8, [u8; 7])> = if board_less {
+            Vec::new()
+        } else {
+            hole_hands
+                .iter()
+                .map(|&(seat_idx, [hole_card_1, hole_card_2])| {
                     let seven_cards: [u8; 7] = [
                         hole_card_1,
                         hole_card_2,
-                        community_cards.get(0).copied().unwrap_or(0),
-                        community_cards.get(1).copied().unwrap_or(0),
-                        community_cards.get(2).copied().unwrap_or(0),
-                        community_cards.get(3).copied().unwrap_or(0),
-                        community_cards.get(4).copied().unwrap_or(0),
+                        community_cards[0],
+                        community_cards[1],
+                        community_cards[2],
+                        community_cards[3],
+                        community_cards[4],
                     ];
+                    (seat_idx, seven_cards)
+                })
+                .collect()
+        };
+
+        // Layer the pot by what each player still in could cover, using what
+        // every seat put in this hand - including seats that have since left
+        let contributions: Vec<(u8, u64, bool)> = hand_state
+            .contributions()
+            .into_iter()
+            .map(|(seat_idx, amount)| (seat_idx, amount, hand_state.is_player_active(seat_idx)))
+            .collect();
+        let side_pots = build_side_pots(&contributions);
+        let layered: u64 = side_pots.iter().map(|side_pot| side_pot.amount).sum();
+        if layered != pot {
+            msg!("Side pots add up to {} but the pot holds {}", layered, pot);
+            return Err(HiddenHandError::PotAccountingMismatch.into());
+        }
+
+        // Find winners and their payouts, pot by pot
+        let mut payouts: Vec<(u8, u64)> = Vec::new();
+        for side_pot in side_pots.iter() {
+            let contenders: Vec<(u8, [u8; 7])> = player_hands
+                .iter()
+                .filter(|(seat_idx, _)| side_pot.eligible.contains(seat_idx))
+                .copied()
+                .collect();
+
+            let pot_payouts = if board_less {
+                let contenders: Vec<(u8, [u8; 2])> = hole_hands
+                    .iter()
+                    .filter(|(seat_idx, _)| side_pot.eligible.contains(seat_idx))
+                    .copied()
+                    .collect();
+                split_pot(side_pot.amount, &find_hole_card_winners(&contenders))
+            } else if table.game_type == GameType::HoldemHiLo {
+                let high_winners = find_winners(&contenders);
+                let low_winners = find_low_winners(&contenders);
+                msg!("Hi-lo showdown - high: {:?}, low: {:?}", high_winners, low_winners);
+                split_hi_lo(side_pot.amount, &high_winners, &low_winners)
+            } else {
+                // Winners come back sorted, so the lowest winning seat gets any remainder
+                split_pot(side_pot.amount, &find_winners(&contenders))
+            };
 
-                    player_hands.push((*seat_idx, seven_cards));
+            for (seat_idx, amount) in pot_payouts {
+                match payouts.iter_mut().find(|(s, _)| *s == seat_idx) {
+                    Some((_, total)) => *total += amount,
+                    None => payouts.push((seat_idx, amount)),
                 }
             }
         }
 
-        // Find winners
-        let winners = find_winners(&player_hands);
-        let winner_count = winners.len() as u64;
+        require!(!payouts.is_empty(), HiddenHandError::InvalidPhase);
 
-        require!(winner_count > 0, HiddenHandError::InvalidPhase);
-
-        // Calculate split (handle remainder)
-        let share = pot / winner_count;
-        let remainder = pot % winner_count;
-
-        msg!("Showdown - {} winner(s), pot: {}, share: {}", winner_count, pot, share);
+        msg!(
+            "Showdown - {} winner(s), pot: {} in {} layer(s)",
+            payouts.len(),
+            pot,
+            side_pots.len()
+        );
 
         // Distribute winnings
-        for (i, winner_seat_idx) in winners.iter().enumerate() {
-            // Find the winner's account
-            for (seat_idx, acc_idx) in active_seats.iter() {
-                if seat_idx == winner_seat_idx {
-                    let account_info = &ctx.remaining_accounts[*acc_idx];
-                    let mut data = account_info.try_borrow_mut_data()?;
-                    if let Ok(mut seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                        // First winner gets any remainder
-                        let winnings = if i == 0 { share + remainder } else { share };
-                        seat.award_chips(winnings);
-                        seat.try_serialize(&mut *data)?;
-
-                        // Log the hand
-                        let hole_1 = if seat.cards_revealed {
-                            seat.revealed_card_1
-                        } else {
-                            (seat.hole_card_1 & 0xFF) as u8
-                        };
-                        let hole_2 = if seat.cards_revealed {
-                            seat.revealed_card_2
-                        } else {
-                            (seat.hole_card_2 & 0xFF) as u8
-                        };
-                        let hand_eval = evaluate_hand(&[
-                            hole_1, hole_2,
-                            community_cards[0], community_cards[1], community_cards[2],
-                            community_cards[3], community_cards[4],
-                        ]);
-
-                        msg!(
-                            "Seat {} wins {} with {:?}",
-                            seat_idx,
-                            winnings,
-                            hand_eval.rank
-                        );
-                    }
-                    break;
+        for (winner_seat_idx, winnings) in payouts.iter() {
+            let winnings = *winnings;
+            // Find the winner's seat
+            if let Some(&pos) = active_seats
+                .iter()
+                .find(|&&pos| seats[pos].1.seat_index == *winner_seat_idx)
+            {
+                let seat = &mut seats[pos].1;
+                seat.award_chips(winnings);
+
+                // Log the hand
+                let (hole_1, hole_2) = hole_cards(seat);
+                if let Some(hand_eval) = evaluate_shown(hole_1, hole_2, &community_cards) {
+                    msg!(
+                        "Seat {} wins {} with {:?}",
+                        winner_seat_idx,
+                        winnings,
+                        hand_eval.rank
+                    );
                 }
             }
         }
+
+        awarded = payouts;
+    }
+
+    for result in event_results.iter_mut() {
+        result.record_winnings(
+            awarded
+                .iter()
+                .filter(|(seat_idx, _)| *seat_idx == result.seat_index)
+                .map(|(_, amount)| *amount)
+                .sum(),
+        );
+    }
+
+    // Count the hand once for every seat that took a share of the pot
+    for (_, seat) in seats.iter_mut() {
+        if awarded
+            .iter()
+            .any(|&(seat_idx, amount)| seat_idx == seat.seat_index && amount > 0)
+        {
+            seat.record_win();
+        }
+    }
+
+    let board: [u8; COMMUNITY_CARD_COUNT] =
+        core::array::from_fn(|i| community_cards.get(i).copied().unwrap_or(CARD_SENTINEL));
+
+    // Persist the hand for later replay / disputes
+    if let Some((history, history_bump)) = hand_history {
+        history.table = table.key();
+        history.hand_number = hand_state.hand_number;
+        history.timestamp = clock.unix_timestamp;
+        history.community_cards = board;
+        history.total_pot = pot;
+        history.seat_count = results_count;
+        history.bump = history_bump;
+        for (entry, result) in history.seats.iter_mut().zip(event_results.iter()) {
+            *entry = HistorySeat {
+                player: result.player,
+                seat_index: result.seat_index,
+                hole_card_1: result.hole_card_1,
+                hole_card_2: result.hole_card_2,
+                chips_bet: result.chips_bet,
+                chips_won: result.chips_won,
+                folded: result.folded,
+            };
+        }
+        msg!("Hand #{} recorded to history", hand_state.hand_number);
     }
 
     // Emit the hand completed event for audit trail (using pre-collected data)
@@ -354,45 +634,67 @@ pub fn handler(ctx: Context<Showdown>) -> Result<()> {
         table_id: table.table_id,
         hand_number: hand_state.hand_number,
         timestamp: clock.unix_timestamp,
-        community_cards: [
-            community_cards.get(0).copied().unwrap_or(255),
-            community_cards.get(1).copied().unwrap_or(255),
-            community_cards.get(2).copied().unwrap_or(255),
-            community_cards.get(3).copied().unwrap_or(255),
-            community_cards.get(4).copied().unwrap_or(255),
-        ],
+        community_cards: board,
         total_pot: pot,
         player_count: results_count,
-        results: event_results,
+        results: HandCompleted::pack_results(&event_results),
         results_count,
+        final_phase: hand_state.phase as u8,
     });
 
-    msg!("HandCompleted event emitted for hand #{}", hand_state.hand_number);
+    msg!(
+        "HandCompleted event emitted for hand #{} ({})",
+        hand_state.hand_number,
+        hand_state.current_street_name()
+    );
+
+    // One reconciled record of where the pot went
+    let summary = SettlementSummary::from_results(
+        table.table_id,
+        hand_state.hand_number,
+        pot,
+        departed_forfeit(&hand_state.contributions(), &event_results),
+        &event_results,
+    );
+
+    if let Some((stats, stats_bump)) = table_stats {
+        stats.table = table.key();
+        stats.bump = stats_bump;
+        if stats.record_hand(pot, summary.rake) {
+            msg!("New biggest pot at this table: {}", pot);
+        }
+    }
+
+    emit!(summary);
 
     // Reset all player states for next hand (including folded players)
-    for account_info in ctx.remaining_accounts.iter() {
-        // Validate seat account (owner check + PDA verification)
-        if let Some(_seat) = validate_seat_account(account_info, &table.key(), &program_id) {
-            // Reset the seat state
-            let mut data = account_info.try_borrow_mut_data()?;
-            if let Ok(mut seat) = PlayerSeat::try_deserialize(&mut &data[..]) {
-                seat.status = PlayerStatus::Sitting;
-                seat.current_bet = 0;
-                seat.total_bet_this_hand = 0;
-                seat.hole_card_1 = 255; // Sentinel: not dealt
-                seat.hole_card_2 = 255; // Sentinel: not dealt
-                seat.revealed_card_1 = 255; // Not revealed
-                seat.revealed_card_2 = 255; // Not revealed
-                seat.cards_revealed = false;
-                seat.has_acted = false;
-                seat.try_serialize(&mut *data)?;
+    // and write each seat back once
+    let mut stack_excess: Vec<(usize, u64)> = Vec::new();
+    for (acc_idx, seat) in seats.iter_mut() {
+        seat.clear_for_new_hand();
+
+        if table.cap_stack_per_hand {
+            let excess = seat.trim_stack(table.max_buy_in);
+            if excess > 0 {
+                let wallet_idx = remaining_accounts
+                    .iter()
+                    .position(|account| *account.key == seat.player)
+                    .ok_or(HiddenHandError::StackCapWalletMissing)?;
+                stack_excess.push((wallet_idx, excess));
+                msg!("Seat {} trimmed to {} ({} over the cap)", seat.seat_index, table.max_buy_in, excess);
             }
         }
+
+        let mut data = remaining_accounts[*acc_idx].try_borrow_mut_data()?;
+        seat.try_serialize(&mut *data)?;
     }
 
     // Mark hand as settled
     hand_state.phase = GamePhase::Settled;
     hand_state.pot = 0;
+    hand_state.uncalled_bet = 0;
+    hand_state.contributed = [0; SEAT_BITMAP_SIZE];
+    hand_state.payouts_done = true;
 
     // Return table to waiting state and record time (for timeout fallback)
     table.status = TableStatus::Waiting;
@@ -400,5 +702,168 @@ pub fn handler(ctx: Context<Showdown>) -> Result<()> {
 
     msg!("Hand #{} complete", hand_state.hand_number);
 
-    Ok(())
+    Ok(stack_excess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::HandRank;
+    use crate::test_fixtures::{hand_state, player_seat};
+
+    /// Test a zero-community variant deals no board and skips reveal phases
+    #[test]
+    fn test_zero_community_variant_skips_reveals() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.community_card_count = GameType::NoBoard.community_card_count();
+
+        assert!(!hand.has_community_to_reveal());
+        assert_eq!(hand.next_community_reveal(false), None);
+        assert_eq!(hand.next_community_reveal(true), None);
+        assert!(hand.community_cards.iter().all(|&c| c == CARD_SENTINEL));
+
+        // Showdown settles on the hole cards alone
+        let board = hand.revealed_community_cards();
+        assert!(board.is_empty());
+        let pair = evaluate_shown(4, 17, &board).unwrap();
+        let ace_king = evaluate_shown(12, 24, &board).unwrap();
+        assert_eq!(pair.rank, HandRank::OnePair);
+        assert_eq!(pair.compare(&ace_king), std::cmp::Ordering::Greater);
+
+        // A board that's only partly out is never evaluated
+        assert!(evaluate_shown(4, 17, &[0, 1, 2]).is_none());
+    }
+
+    /// Test showdown's pot audit catches seat investments drifting from the hand's record
+    #[test]
+    fn test_pot_investment_mismatch_detected() {
+        // Three seats put in 100, 100 and a folded 40
+        let mut seats = [player_seat(0, 900), player_seat(1, 900), player_seat(2, 960)];
+        let mut hand = hand_state(GamePhase::Showdown);
+        for (seat, bet) in seats.iter_mut().zip([100, 100, 40]) {
+            seat.total_bet_this_hand = bet;
+            hand.add_to_pot(seat.seat_index, bet).unwrap();
+        }
+        assert_eq!(pot_investment_mismatch(&seats, &hand), None);
+
+        // An uncalled bet taken back out of the pot still counts as invested
+        hand.pot = 200;
+        hand.uncalled_bet = 40;
+        assert_eq!(pot_investment_mismatch(&seats, &hand), None);
+
+        // A seat that left mid-hand isn't passed, and its chips are still recorded
+        assert_eq!(pot_investment_mismatch(&seats[..2], &hand), None);
+
+        // A bet that reached the pot without being recorded on the seat
+        seats[1].total_bet_this_hand = 80;
+        assert_eq!(pot_investment_mismatch(&seats, &hand), Some((220, 240)));
+    }
+
+    /// Test showdown spots a player in the hand whose seat wasn't passed
+    #[test]
+    fn test_missing_active_seats() {
+        // Seats 0, 1 and 3 still in the hand
+        let seats = [player_seat(0, 900), player_seat(1, 900), player_seat(3, 900)];
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.active_players = 0b1011;
+        hand.active_count = 3;
+        assert_eq!(missing_active_seats(&seats, &hand), 0);
+
+        // Leaving out seat 1 is caught
+        let partial = [seats[0].clone(), seats[2].clone()];
+        assert_eq!(missing_active_seats(&partial, &hand), 0b0010);
+
+        // A folded seat passed in its place doesn't cover for it
+        let mut folded = player_seat(1, 900);
+        folded.status = PlayerStatus::Folded;
+        let with_folded = [seats[0].clone(), folded, seats[2].clone()];
+        assert_eq!(missing_active_seats(&with_folded, &hand), 0b0010);
+    }
+
+    /// Test the settlement summary reconciles with the individual awards
+    #[test]
+    fn test_settlement_summary_reconciles() {
+        let result = |seat_index: u8, chips_bet: u64, chips_won: u64| PlayerHandResult {
+            player: Pubkey::new_unique(),
+            seat_index,
+            hole_card_1: CARD_SENTINEL,
+            hole_card_2: CARD_SENTINEL,
+            hand_rank: 255,
+            chips_won,
+            chips_bet,
+            folded: chips_won == 0,
+            all_in: false,
+            net: chips_won as i64 - chips_bet as i64,
+        };
+
+        // Three-way pot of 301 chopped between seats 0 and 2 (odd chip to seat 0)
+        let payouts = split_pot(301, &[0, 2]);
+        let results = [result(0, 100, payouts[0].1), result(1, 101, 0), result(2, 100, payouts[1].1)];
+        let summary = SettlementSummary::from_results([0; 32], 7, 301, 0, &results);
+
+        assert_eq!(summary.seat_count, 3);
+        assert_eq!(summary.total_awarded, 301);
+        assert_eq!(summary.rake, 0);
+        assert_eq!(summary.seats[0].net, 51);
+        assert_eq!(summary.seats[1].net, -101);
+        assert_eq!(summary.seats[2].net, 50);
+        assert!(summary.reconciles());
+
+        // Fold-out: the winner's uncalled bet is already out of both pot and chips_bet
+        let results = [result(0, 20, 30), result(1, 10, 0), result(2, 0, 0)];
+        let summary = SettlementSummary::from_results([0; 32], 8, 30, 0, &results);
+        assert_eq!(summary.seats[0].net, 10);
+        assert!(summary.reconciles());
+
+        // Seat 1 bet 40 and left mid-hand: its chips stay in the pot with no
+        // seat to list, and count as its loss
+        let mut hand = hand_state(GamePhase::Turn);
+        for (seat, bet) in [(0, 40), (1, 40), (2, 40)] {
+            hand.add_to_pot(seat, bet).unwrap();
+        }
+        let results = [result(0, 40, 120), result(2, 40, 0)];
+        let forfeit = departed_forfeit(&hand.contributions(), &results);
+        assert_eq!(forfeit, 40);
+        let summary = SettlementSummary::from_results([0; 32], 9, hand.pot, forfeit, &results);
+        assert_eq!(summary.seat_count, 2);
+        assert_eq!(summary.departed_forfeit, 40);
+        assert!(summary.reconciles());
+        // Leaving the forfeit out no longer balances
+        assert!(!SettlementSummary::from_results([0; 32], 9, hand.pot, 0, &results).reconciles());
+
+        // An award that doesn't match the pot is caught
+        let results = [result(0, 20, 31), result(1, 10, 0)];
+        assert!(!SettlementSummary::from_results([0; 32], 9, 30, 0, &results).reconciles());
+    }
+
+    /// Test all-in players count as in the hand and must reveal at showdown
+    #[test]
+    fn test_all_in_players_must_reveal() {
+        let mut seat = player_seat(0, 0);
+        for (status, in_hand) in [
+            (PlayerStatus::Playing, true),
+            (PlayerStatus::AllIn, true),
+            (PlayerStatus::Folded, false),
+            (PlayerStatus::Sitting, false),
+        ] {
+            seat.status = status;
+            assert_eq!(seat.is_in_hand(), in_hand, "{:?}", status);
+        }
+
+        // Seat 0 revealed, seat 1 is all-in and hasn't
+        let hand = hand_state(GamePhase::Showdown);
+        let mut revealed = player_seat(0, 500);
+        revealed.cards_revealed = true;
+        let mut all_in = player_seat(1, 0);
+        all_in.status = PlayerStatus::AllIn;
+        assert_eq!(first_unrevealed_seat([&revealed, &all_in], &hand), Some(1));
+
+        all_in.cards_revealed = true;
+        assert_eq!(first_unrevealed_seat([&revealed, &all_in], &hand), None);
+
+        // Folded seats never owe a reveal
+        let mut folded = player_seat(1, 0);
+        folded.status = PlayerStatus::Folded;
+        assert_eq!(first_unrevealed_seat([&revealed, &folded], &hand), None);
+    }
 }
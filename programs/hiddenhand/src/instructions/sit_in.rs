@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{PlayerSeat, Table};
+
+#[derive(Accounts)]
+pub struct SitIn<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump,
+        has_one = player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+}
+
+/// Come back after being sat out for repeated timeouts.
+/// The seat is dealt in again from the next hand
+pub fn handler(ctx: Context<SitIn>) -> Result<()> {
+    let player_seat = &mut ctx.accounts.player_seat;
+
+    require!(player_seat.is_away(), HiddenHandError::PlayerNotAway);
+
+    player_seat.sit_in();
+
+    msg!("Player at seat {} is back at the table", player_seat.seat_index);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Start a bomb pot
+//!
+//! A casual format where every seated player antes the same amount and the
+//! flop is dealt with no pre-flop betting. The hand otherwise plays out like
+//! any other: the deal collects the ante in place of the blinds, then the
+//! hand waits for the flop reveal and betting opens on the flop.
+//!
+//! Requirements:
+//! - Only the table authority can call a bomb pot
+//! - Ante of at least the big blind
+//! - A variant with a board (there is no flop to jump to otherwise)
+//!
+//! Seats the ante puts all-in stay in the hand for the main pot.
+
+use anchor_lang::prelude::*;
+
+use crate::error::HiddenHandError;
+use crate::instructions::start_hand::{begin_hand, StartHand};
+
+pub fn handler(ctx: Context<StartHand>, ante: u64) -> Result<()> {
+    let table = &ctx.accounts.table;
+
+    // Unlike start_hand there's no timeout fallback - nobody else can force
+    // the whole table into a bomb pot
+    require!(
+        table.authority == ctx.accounts.caller.key(),
+        HiddenHandError::UnauthorizedAuthority
+    );
+    require!(ante >= table.big_blind, HiddenHandError::BetTooSmall);
+    require!(
+        table.game_type.community_card_count() > 0,
+        HiddenHandError::InvalidAction
+    );
+
+    begin_hand(ctx, ante)
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
+use crate::instructions::showdown::validate_seat_account;
 use crate::state::{DeckState, GamePhase, HandState, Table, TableStatus};
 
 #[derive(Accounts)]
@@ -40,15 +41,51 @@ pub struct StartHand<'info> {
 
 /// Start a new hand
 /// Authority can call immediately, anyone else must wait for timeout
+///
+/// On rotating-operator tables the new dealer's seat must be passed as a
+/// remaining account; that player becomes the hand operator.
 pub fn handler(ctx: Context<StartHand>) -> Result<()> {
-    let table = &mut ctx.accounts.table;
-    let caller = &ctx.accounts.caller;
+    begin_hand(ctx, 0)
+}
+
+/// Set up the next hand - shared by start_hand and start_bomb_pot.
+/// A non-zero `bomb_pot_ante` makes it a bomb pot: the deal collects the ante
+/// from every seat instead of the blinds
+pub(crate) fn begin_hand(ctx: Context<StartHand>, bomb_pot_ante: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    open_hand(
+        &mut accounts.table,
+        (&mut accounts.hand_state, ctx.bumps.hand_state),
+        (&mut accounts.deck_state, ctx.bumps.deck_state),
+        &accounts.caller.key(),
+        ctx.remaining_accounts,
+        bomb_pot_ante,
+    )
+}
+
+/// Advance the table to its next hand and initialize that hand's freshly
+/// created hand and deck accounts (passed with their PDA bumps). Shared by
+/// begin_hand and settle_and_start. On rotating-operator tables the dealer's
+/// seat is looked up among `seat_accounts`
+pub(crate) fn open_hand(
+    table: &mut Account<Table>,
+    (hand_state, hand_bump): (&mut Account<HandState>, u8),
+    (deck_state, deck_bump): (&mut Account<DeckState>, u8),
+    caller: &Pubkey,
+    seat_accounts: &[AccountInfo],
+    bomb_pot_ante: u64,
+) -> Result<()> {
     let clock = Clock::get()?;
 
+    require!(
+        table.status != TableStatus::Paused,
+        HiddenHandError::TablePaused
+    );
+
     // Authorization check: authority can call immediately, others must wait for timeout
-    let is_authority = table.authority == caller.key();
+    let is_authority = table.authority == *caller;
     if !is_authority {
-        let elapsed = clock.unix_timestamp - table.last_ready_time;
+        let elapsed = table.seconds_since_ready(clock.unix_timestamp);
         require!(
             elapsed >= ACTION_TIMEOUT_SECONDS,
             HiddenHandError::UnauthorizedAuthority
@@ -58,10 +95,14 @@ pub fn handler(ctx: Context<StartHand>) -> Result<()> {
 
     // Validate enough players
     require!(
-        table.current_players >= MIN_PLAYERS,
+        table.has_enough_players(),
         HiddenHandError::NotEnoughPlayers
     );
 
+    // Rate limit before the status check, so a repeat start in the same slot
+    // reports the gap rather than the hand it just started
+    table.check_hand_gap(clock.slot)?;
+
     require!(
         table.status == TableStatus::Waiting,
         HiddenHandError::HandAlreadyInProgress
@@ -70,58 +111,41 @@ pub fn handler(ctx: Context<StartHand>) -> Result<()> {
     // Increment hand number
     table.hand_number += 1;
     table.status = TableStatus::Playing;
+    table.last_hand_start_slot = clock.slot;
 
     // Advance dealer button
-    table.advance_dealer();
+    table.advance_dealer()?;
 
-    // Calculate positions
+    // Find small blind, big blind and first to act
+    // In heads-up (2 players): dealer = SB and acts first preflop, other player = BB
+    // In 3+ players: SB is left of dealer, BB is left of SB, UTG acts first
     let dealer_pos = table.dealer_position;
-    let max_players = table.max_players;
-    let is_heads_up = table.current_players == 2;
-
-    // Find small blind and big blind positions
-    // In heads-up (2 players): dealer = SB, other player = BB
-    // In 3+ players: SB is left of dealer, BB is left of SB
-    let (sb_pos, bb_pos, action_pos) = if is_heads_up {
-        // Heads-up: dealer is SB
-        let sb = dealer_pos;
-        let mut bb = (dealer_pos + 1) % max_players;
-        while !table.is_seat_occupied(bb) {
-            bb = (bb + 1) % max_players;
-        }
-        // In heads-up, SB (dealer) acts first preflop
-        (sb, bb, sb)
+    let (sb_pos, bb_pos, action_pos) = table.blind_positions();
+
+    // Pick who handles reveals/grants this hand
+    let hand_operator = if table.rotating_operator {
+        let dealer_seat = seat_accounts
+            .iter()
+            .filter_map(|info| validate_seat_account(info, &table.key(), &crate::ID))
+            .find(|seat| seat.seat_index == dealer_pos)
+            .ok_or(HiddenHandError::InvalidRemainingAccounts)?;
+        dealer_seat.player
     } else {
-        // Standard: SB is left of dealer
-        let mut sb = (dealer_pos + 1) % max_players;
-        while !table.is_seat_occupied(sb) {
-            sb = (sb + 1) % max_players;
-        }
-
-        let mut bb = (sb + 1) % max_players;
-        while !table.is_seat_occupied(bb) {
-            bb = (bb + 1) % max_players;
-        }
-
-        // First to act preflop is after big blind (UTG)
-        let mut action = (bb + 1) % max_players;
-        while !table.is_seat_occupied(action) {
-            action = (action + 1) % max_players;
-        }
-        (sb, bb, action)
+        table.authority
     };
 
     // Initialize hand state
-    let hand_state = &mut ctx.accounts.hand_state;
     hand_state.table = table.key();
     hand_state.hand_number = table.hand_number;
     hand_state.phase = GamePhase::Dealing;
     hand_state.pot = 0;
-    hand_state.current_bet = table.big_blind;
+    hand_state.contributed = [0; SEAT_BITMAP_SIZE];
+    // A bomb pot has no blinds to call - the deal sets the bet to the ante
+    hand_state.current_bet = if bomb_pot_ante > 0 { 0 } else { table.big_blind };
     hand_state.min_raise = table.big_blind;
     hand_state.dealer_position = dealer_pos;
     hand_state.action_on = action_pos;
-    hand_state.community_cards = vec![255, 255, 255, 255, 255]; // 255 = not revealed
+    hand_state.community_cards = vec![CARD_SENTINEL; COMMUNITY_CARD_COUNT]; // Not revealed
     hand_state.community_revealed = 0;
     hand_state.active_players = table.occupied_seats;
     hand_state.acted_this_round = 0;
@@ -130,19 +154,42 @@ pub fn handler(ctx: Context<StartHand>) -> Result<()> {
     hand_state.last_action_time = clock.unix_timestamp;
     hand_state.hand_start_time = clock.unix_timestamp;
     hand_state.awaiting_community_reveal = false;
-    hand_state.bump = ctx.bumps.hand_state;
+    hand_state.bump = hand_bump;
+    hand_state.community_card_count = table.game_type.community_card_count();
+    hand_state.street_start_pot = 0;
+    hand_state.hand_operator = hand_operator;
+    hand_state.any_bet_made_this_street = false;
+    // The small blind is in against the big blind before anyone acts
+    hand_state.matched_bet = if bomb_pot_ante > 0 { 0 } else { table.small_blind.min(table.big_blind) };
+    hand_state.uncalled_bet = 0;
+    hand_state.raise_closed = 0;
+    hand_state.bomb_pot_ante = bomb_pot_ante;
+    hand_state.folded_players = 0;
+    hand_state.departed_players = 0;
+    hand_state.max_raises_per_street = table.raise_cap();
+    hand_state.count_blind_as_opening_bet();
+    hand_state.sb_seat = sb_pos;
+    hand_state.bb_seat = bb_pos;
+    hand_state.payouts_done = false;
+    hand_state.revealed_players = 0;
+    hand_state.last_aggressor = NO_SEAT;
+    hand_state.next_to_reveal = NO_SEAT;
 
     // Initialize deck state
     // NOTE: With Modified Option B, VRF seed is NEVER stored!
     // Shuffle + encrypt happens atomically in callback_shuffle
-    let deck_state = &mut ctx.accounts.deck_state;
     deck_state.hand = hand_state.key();
     deck_state.cards = [0u128; DECK_SIZE]; // Will be shuffled in callback
     deck_state.deal_index = 0;
     deck_state.is_shuffled = false;
-    deck_state.bump = ctx.bumps.deck_state;
-    deck_state._reserved = [0u8; 33]; // Reserved for future use
+    deck_state.bump = deck_bump;
+    deck_state.seed_commitment = [0u8; 32]; // Set by callback_shuffle
+    deck_state.burn_cards = table.use_burn_cards;
 
+    msg!("Hand operator: {}", hand_operator);
+    if bomb_pot_ante > 0 {
+        msg!("Bomb pot: every seat antes {}, no pre-flop betting", bomb_pot_ante);
+    }
     msg!(
         "Hand #{} started. Dealer: seat {}, SB: seat {}, BB: seat {}, Action: seat {}",
         table.hand_number,
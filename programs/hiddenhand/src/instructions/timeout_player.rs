@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::error::HiddenHandError;
+use crate::events::PlayerAutoSatOut;
 use crate::state::{DeckState, GamePhase, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
 
 /// Timeout a player who hasn't acted within the time limit
@@ -21,7 +22,8 @@ pub struct TimeoutPlayer<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -81,12 +83,12 @@ pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
         HiddenHandError::InvalidAction
     );
 
-    // Check timeout has elapsed (60 seconds)
+    // Check timeout has elapsed (60 seconds, plus any time bank in use)
     let current_time = clock.unix_timestamp;
-    let elapsed = current_time - hand_state.last_action_time;
+    let elapsed = hand_state.seconds_since_last_action(current_time);
 
     require!(
-        elapsed >= ACTION_TIMEOUT_SECONDS,
+        hand_state.action_timed_out(current_time),
         HiddenHandError::ActionNotTimedOut
     );
 
@@ -97,7 +99,7 @@ pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
     );
 
     // Determine action: Check if possible, otherwise Fold
-    let can_check = player_seat.current_bet >= hand_state.current_bet;
+    let can_check = hand_state.can_check(player_seat.current_bet);
 
     if can_check {
         // Auto-CHECK - player doesn't lose anything
@@ -117,6 +119,22 @@ pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
         // Fold the player
         hand_state.fold_player(player_seat.seat_index);
         player_seat.status = PlayerStatus::Folded;
+
+        // Repeated timeouts sit the player out from the next hand
+        if player_seat.record_timeout() {
+            emit!(PlayerAutoSatOut {
+                table_id: table.table_id,
+                player: player_seat.player,
+                seat_index: player_seat.seat_index,
+                consecutive_timeouts: player_seat.consecutive_timeouts,
+                timestamp: current_time,
+            });
+            msg!(
+                "Seat {} timed out {} hands in a row - sitting out until sit_in",
+                player_seat.seat_index,
+                player_seat.consecutive_timeouts
+            );
+        }
     }
 
     // Update timestamp for next action
@@ -124,43 +142,22 @@ pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
 
     // Check if only one player remains (winner by default)
     if hand_state.active_count == 1 {
+        let uncalled = hand_state.return_uncalled_bet();
+        if uncalled > 0 {
+            msg!("Uncalled bet of {} returned from the pot", uncalled);
+        }
         hand_state.phase = GamePhase::Showdown;
         msg!("Only one player remains - advancing to showdown");
         return Ok(());
     }
 
-    // Find next active player
-    let next_player = hand_state.next_active_player(player_seat.seat_index, table.max_players);
-
-    if let Some(next) = next_player {
-        // Skip all-in players
-        let mut action_seat = next;
-        for _ in 0..table.max_players {
-            if !hand_state.is_player_all_in(action_seat) {
-                break;
-            }
-            if let Some(n) = hand_state.next_active_player(action_seat, table.max_players) {
-                action_seat = n;
-            } else {
-                break;
-            }
-        }
-        hand_state.action_on = action_seat;
-    }
-
-    // Check if betting round is complete
-    if hand_state.is_betting_complete() || !hand_state.can_anyone_bet() {
-        msg!("Betting round complete, advancing phase");
-
-        // Check if any more betting is possible
-        if hand_state.can_anyone_bet() {
-            // Normal phase advancement with card reveal
-            advance_phase_with_cards(hand_state, deck_state, table.max_players);
-        } else {
-            // All remaining players are all-in - run out to showdown
-            run_out_to_showdown(hand_state, deck_state);
-        }
-    }
+    pass_action_after_timeout(
+        hand_state,
+        deck_state,
+        player_seat.seat_index,
+        table.max_players,
+        table.big_blind,
+    )?;
 
     msg!(
         "Timeout processed. Action now on seat {}. Phase: {:?}",
@@ -171,117 +168,120 @@ pub fn handler(ctx: Context<TimeoutPlayer>) -> Result<()> {
     Ok(())
 }
 
-/// Advance to next phase and reveal community cards
-fn advance_phase_with_cards(hand_state: &mut HandState, deck_state: &DeckState, max_players: u8) {
-    // Find first active player left of dealer for post-flop action
-    let first_to_act = get_first_active_left_of_dealer(hand_state, max_players);
-
-    match hand_state.phase {
-        GamePhase::PreFlop => {
-            hand_state.phase = GamePhase::Flop;
-            hand_state.reset_betting_round();
-            // Reveal flop (3 cards)
-            hand_state.community_cards[0] = (deck_state.cards[0] & 0xFF) as u8;
-            hand_state.community_cards[1] = (deck_state.cards[1] & 0xFF) as u8;
-            hand_state.community_cards[2] = (deck_state.cards[2] & 0xFF) as u8;
-            hand_state.community_revealed = 3;
-            hand_state.action_on = first_to_act;
-            msg!("Advancing to Flop - cards: {}, {}, {}",
-                hand_state.community_cards[0],
-                hand_state.community_cards[1],
-                hand_state.community_cards[2]);
-        }
-        GamePhase::Flop => {
-            hand_state.phase = GamePhase::Turn;
-            hand_state.reset_betting_round();
-            // Reveal turn (4th card)
-            hand_state.community_cards[3] = (deck_state.cards[3] & 0xFF) as u8;
-            hand_state.community_revealed = 4;
-            hand_state.action_on = first_to_act;
-            msg!("Advancing to Turn - card: {}", hand_state.community_cards[3]);
-        }
-        GamePhase::Turn => {
-            hand_state.phase = GamePhase::River;
-            hand_state.reset_betting_round();
-            // Reveal river (5th card)
-            hand_state.community_cards[4] = (deck_state.cards[4] & 0xFF) as u8;
-            hand_state.community_revealed = 5;
-            hand_state.action_on = first_to_act;
-            msg!("Advancing to River - card: {}", hand_state.community_cards[4]);
-        }
-        GamePhase::River => {
-            hand_state.phase = GamePhase::Showdown;
-            msg!("Advancing to Showdown");
-        }
-        _ => {}
+/// Move the action on from the timed-out seat. It only ever lands on a seat
+/// that still has to act (not folded, not all-in); with nobody left to act the
+/// betting round closes, running the board out if no two players can bet
+pub(crate) fn pass_action_after_timeout(
+    hand_state: &mut HandState,
+    deck_state: &DeckState,
+    timed_out_seat: u8,
+    max_players: u8,
+    big_blind: u64,
+) -> Result<()> {
+    if let Some(next) = hand_state.next_to_act(timed_out_seat, max_players) {
+        hand_state.action_on = next;
+        return Ok(());
+    }
+
+    msg!("Betting round complete, advancing phase");
+    if hand_state.can_anyone_bet() {
+        // Normal phase advancement with card reveal
+        advance_phase_with_cards(hand_state, deck_state, max_players, big_blind)
+    } else {
+        // No two players can bet - run out to showdown
+        run_out_to_showdown(hand_state, deck_state)
     }
 }
 
-/// Find first active player to the left of dealer (for post-flop betting order)
-fn get_first_active_left_of_dealer(hand_state: &HandState, max_players: u8) -> u8 {
-    let dealer = hand_state.dealer_position;
-    let mut pos = (dealer + 1) % max_players;
+/// Advance to next phase and reveal community cards
+/// Plaintext decks are revealed inline; encrypted decks wait for reveal_community
+/// so both paths write the board through HandState::reveal_community_cards
+pub(crate) fn advance_phase_with_cards(
+    hand_state: &mut HandState,
+    deck_state: &DeckState,
+    max_players: u8,
+    big_blind: u64,
+) -> Result<()> {
+    // River, or variants without (more) board cards, go straight to showdown
+    let Some((start_idx, count)) = hand_state.next_community_reveal(false) else {
+        hand_state.phase = GamePhase::Showdown;
+        msg!("Advancing to Showdown");
+        return Ok(());
+    };
+
+    let Some(cards) = deck_state.plaintext_cards(start_idx, count) else {
+        hand_state.awaiting_community_reveal = true;
+        msg!("Community cards are encrypted - awaiting reveal_community");
+        return Ok(());
+    };
 
-    for _ in 0..max_players {
-        if hand_state.is_player_active(pos) && !hand_state.is_player_all_in(pos) {
-            return pos;
-        }
-        pos = (pos + 1) % max_players;
-    }
+    hand_state.reveal_community_cards(start_idx, &cards)?;
+    hand_state.advance_phase(big_blind);
 
-    // Fallback: return first active player even if all-in
-    pos = (dealer + 1) % max_players;
-    for _ in 0..max_players {
-        if hand_state.is_player_active(pos) {
-            return pos;
-        }
-        pos = (pos + 1) % max_players;
-    }
+    // Find first active player left of dealer for post-flop action
+    let first_to_act = hand_state.first_active_left_of_dealer(max_players);
+    hand_state.action_on = first_to_act;
+
+    msg!(
+        "Advancing to {:?} - cards: {:?}",
+        hand_state.phase,
+        cards
+    );
 
-    // Fallback (shouldn't happen with 2+ active players)
-    dealer
+    Ok(())
 }
 
 /// Run out all remaining community cards and advance to showdown
-fn run_out_to_showdown(hand_state: &mut HandState, deck_state: &DeckState) {
-    // Reveal all remaining community cards
-    match hand_state.phase {
-        GamePhase::PreFlop => {
-            // Reveal flop + turn + river
-            hand_state.community_cards[0] = (deck_state.cards[0] & 0xFF) as u8;
-            hand_state.community_cards[1] = (deck_state.cards[1] & 0xFF) as u8;
-            hand_state.community_cards[2] = (deck_state.cards[2] & 0xFF) as u8;
-            hand_state.community_cards[3] = (deck_state.cards[3] & 0xFF) as u8;
-            hand_state.community_cards[4] = (deck_state.cards[4] & 0xFF) as u8;
-            hand_state.community_revealed = 5;
-            msg!("Running out: Flop {}, {}, {} | Turn {} | River {}",
-                hand_state.community_cards[0],
-                hand_state.community_cards[1],
-                hand_state.community_cards[2],
-                hand_state.community_cards[3],
-                hand_state.community_cards[4]);
-        }
-        GamePhase::Flop => {
-            // Reveal turn + river
-            hand_state.community_cards[3] = (deck_state.cards[3] & 0xFF) as u8;
-            hand_state.community_cards[4] = (deck_state.cards[4] & 0xFF) as u8;
-            hand_state.community_revealed = 5;
-            msg!("Running out: Turn {} | River {}",
-                hand_state.community_cards[3],
-                hand_state.community_cards[4]);
-        }
-        GamePhase::Turn => {
-            // Reveal river
-            hand_state.community_cards[4] = (deck_state.cards[4] & 0xFF) as u8;
-            hand_state.community_revealed = 5;
-            msg!("Running out: River {}", hand_state.community_cards[4]);
-        }
-        GamePhase::River => {
-            // Already all revealed
-        }
-        _ => {}
+fn run_out_to_showdown(hand_state: &mut HandState, deck_state: &DeckState) -> Result<()> {
+    // Reveal all remaining community cards (none for board-less variants)
+    if let Some((start_idx, count)) = hand_state.next_community_reveal(true) {
+        let Some(cards) = deck_state.plaintext_cards(start_idx, count) else {
+            hand_state.awaiting_community_reveal = true;
+            msg!("Community cards are encrypted - awaiting reveal_community to run out the board");
+            return Ok(());
+        };
+
+        hand_state.reveal_community_cards(start_idx, &cards)?;
+        msg!("Running out: {:?}", cards);
     }
 
     hand_state.phase = GamePhase::Showdown;
     msg!("Advancing to Showdown - all players all-in or one active");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{deck_state, hand_state};
+
+    /// Test the action after a timeout never lands on an all-in seat: with only
+    /// all-ins left the board runs out, and a bettor facing an all-in still acts
+    #[test]
+    fn test_timeout_passes_action_past_all_ins() {
+        let deck = deck_state(&[]);
+
+        // Seat 1 timed out and folded; seats 0 and 2 are all-in
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.community_revealed = 3;
+        hand.active_players = 0b101;
+        hand.active_count = 2;
+        hand.all_in_players = 0b101;
+        hand.acted_this_round = 0b101;
+        pass_action_after_timeout(&mut hand, &deck, 1, 6, 20).unwrap();
+        assert_eq!(hand.phase, GamePhase::Showdown, "Board runs out instead of stalling");
+        assert_eq!(hand.community_revealed, 5);
+
+        // Seat 0 is all-in, seat 2 hasn't answered it yet
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.community_revealed = 3;
+        hand.active_players = 0b101;
+        hand.active_count = 2;
+        hand.all_in_players = 0b001;
+        hand.acted_this_round = 0b001;
+        pass_action_after_timeout(&mut hand, &deck, 1, 6, 20).unwrap();
+        assert_eq!(hand.action_on, 2);
+        assert_eq!(hand.phase, GamePhase::Flop);
+    }
 }
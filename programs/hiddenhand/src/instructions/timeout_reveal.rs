@@ -29,7 +29,8 @@ pub struct TimeoutReveal<'info> {
     #[account(
         mut,
         seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
-        bump = hand_state.bump
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
     )]
     pub hand_state: Account<'info, HandState>,
 
@@ -64,7 +65,7 @@ pub fn handler(ctx: Context<TimeoutReveal>, target_seat: u8) -> Result<()> {
 
     // Validate target is an active player who hasn't revealed
     require!(
-        target_player.status == PlayerStatus::Playing || target_player.status == PlayerStatus::AllIn,
+        target_player.is_in_hand(),
         HiddenHandError::PlayerNotActive
     );
 
@@ -74,10 +75,10 @@ pub fn handler(ctx: Context<TimeoutReveal>, target_seat: u8) -> Result<()> {
     );
 
     // Check timeout - must wait REVEAL_TIMEOUT_SECONDS (3 minutes)
-    let elapsed = clock.unix_timestamp - hand_state.last_action_time;
+    let elapsed = hand_state.seconds_since_last_action(clock.unix_timestamp);
     require!(
         elapsed >= REVEAL_TIMEOUT_SECONDS,
-        HiddenHandError::TimeoutNotReached
+        HiddenHandError::RevealNotTimedOut
     );
 
     msg!(
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::instructions::create_table::validate_stakes;
+use crate::error::HiddenHandError;
+use crate::events::BlindsUpdated;
+use crate::state::{Table, TableStatus};
+
+/// Change a table's stakes (authority only, between hands)
+#[derive(Accounts)]
+pub struct UpdateBlinds<'info> {
+    #[account(
+        constraint = table.authority == authority.key() @ HiddenHandError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+/// Apply new blinds and buy-in limits, checked like create_table's.
+/// Seated stacks are left as they are, even outside the new buy-in range
+pub fn handler(
+    ctx: Context<UpdateBlinds>,
+    small_blind: u64,
+    big_blind: u64,
+    min_buy_in: u64,
+    max_buy_in: u64,
+) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+
+    // The hand in progress was dealt at the old stakes
+    require!(
+        table.status == TableStatus::Waiting,
+        HiddenHandError::HandInProgress
+    );
+
+    validate_stakes(small_blind, big_blind, min_buy_in, max_buy_in)?;
+
+    table.small_blind = small_blind;
+    table.big_blind = big_blind;
+    table.min_buy_in = min_buy_in;
+    table.max_buy_in = max_buy_in;
+
+    emit!(BlindsUpdated {
+        table_id: table.table_id,
+        small_blind,
+        big_blind,
+        min_buy_in,
+        max_buy_in,
+    });
+
+    msg!(
+        "Blinds updated to {}/{}, buy-in {}-{}",
+        small_blind,
+        big_blind,
+        min_buy_in,
+        max_buy_in
+    );
+
+    Ok(())
+}
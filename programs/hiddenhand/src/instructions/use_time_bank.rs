@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{GamePhase, HandState, PlayerSeat, Table, TableStatus};
+
+#[derive(Accounts)]
+pub struct UseTimeBank<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hand_number.to_le_bytes()],
+        bump = hand_state.bump,
+        constraint = hand_state.is_current(table.hand_number) @ HiddenHandError::StaleHand
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    #[account(
+        mut,
+        seeds = [SEAT_SEED, table.key().as_ref(), &[player_seat.seat_index]],
+        bump = player_seat.bump,
+        has_one = player @ HiddenHandError::PlayerNotAtTable
+    )]
+    pub player_seat: Account<'info, PlayerSeat>,
+}
+
+/// Extend the acting player's clock by the table's time bank, once per hand.
+/// Pushes `last_action_time` forward so timeout_player can't fire until the
+/// extra window has also passed
+pub fn handler(ctx: Context<UseTimeBank>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand_state = &mut ctx.accounts.hand_state;
+    let player_seat = &mut ctx.accounts.player_seat;
+
+    require!(
+        table.status == TableStatus::Playing,
+        HiddenHandError::HandNotInProgress
+    );
+
+    require!(
+        table.time_bank_secs > 0,
+        HiddenHandError::TimeBankDisabled
+    );
+
+    require!(
+        matches!(
+            hand_state.phase,
+            GamePhase::PreFlop | GamePhase::Flop | GamePhase::Turn | GamePhase::River
+        ),
+        HiddenHandError::InvalidPhase
+    );
+
+    // Only the player on the clock can extend it
+    require!(
+        hand_state.action_on == player_seat.seat_index,
+        HiddenHandError::NotPlayersTurn
+    );
+
+    require!(
+        player_seat.can_act(),
+        HiddenHandError::PlayerFolded
+    );
+
+    require!(
+        !player_seat.time_bank_used,
+        HiddenHandError::TimeBankAlreadyUsed
+    );
+
+    hand_state.last_action_time = hand_state
+        .last_action_time
+        .checked_add(table.time_bank_secs as i64)
+        .ok_or(HiddenHandError::ArithmeticOverflow)?;
+    player_seat.time_bank_used = true;
+
+    msg!(
+        "Seat {} uses time bank: +{} seconds",
+        player_seat.seat_index,
+        table.time_bank_secs
+    );
+
+    Ok(())
+}
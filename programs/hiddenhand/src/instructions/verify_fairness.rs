@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::HiddenHandError;
+use crate::state::{seed_commitment, shuffle_deck, DeckState, GamePhase, HandHistory, HandState, Table};
+
+/// Read-only post-hand check that a hand was dealt from its committed VRF seed
+#[derive(Accounts)]
+#[instruction(hand_number: u64)]
+pub struct VerifyFairness<'info> {
+    #[account(
+        seeds = [TABLE_SEED, table.table_id.as_ref()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        seeds = [HAND_SEED, table.key().as_ref(), &hand_number.to_le_bytes()],
+        bump = hand_state.bump
+    )]
+    pub hand_state: Account<'info, HandState>,
+
+    #[account(
+        seeds = [DECK_SEED, table.key().as_ref(), &hand_number.to_le_bytes()],
+        bump = deck_state.bump
+    )]
+    pub deck_state: Account<'info, DeckState>,
+
+    /// Recorded hand, if the table keeps history - shown hole cards are checked too
+    #[account(
+        seeds = [HISTORY_SEED, table.key().as_ref(), &hand_number.to_le_bytes()],
+        bump = hand_history.bump
+    )]
+    pub hand_history: Option<Account<'info, HandHistory>>,
+}
+
+/// Check a replayed deck against what the hand showed: every revealed
+/// community card at its board position, and every shown hand as one of the
+/// dealt hole card pairs. `burn_cards` selects the deck layout the hand used
+pub fn deck_matches_hand(
+    deck: &[u8; DECK_SIZE],
+    burn_cards: bool,
+    board: &[u8],
+    shown_hands: &[(u8, u8)],
+) -> bool {
+    let board_matches = board
+        .iter()
+        .enumerate()
+        .all(|(i, &card)| deck[DeckState::community_index_for(burn_cards, i)] == card);
+
+    let hole_pairs: Vec<(u8, u8)> = (0..)
+        .step_by(HOLE_CARDS)
+        .map(|slot| DeckState::hole_index_for(burn_cards, slot))
+        .take_while(|&index| index + 1 < DECK_SIZE)
+        .map(|index| (deck[index], deck[index + 1]))
+        .collect();
+    let hands_match = shown_hands.iter().all(|hand| hole_pairs.contains(hand));
+
+    board_matches && hands_match
+}
+
+/// Recompute the seed commitment, replay the shuffle and confirm it produced
+/// the hand's board (and shown hands, with history). The seed is the VRF
+/// randomness passed to callback_shuffle, public once the hand is settled
+pub fn handler(ctx: Context<VerifyFairness>, hand_number: u64, revealed_seed: [u8; 32]) -> Result<()> {
+    let hand_state = &ctx.accounts.hand_state;
+    let deck_state = &ctx.accounts.deck_state;
+
+    // Post-hand only - the deal stays secret while the hand is live
+    require!(
+        hand_state.phase == GamePhase::Settled,
+        HiddenHandError::InvalidPhase
+    );
+
+    // Plaintext deals never had a VRF seed
+    require!(
+        deck_state.seed_commitment != [0u8; 32],
+        HiddenHandError::NoSeedCommitment
+    );
+
+    require!(
+        seed_commitment(&revealed_seed) == deck_state.seed_commitment,
+        HiddenHandError::FairnessCheckFailed
+    );
+
+    let deck = shuffle_deck(&revealed_seed);
+    let board = hand_state.revealed_community_cards();
+    let shown_hands: Vec<(u8, u8)> = ctx
+        .accounts
+        .hand_history
+        .as_ref()
+        .map(|history| {
+            history
+                .recorded_seats()
+                .iter()
+                .filter(|seat| seat.hole_card_1 < 52 && seat.hole_card_2 < 52)
+                .map(|seat| (seat.hole_card_1, seat.hole_card_2))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    require!(
+        deck_matches_hand(&deck, deck_state.burn_cards, &board, &shown_hands),
+        HiddenHandError::FairnessCheckFailed
+    );
+
+    msg!(
+        "Hand #{} verified: seed matches commitment, {} board card(s) and {} shown hand(s) reproduced",
+        hand_number,
+        board.len(),
+        shown_hands.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the shuffle replays from its seed and a tampered seed is caught
+    #[test]
+    fn test_verify_fairness_rejects_tampered_seed() {
+        let seed: [u8; 32] = core::array::from_fn(|i| (i as u8).wrapping_mul(37).wrapping_add(11));
+        let commitment = seed_commitment(&seed);
+        let deck = shuffle_deck(&seed);
+
+        // A real deck: every card exactly once
+        let mut sorted = deck;
+        sorted.sort_unstable();
+        assert_eq!(sorted, core::array::from_fn(|i| i as u8));
+        assert_eq!(shuffle_deck(&seed), deck, "Replay is deterministic");
+
+        // What the hand showed: the full board and the second seat's hand
+        let board = deck[..COMMUNITY_CARD_COUNT].to_vec();
+        let shown = [(deck[7], deck[8])];
+        assert!(deck_matches_hand(&deck, false, &board, &shown));
+
+        // Flipping one bit of the seed breaks the commitment and the replay
+        let mut tampered = seed;
+        tampered[31] ^= 1;
+        assert_ne!(seed_commitment(&tampered), commitment);
+        assert!(!deck_matches_hand(&shuffle_deck(&tampered), false, &board, &shown));
+
+        // A shown hand that wasn't dealt as a pair fails too
+        assert!(!deck_matches_hand(&deck, false, &board, &[(deck[6], deck[7])]));
+    }
+}
@@ -4,6 +4,8 @@ pub mod events;
 pub mod inco_cpi;
 pub mod instructions;
 pub mod state;
+#[cfg(test)]
+mod test_fixtures;
 
 use anchor_lang::prelude::*;
 
@@ -35,9 +37,62 @@ pub mod hiddenhand {
         instructions::create_table::handler(ctx, table_id, small_blind, big_blind, min_buy_in, max_buy_in, max_players)
     }
 
+    /// Update table options such as the game type (authority only, between hands)
+    pub fn configure_table(ctx: Context<ConfigureTable>, config: TableConfig) -> Result<()> {
+        instructions::configure_table::handler(ctx, config)
+    }
+
+    /// Change the blinds and buy-in limits (authority only, between hands)
+    pub fn update_blinds(
+        ctx: Context<UpdateBlinds>,
+        small_blind: u64,
+        big_blind: u64,
+        min_buy_in: u64,
+        max_buy_in: u64,
+    ) -> Result<()> {
+        instructions::update_blinds::handler(ctx, small_blind, big_blind, min_buy_in, max_buy_in)
+    }
+
+    /// Pause the table between hands: start_hand fails until resume_table
+    /// Seats and chips are untouched, and players can still leave
+    pub fn pause_table(ctx: Context<PauseTable>) -> Result<()> {
+        instructions::pause_table::handler(ctx)
+    }
+
+    /// Resume a paused table (authority only)
+    pub fn resume_table(ctx: Context<ResumeTable>) -> Result<()> {
+        instructions::resume_table::handler(ctx)
+    }
+
     /// Join a table with a buy-in
-    pub fn join_table(ctx: Context<JoinTable>, seat_index: u8, buy_in: u64) -> Result<()> {
-        instructions::join_table::handler(ctx, seat_index, buy_in)
+    /// Private tables need a Merkle proof of the player's pubkey against the
+    /// table's allowlist root; public tables take an empty proof
+    pub fn join_table(
+        ctx: Context<JoinTable>,
+        seat_index: u8,
+        buy_in: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::join_table::handler(ctx, seat_index, buy_in, allowlist_proof)
+    }
+
+    /// Join a table at the lowest free seat (client derives the same seat PDA)
+    pub fn join_table_auto(
+        ctx: Context<JoinTableAuto>,
+        buy_in: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::join_table_auto::handler(ctx, buy_in, allowlist_proof)
+    }
+
+    /// Queue for a seat at a full table
+    pub fn join_waitlist(ctx: Context<JoinWaitlist>) -> Result<()> {
+        instructions::join_waitlist::handler(ctx)
+    }
+
+    /// Buy more chips between hands, up to the table's add-on limit per seat
+    pub fn add_on(ctx: Context<AddOn>, amount: u64) -> Result<()> {
+        instructions::add_on::handler(ctx, amount)
     }
 
     /// Leave a table and cash out
@@ -45,22 +100,48 @@ pub mod hiddenhand {
         instructions::leave_table::handler(ctx)
     }
 
+    /// Fold if still in the hand (in turn or not) and cash out in one go
+    pub fn fold_and_leave(ctx: Context<FoldAndLeave>) -> Result<()> {
+        instructions::fold_and_leave::handler(ctx)
+    }
+
+    /// Remove a player and return their chips (table authority only, between hands)
+    pub fn kick_player(ctx: Context<KickPlayer>, seat_index: u8) -> Result<()> {
+        instructions::kick_player::handler(ctx, seat_index)
+    }
+
     /// Start a new hand (table authority only)
+    /// Rotating-operator tables: pass the new dealer's seat as a remaining account
     pub fn start_hand(ctx: Context<StartHand>) -> Result<()> {
         instructions::start_hand::handler(ctx)
     }
 
+    /// Start a bomb pot (table authority only)
+    /// Every dealt seat antes `ante` and the hand skips straight to the flop
+    pub fn start_bomb_pot(ctx: Context<StartHand>, ante: u64) -> Result<()> {
+        instructions::start_bomb_pot::handler(ctx, ante)
+    }
+
     /// Perform a player action (fold, check, call, raise, all-in)
     pub fn player_action(ctx: Context<PlayerAction>, action: Action) -> Result<()> {
         instructions::player_action::handler(ctx, action)
     }
 
     /// Showdown - evaluate hands and distribute pot
-    /// Remaining accounts should be all player seat accounts
-    pub fn showdown(ctx: Context<Showdown>) -> Result<()> {
+    /// Remaining accounts should be all player seat accounts, then (on
+    /// cap_stack_per_hand tables) the players' wallets for any stack excess
+    pub fn showdown<'info>(ctx: Context<'_, '_, 'info, 'info, Showdown<'info>>) -> Result<()> {
         instructions::showdown::handler(ctx)
     }
 
+    /// Showdown and start the next hand in one transaction (table authority only)
+    /// Remaining accounts should be all player seat accounts, plus wallets as for showdown
+    pub fn settle_and_start<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleAndStart<'info>>,
+    ) -> Result<()> {
+        instructions::settle_and_start::handler(ctx)
+    }
+
     /// Deal cards to all players and post blinds
     /// SB and BB seats are named accounts, others via remaining_accounts
     /// NOTE: For provably fair games, use request_shuffle + callback_shuffle instead
@@ -111,6 +192,16 @@ pub mod hiddenhand {
         instructions::timeout_player::handler(ctx)
     }
 
+    /// Extend your own action clock by the table's time bank (once per hand)
+    pub fn use_time_bank(ctx: Context<UseTimeBank>) -> Result<()> {
+        instructions::use_time_bank::handler(ctx)
+    }
+
+    /// Return to the table after being sat out for repeated timeouts
+    pub fn sit_in(ctx: Context<SitIn>) -> Result<()> {
+        instructions::sit_in::handler(ctx)
+    }
+
     // ============================================================
     // Inco Encryption Instructions (Phase 2 - Cryptographic Privacy)
     // ============================================================
@@ -132,6 +223,14 @@ pub mod hiddenhand {
         instructions::encrypt_hole_cards::grant_allowance_handler(ctx, seat_index)
     }
 
+    /// Grant hole card allowances for every active player in one transaction
+    /// remaining_accounts: [seat, allowance_card1, allowance_card2, player] per player
+    pub fn grant_all_allowances<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GrantAllAllowances<'info>>,
+    ) -> Result<()> {
+        instructions::grant_all_allowances::handler(ctx)
+    }
+
     /// Reveal cards at showdown with Ed25519 signature verification
     ///
     /// Players call this at Showdown phase to reveal their decrypted cards.
@@ -159,6 +258,13 @@ pub mod hiddenhand {
         instructions::timeout_reveal::handler(ctx, target_seat)
     }
 
+    /// Muck an unrevealed hand at showdown instead of showing it
+    /// Allowed once another player still in the hand has revealed; pass that
+    /// player's seat account in remaining accounts
+    pub fn muck(ctx: Context<Muck>) -> Result<()> {
+        instructions::muck::handler(ctx)
+    }
+
     /// Close an inactive table and return all funds to players
     /// Can be called by anyone after 1 hour of inactivity
     /// Table must be in Waiting status (not mid-hand)
@@ -167,11 +273,25 @@ pub mod hiddenhand {
         instructions::close_inactive_table::handler(ctx)
     }
 
+    /// Archive an empty table that has outlived its maximum lifetime
+    /// Can be called by anyone after TABLE_MAX_LIFETIME_SLOTS (~30 days)
+    /// Closes the table account and returns its rent to the authority
+    pub fn archive_table(ctx: Context<ArchiveTable>) -> Result<()> {
+        instructions::archive_table::handler(ctx)
+    }
+
+    /// Close a settled hand's HandState and DeckState
+    /// Can be called by anyone once the hand has been paid out
+    /// Returns the rent of both accounts to the authority
+    pub fn close_hand(ctx: Context<CloseHand>, hand_number: u64) -> Result<()> {
+        instructions::close_hand::handler(ctx, hand_number)
+    }
+
     /// Grant community card allowances to a player
     /// This enables the player to decrypt community cards via Inco, which is needed
     /// if they want to reveal community cards when authority is AFK
     ///
-    /// Called by authority after VRF shuffle for each active player.
+    /// Called by authority (or the hand operator) after VRF shuffle for each active player.
     /// remaining_accounts: 5 allowance PDAs for community cards [card0-card4]
     pub fn grant_community_allowances<'info>(
         ctx: Context<'_, '_, 'info, 'info, GrantCommunityAllowances<'info>>,
@@ -182,7 +302,7 @@ pub mod hiddenhand {
 
     /// Reveal community cards (flop/turn/river) with Ed25519 signature verification
     ///
-    /// Authority calls this when betting round completes and community cards need to be revealed.
+    /// Authority (or the hand operator) calls this when betting round completes and community cards need to be revealed.
     /// Community cards are encrypted during VRF shuffle for privacy - this reveals them.
     ///
     /// The transaction must include Ed25519 verification instructions for each card from
@@ -192,9 +312,43 @@ pub mod hiddenhand {
     /// - PreFlop -> Flop: 3 cards (or 5 if all-in runout)
     /// - Flop -> Turn: 1 card (or 2 if all-in runout)
     /// - Turn -> River: 1 card
+    ///
+    /// Optional remaining_accounts: player seats to cross-check against revealed hole cards
     pub fn reveal_community(ctx: Context<RevealCommunity>, cards: Vec<u8>) -> Result<()> {
         instructions::reveal_community::handler(ctx, cards)
     }
+
+    // ============================================================
+    // Views (read-only, simulate to read the return value)
+    // ============================================================
+
+    /// Minimum total bet the seat must reach to raise (`current_bet + min_raise`,
+    /// capped at the seat's stack)
+    pub fn get_min_raise(ctx: Context<GetMinRaise>) -> Result<u64> {
+        instructions::get_min_raise::handler(ctx)
+    }
+
+    /// Whether a seat has revealed its hole cards, the cards if so, and whether
+    /// it can be mucked by timeout_reveal
+    pub fn get_reveal_status(ctx: Context<GetRevealStatus>) -> Result<RevealStatus> {
+        instructions::get_reveal_status::handler(ctx)
+    }
+
+    /// Win/tie counts for every seat over all remaining runouts once players
+    /// are all-in and have tabled their hands
+    /// Remaining accounts should be the seat accounts still in the hand
+    pub fn all_in_equity(ctx: Context<AllInEquity>) -> Result<EquityPreview> {
+        instructions::all_in_equity::handler(ctx)
+    }
+
+    /// Check a settled hand was dealt from its committed VRF seed
+    pub fn verify_fairness(
+        ctx: Context<VerifyFairness>,
+        hand_number: u64,
+        revealed_seed: [u8; 32],
+    ) -> Result<()> {
+        instructions::verify_fairness::handler(ctx, hand_number, revealed_seed)
+    }
 }
 
 /// Unit tests using LiteSVM for fast execution
@@ -210,6 +364,15 @@ mod unit_tests {
         assert!(MAX_PLAYERS <= 9, "Max players should be reasonable (<=9)");
     }
 
+    /// Test the card sentinel can never be mistaken for a real card
+    #[test]
+    fn test_card_sentinel_is_not_a_card() {
+        assert!(
+            !(0..=51).contains(&CARD_SENTINEL),
+            "Sentinel must be outside the 0-51 card range"
+        );
+    }
+
     /// Test player status transitions
     #[test]
     fn test_player_status_transitions() {
@@ -249,8 +412,9 @@ mod unit_tests {
         // 8 (discriminator) + 32 (table) + 32 (player) + 1 (seat_index) +
         // 8 (chips) + 8 (current_bet) + 8 (total_bet) + 16 (hole_card_1) +
         // 16 (hole_card_2) + 1 (revealed_card_1) + 1 (revealed_card_2) +
-        // 1 (cards_revealed) + 1 (status) + 1 (has_acted) + 1 (bump)
-        let expected_size = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 16 + 16 + 1 + 1 + 1 + 1 + 1 + 1;
+        // 1 (cards_revealed) + 1 (status) + 1 (has_acted) + 1 (bump) + 1 (addon_count) +
+        // 1 (time_bank_used) + 1 (consecutive_timeouts) + 4 (hands_played) + 4 (hands_won)
+        let expected_size = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 16 + 16 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 4 + 4;
         assert_eq!(PlayerSeat::SIZE, expected_size, "PlayerSeat size mismatch");
     }
 
@@ -262,11 +426,32 @@ mod unit_tests {
         // 8 (discriminator) + 32 (authority) + 32 (table_id) + 8 (small_blind) +
         // 8 (big_blind) + 8 (min_buy_in) + 8 (max_buy_in) + 1 (max_players) +
         // 1 (current_players) + 1 (status) + 8 (hand_number) + 1 (occupied_seats) +
-        // 1 (dealer_position) + 8 (last_ready_time) + 1 (bump)
-        let expected_size = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 1 + 1 + 8 + 1;
+        // 1 (dealer_position) + 8 (last_ready_time) + 1 (bump) + 1 (game_type) +
+        // 1 (rotating_operator) + 1 (record_history) + 1 (max_addons) +
+        // 8 (addon_after_hand) + 1 (min_players_to_start) + 4 (time_bank_secs) +
+        // 32 * 8 (seat_owners) + 8 (last_hand_start_slot) + 8 (created_slot) +
+        // 1 (use_burn_cards) + 1 (betting_mode) + 1 (max_raises_per_street) +
+        // 1 (track_stats) + 32 (allowlist_root) + 1 (ordered_reveal) +
+        // 1 (cap_stack_per_hand) + 4 + 32 * 8 (waitlist)
+        let expected_size = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 1 + 1 + 8 + 1 + 1 + 1 + 1 + 1 + 8 + 1 + 4 + 32 * 8 + 8 + 8 + 1 + 1 + 1 + 1 + 32 + 1 + 1 + 4 + 32 * 8;
         assert_eq!(Table::SIZE, expected_size, "Table size mismatch");
     }
 
+    /// Test hand history size calculation
+    #[test]
+    fn test_hand_history_size() {
+        use state::{HandHistory, HistorySeat};
+
+        // 32 (player) + 1 (seat_index) + 2 (hole cards) + 8 (chips_bet) + 8 (chips_won) + 1 (folded)
+        assert_eq!(HistorySeat::SIZE, 32 + 1 + 2 + 8 + 8 + 1, "HistorySeat size mismatch");
+
+        // 8 (discriminator) + 32 (table) + 8 (hand_number) + 8 (timestamp) + 5 (board) +
+        // 8 (total_pot) + 8 seats (one for every bitmap seat, like HandCompleted) +
+        // 1 (seat_count) + 1 (bump)
+        let expected_size = 8 + 32 + 8 + 8 + 5 + 8 + HistorySeat::SIZE * 8 + 1 + 1;
+        assert_eq!(HandHistory::SIZE, expected_size, "HandHistory size mismatch");
+    }
+
     /// Test action enum serialization
     #[test]
     fn test_action_variants() {
@@ -284,6 +469,7 @@ mod unit_tests {
         assert_eq!(raise, Action::Raise { amount: 1000 });
         assert_ne!(raise, Action::Raise { amount: 2000 });
         assert_ne!(all_in, fold);
+        assert_ne!(Action::Bet { amount: 1000 }, raise);
     }
 
     /// Test error codes exist
@@ -307,6 +493,25 @@ mod unit_tests {
         );
     }
 
+    /// Test each timeout guard has its own error code
+    #[test]
+    fn test_timeout_error_codes() {
+        use error::HiddenHandError;
+        use std::collections::BTreeSet;
+
+        let guards = [
+            HiddenHandError::ActionNotTimedOut,   // timeout_player
+            HiddenHandError::RevealNotTimedOut,   // timeout_reveal
+            HiddenHandError::TableNotInactive,    // close_inactive_table
+            HiddenHandError::DealNotTimedOut,     // deal_cards / deal_cards_encrypted
+            HiddenHandError::ShowdownNotTimedOut, // showdown
+        ];
+        let codes: BTreeSet<u32> = guards.iter().map(|e| *e as u32).collect();
+        assert_eq!(codes.len(), guards.len(), "Timeout errors must be distinct");
+        assert!(!codes.contains(&(HiddenHandError::UnauthorizedAuthority as u32)));
+        assert!(!codes.contains(&(HiddenHandError::TimeoutNotReached as u32)));
+    }
+
     /// Test seat bitmap operations
     #[test]
     fn test_seat_bitmap_operations() {
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Merkle leaf for an allowlisted player: SHA-256 of the pubkey bytes
+pub fn allowlist_leaf(player: &Pubkey) -> [u8; 32] {
+    Sha256::digest(player.as_ref()).into()
+}
+
+/// Parent of two Merkle nodes. Pairs are hashed in sorted order, so a proof
+/// is just the sibling hashes from leaf to root - no left/right flags
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(lo);
+    hasher.update(hi);
+    hasher.finalize().into()
+}
+
+/// Check `proof` leads from the player's leaf to `root`
+pub fn verify_allowlist_proof(root: &[u8; 32], player: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(allowlist_leaf(player), |node, sibling| allowlist_node(&node, sibling));
+    computed == *root
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::DECK_SIZE;
+use crate::constants::{BURN_CARD_COUNT, COMMUNITY_CARD_COUNT, DECK_SIZE};
+use crate::error::HiddenHandError;
 
 /// Encrypted deck state for a hand
 /// Cards are stored as Inco encrypted handles
 ///
 /// SECURITY NOTE: The VRF seed is NEVER stored here!
 /// It only exists in memory during the atomic shuffle+encrypt in callback_shuffle.
-/// This eliminates the account state leak vector.
+/// This eliminates the account state leak vector. Only a one-way commitment to
+/// it is kept, so the shuffle can be checked with verify_fairness after the hand.
 #[account]
 pub struct DeckState {
     /// Reference to hand
@@ -15,7 +17,8 @@ pub struct DeckState {
 
     /// Shuffled encrypted cards (Inco handles)
     /// Each u128 is a handle to an encrypted card value (0-51)
-    /// First 5 cards (indices 0-4) are community cards (plaintext until revealed)
+    /// First 5 cards (indices 0-4) are community cards (plaintext until revealed),
+    /// or the first 8 with burn cards (burn, flop, burn, turn, burn, river)
     /// Remaining cards are encrypted hole cards
     pub cards: [u128; DECK_SIZE],
 
@@ -28,9 +31,14 @@ pub struct DeckState {
     /// PDA bump
     pub bump: u8,
 
-    /// Reserved space for future use (maintains account size compatibility)
-    /// Previously: vrf_seed [u8; 32] + seed_received bool = 33 bytes
-    pub _reserved: [u8; 33],
+    /// SHA-256 of the VRF randomness the deck was shuffled with (zero for
+    /// plaintext deals). Reveals nothing about the deal during play
+    pub seed_commitment: [u8; 32],
+
+    /// Burn a card before each street (copied from the table at start_hand)
+    /// Previously: vrf_seed [u8; 32] + seed_received bool = 33 bytes, now
+    /// seed_commitment + this flag
+    pub burn_cards: bool,
 }
 
 impl DeckState {
@@ -40,7 +48,69 @@ impl DeckState {
         1 +  // deal_index
         1 +  // is_shuffled
         1 +  // bump
-        33;  // _reserved (maintains size compatibility)
+        32 + // seed_commitment
+        1;   // burn_cards
+
+    /// Deck slots reserved ahead of the hole cards: the board, plus a burn
+    /// card before the flop, turn and river when burns are on
+    pub const fn board_slots(burn_cards: bool) -> usize {
+        if burn_cards {
+            COMMUNITY_CARD_COUNT + BURN_CARD_COUNT
+        } else {
+            COMMUNITY_CARD_COUNT
+        }
+    }
+
+    /// Deck index of community card `i` (0 = first flop card). With burns
+    /// the board sits at 1-3 (flop), 5 (turn) and 7 (river)
+    pub const fn community_index_for(burn_cards: bool, i: usize) -> usize {
+        if !burn_cards {
+            return i;
+        }
+        // One burn before the flop, then one more before each later street
+        let burns_before = 1 + (i >= 3) as usize + (i >= 4) as usize;
+        i + burns_before
+    }
+
+    /// Deck index of hole card slot `seat_slot` (slots are numbered in deal order,
+    /// HOLE_CARDS per seat, starting right after the board slots)
+    pub const fn hole_index_for(burn_cards: bool, seat_slot: usize) -> usize {
+        Self::board_slots(burn_cards) + seat_slot
+    }
+
+    /// Deck index of community card `i` in this deck's layout
+    pub fn community_index(&self, i: usize) -> usize {
+        Self::community_index_for(self.burn_cards, i)
+    }
+
+    /// Deck index of hole card slot `seat_slot` in this deck's layout
+    pub fn hole_index(&self, seat_slot: usize) -> usize {
+        Self::hole_index_for(self.burn_cards, seat_slot)
+    }
+
+    /// Handle for community card `i` (0 = first flop card)
+    pub fn community_handle(&self, i: usize) -> u128 {
+        debug_assert!(i < COMMUNITY_CARD_COUNT);
+        self.cards[self.community_index(i)]
+    }
+
+    /// Store the handle for community card `i`
+    pub fn set_community_handle(&mut self, i: usize, handle: u128) {
+        debug_assert!(i < COMMUNITY_CARD_COUNT);
+        let index = self.community_index(i);
+        self.cards[index] = handle;
+    }
+
+    /// Handle for hole card slot `seat_slot`
+    pub fn hole_handle(&self, seat_slot: usize) -> u128 {
+        self.cards[self.hole_index(seat_slot)]
+    }
+
+    /// Store the handle for hole card slot `seat_slot`
+    pub fn set_hole_handle(&mut self, seat_slot: usize, handle: u128) {
+        let index = self.hole_index(seat_slot);
+        self.cards[index] = handle;
+    }
 
     /// Deal next card, returns the encrypted handle
     pub fn deal_card(&mut self) -> Option<u128> {
@@ -62,6 +132,35 @@ impl DeckState {
     pub fn cards_remaining(&self) -> u8 {
         (DECK_SIZE as u8).saturating_sub(self.deal_index)
     }
+
+    /// Read a range of community cards stored as plaintext (legacy/plaintext deals)
+    /// Returns None if any of them is an encrypted handle, which can only be
+    /// revealed through reveal_community's Ed25519 attestation
+    pub fn plaintext_cards(&self, start: usize, count: usize) -> Option<Vec<u8>> {
+        (start..start + count)
+            .map(|i| self.community_handle(i))
+            .map(|handle| if handle <= 51 { Some(handle as u8) } else { None })
+            .collect()
+    }
+
+    /// On a plaintext board, whether `cards` are exactly the stored cards from
+    /// `start`. None if the board is encrypted and needs an attestation instead
+    pub fn plaintext_reveal_matches(&self, start: usize, cards: &[u8]) -> Option<bool> {
+        self.plaintext_cards(start, cards.len())
+            .map(|stored| stored == cards)
+    }
+
+    /// Check a shuffled deck holds every card 0-51 exactly once, before any
+    /// of it is encrypted and dealt
+    pub fn verify_permutation(deck: &[u8; DECK_SIZE]) -> Result<()> {
+        let mut seen: u64 = 0;
+        for &card in deck.iter() {
+            require!((card as usize) < DECK_SIZE, HiddenHandError::InvalidDeck);
+            require!(seen & (1 << card) == 0, HiddenHandError::InvalidDeck);
+            seen |= 1 << card;
+        }
+        Ok(())
+    }
 }
 
 /// Helper functions for card encoding
@@ -69,6 +168,8 @@ impl DeckState {
 /// Suit: value / 13 (0=Hearts, 1=Diamonds, 2=Clubs, 3=Spades)
 /// Rank: value % 13 (0=2, 1=3, ..., 8=10, 9=J, 10=Q, 11=K, 12=A)
 pub mod card_utils {
+    use crate::constants::CARD_SENTINEL;
+
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum Suit {
         Hearts = 0,
@@ -125,6 +226,15 @@ pub mod card_utils {
         (suit as u8) * 13 + (rank as u8)
     }
 
+    /// Find the first card in `cards` that also appears in `seen`
+    /// Unrevealed cards (CARD_SENTINEL) are ignored
+    pub fn find_duplicate_card(cards: &[u8], seen: &[u8]) -> Option<u8> {
+        cards
+            .iter()
+            .copied()
+            .find(|&card| card != CARD_SENTINEL && seen.contains(&card))
+    }
+
     /// Display card as string (for debugging/UI)
     pub fn card_to_string(card: u8) -> String {
         let rank = match card % 13 {
@@ -153,3 +263,111 @@ pub mod card_utils {
         format!("{}{}", rank, suit)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{CARD_SENTINEL, HOLE_CARDS};
+    use crate::test_fixtures::deck_state;
+
+    /// Test named deck accessors match direct indexing
+    #[test]
+    fn test_deck_accessors() {
+        let handles: Vec<u128> = (0..DECK_SIZE as u128).map(|i| 1_000 + i).collect();
+        let mut deck = deck_state(&handles);
+
+        for i in 0..COMMUNITY_CARD_COUNT {
+            assert_eq!(deck.community_handle(i), deck.cards[i]);
+        }
+        for slot in 0..DECK_SIZE - COMMUNITY_CARD_COUNT {
+            assert_eq!(deck.hole_handle(slot), deck.cards[COMMUNITY_CARD_COUNT + slot]);
+        }
+
+        // First hole card sits right after the board, the last at the end of the deck
+        assert_eq!(deck.hole_index(0), 5);
+        assert_eq!(deck.hole_index(DECK_SIZE - COMMUNITY_CARD_COUNT - 1), DECK_SIZE - 1);
+
+        deck.set_community_handle(4, 7);
+        deck.set_hole_handle(HOLE_CARDS, 9); // first card of the second seat dealt
+        assert_eq!(deck.cards[4], 7);
+        assert_eq!(deck.cards[COMMUNITY_CARD_COUNT + HOLE_CARDS], 9);
+    }
+
+    /// Test burn cards shift the board to 1-3, 5 and 7 and the hole cards to 8+
+    #[test]
+    fn test_deck_burn_card_layout() {
+        let handles: Vec<u128> = (0..DECK_SIZE as u128).collect();
+        let mut deck = deck_state(&handles);
+        deck.burn_cards = true;
+
+        let board: Vec<usize> = (0..COMMUNITY_CARD_COUNT).map(|i| deck.community_index(i)).collect();
+        assert_eq!(board, vec![1, 2, 3, 5, 7]);
+        assert_eq!(DeckState::board_slots(true), 8);
+        assert_eq!(deck.hole_index(0), 8);
+
+        // Flop, turn and river reveals read the burn-adjusted cards
+        assert_eq!(deck.plaintext_cards(0, 3), Some(vec![1, 2, 3]));
+        assert_eq!(deck.plaintext_cards(3, 1), Some(vec![5]));
+        assert_eq!(deck.plaintext_cards(4, 1), Some(vec![7]));
+        assert_eq!(deck.hole_handle(0), 8);
+        assert_eq!(deck.hole_handle(HOLE_CARDS + 1), 11);
+
+        // The burns (0, 4, 6) are never part of the board or a hand
+        for burn in [0, 4, 6] {
+            assert!(!board.contains(&burn));
+            assert!(burn < deck.hole_index(0));
+        }
+
+        // Without burns the layout is unchanged
+        deck.burn_cards = false;
+        assert_eq!(deck.plaintext_cards(3, 2), Some(vec![3, 4]));
+        assert_eq!(deck.hole_index(0), COMMUNITY_CARD_COUNT);
+    }
+
+    /// Test plaintext deck reads refuse encrypted handles
+    #[test]
+    fn test_plaintext_cards() {
+        let plaintext = deck_state(&[12, 25, 38, 51, 0]);
+        assert_eq!(plaintext.plaintext_cards(0, 3), Some(vec![12, 25, 38]));
+        assert_eq!(plaintext.plaintext_cards(3, 2), Some(vec![51, 0]));
+
+        let encrypted = deck_state(&[1 << 100, 2 << 100, 3 << 100, 4 << 100, 5 << 100]);
+        assert_eq!(encrypted.plaintext_cards(0, 3), None);
+    }
+
+    /// Test board cards colliding with revealed hole cards are detected
+    #[test]
+    fn test_duplicate_card_detection() {
+        use card_utils::find_duplicate_card;
+
+        let flop = [12, 25, 38];
+
+        // Intentional collision: a player's revealed hole card is on the board
+        assert_eq!(find_duplicate_card(&flop, &[25, 40]), Some(25));
+
+        // Distinct cards and unrevealed hole cards pass
+        assert_eq!(find_duplicate_card(&flop, &[0, 1]), None);
+        assert_eq!(find_duplicate_card(&flop, &[CARD_SENTINEL, CARD_SENTINEL]), None);
+        assert_eq!(find_duplicate_card(&[CARD_SENTINEL], &[CARD_SENTINEL]), None);
+    }
+
+    /// Test a plaintext board is revealed by matching the stored cards, and an
+    /// encrypted board still needs the Inco attestation
+    #[test]
+    fn test_plaintext_community_reveal_matches() {
+        let mut handles: Vec<u128> = vec![12, 30, 45, 7, 51];
+        let deck = deck_state(&handles);
+
+        assert_eq!(deck.plaintext_reveal_matches(0, &[12, 30, 45]), Some(true));
+        assert_eq!(deck.plaintext_reveal_matches(3, &[7]), Some(true));
+        assert_eq!(deck.plaintext_reveal_matches(3, &[7, 51]), Some(true), "All-in runout");
+        assert_eq!(deck.plaintext_reveal_matches(0, &[12, 45, 30]), Some(false), "Order matters");
+        assert_eq!(deck.plaintext_reveal_matches(4, &[50]), Some(false));
+
+        // Inco handles on the board: no plaintext shortcut
+        handles[3] = 0xdead_beef_u128 << 64;
+        let deck = deck_state(&handles);
+        assert_eq!(deck.plaintext_reveal_matches(0, &[12, 30, 45]), Some(true));
+        assert_eq!(deck.plaintext_reveal_matches(3, &[7]), None);
+    }
+}
@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    ACTION_TIMEOUT_SECONDS, CARD_SENTINEL, COMMUNITY_CARD_COUNT, HOLE_CARDS, NO_SEAT,
+    SEAT_BITMAP_SIZE,
+};
+use crate::error::HiddenHandError;
+use crate::state::{seats_from, TableStatus};
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum GamePhase {
     /// Cards being dealt
@@ -24,6 +31,21 @@ impl Default for GamePhase {
     }
 }
 
+impl GamePhase {
+    /// Display name for logs and clients
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GamePhase::Dealing => "dealing",
+            GamePhase::PreFlop => "preflop",
+            GamePhase::Flop => "flop",
+            GamePhase::Turn => "turn",
+            GamePhase::River => "river",
+            GamePhase::Showdown => "showdown",
+            GamePhase::Settled => "settled",
+        }
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct HandState {
@@ -82,6 +104,72 @@ pub struct HandState {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Community cards dealt this hand (0-5, from the table's game type)
+    pub community_card_count: u8,
+
+    /// Pot size when the current betting street started (pot-limit sizing)
+    pub street_start_pot: u64,
+
+    /// Who performs authority duties (reveals, grants) for this hand.
+    /// The table authority, or the dealer-seat player on rotating-operator tables
+    pub hand_operator: Pubkey,
+
+    /// Whether anyone called, bet, raised, or went all-in this street
+    pub any_bet_made_this_street: bool,
+
+    /// Highest bet this street that someone other than the last aggressor has put in.
+    /// Anything the aggressor bet above this is uncalled
+    pub matched_bet: u64,
+
+    /// Uncalled bet taken back out of the pot when the hand folded out,
+    /// returned to the last remaining player at settlement
+    pub uncalled_bet: u64,
+
+    /// Bitmap of seats whose raise option was closed by an all-in short of a
+    /// full raise: they had already acted on the last full bet, so they may
+    /// only call or fold the extra
+    pub raise_closed: u8,
+
+    /// Ante every seat posts on a bomb pot (0 = regular hand with blinds)
+    pub bomb_pot_ante: u64,
+
+    /// Bitmap of seats that dropped out of the hand, by folding or leaving
+    pub folded_players: u8,
+
+    /// Bitmap of seats that left the table mid-hand (fold_and_leave)
+    pub departed_players: u8,
+
+    /// Bets plus raises allowed each street (0 = uncapped; set on fixed-limit tables)
+    pub max_raises_per_street: u8,
+
+    /// Bets and full raises made so far this street
+    pub raises_this_street: u8,
+
+    /// Small blind seat, fixed by start_hand for every deal path to read
+    pub sb_seat: u8,
+
+    /// Big blind seat, fixed by start_hand for every deal path to read
+    pub bb_seat: u8,
+
+    /// Set once showdown has paid out the pot, so it can never pay twice
+    pub payouts_done: bool,
+
+    /// Bitmap of seats that have tabled their hole cards with reveal_cards
+    pub revealed_players: u8,
+
+    /// Seat that made the last bet or raise this street (NO_SEAT if none)
+    pub last_aggressor: u8,
+
+    /// Where the next ordered reveal starts looking, clockwise
+    /// (NO_SEAT until the first hand is shown)
+    pub next_to_reveal: u8,
+
+    /// Chips each seat has put in the pot this hand, by seat index. Side pots
+    /// are built from this, so they don't depend on which seat accounts a
+    /// caller passes (or on a seat that has since left and been closed).
+    /// Always adds up to `pot + uncalled_bet`
+    pub contributed: [u64; SEAT_BITMAP_SIZE],
 }
 
 impl HandState {
@@ -103,7 +191,37 @@ impl HandState {
         8 +  // last_action_time (i64)
         8 +  // hand_start_time (i64)
         1 +  // awaiting_community_reveal
-        1;   // bump
+        1 +  // bump
+        1 +  // community_card_count
+        8 +  // street_start_pot
+        32 + // hand_operator
+        1 +  // any_bet_made_this_street
+        8 +  // matched_bet
+        8 +  // uncalled_bet
+        1 +  // raise_closed
+        8 +  // bomb_pot_ante
+        1 +  // folded_players
+        1 +  // departed_players
+        1 +  // max_raises_per_street
+        1 +  // raises_this_street
+        1 +  // sb_seat
+        1 +  // bb_seat
+        1 +  // payouts_done
+        1 +  // revealed_players
+        1 +  // last_aggressor
+        1 +  // next_to_reveal
+        8 * SEAT_BITMAP_SIZE; // contributed
+
+    /// Check this is the hand the table is currently on, so a late transaction
+    /// can't land on an earlier hand that hasn't been closed yet
+    pub fn is_current(&self, table_hand_number: u64) -> bool {
+        self.hand_number == table_hand_number
+    }
+
+    /// Check if the key is this hand's operator
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        self.hand_operator == *key
+    }
 
     /// Check if player is still active in hand
     pub fn is_player_active(&self, seat_index: u8) -> bool {
@@ -114,6 +232,78 @@ impl HandState {
     pub fn fold_player(&mut self, seat_index: u8) {
         self.active_players &= !(1 << seat_index);
         self.active_count = self.active_count.saturating_sub(1);
+        self.folded_players |= 1 << seat_index;
+    }
+
+    /// Record a seat tabling its hole cards
+    pub fn mark_revealed(&mut self, seat_index: u8) {
+        self.revealed_players |= 1 << seat_index;
+    }
+
+    /// Seats still contesting the pot that have yet to reveal. Showdown is
+    /// ready to settle once this reaches zero
+    pub fn players_remaining_to_reveal(&self) -> u8 {
+        (self.active_players & !self.revealed_players).count_ones() as u8
+    }
+
+    /// Seat whose turn it is to show on an ordered-reveal table. The last
+    /// aggressor opens (or, with no bet on the final street, the first seat
+    /// left of the dealer), then the order goes clockwise, skipping seats that
+    /// have shown, mucked or folded. None once every active seat has shown
+    pub fn expected_revealer(&self, max_players: u8) -> Option<u8> {
+        let start = if self.next_to_reveal != NO_SEAT {
+            self.next_to_reveal
+        } else if self.last_aggressor != NO_SEAT && self.is_player_active(self.last_aggressor) {
+            self.last_aggressor
+        } else {
+            self.dealer_position + 1
+        };
+        self.active_seats_clockwise(start, max_players)
+            .find(|&seat| self.revealed_players & (1 << seat) == 0)
+    }
+
+    /// Check `seat_index` may show now on an ordered-reveal table: it is its
+    /// turn, or the seat whose turn it is has stalled past the action timeout
+    pub fn check_reveal_turn(&self, seat_index: u8, max_players: u8, now: i64) -> Result<()> {
+        match self.expected_revealer(max_players) {
+            Some(expected) if expected != seat_index => {
+                let elapsed = self.seconds_since_last_action(now);
+                require!(
+                    elapsed >= ACTION_TIMEOUT_SECONDS,
+                    HiddenHandError::RevealOutOfOrder
+                );
+                msg!(
+                    "Seat {} revealing out of turn - seat {} has not shown after {} seconds",
+                    seat_index,
+                    expected,
+                    elapsed
+                );
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a reveal on an ordered-reveal table, passing the turn to show
+    /// to the next seat clockwise
+    pub fn record_ordered_reveal(&mut self, seat_index: u8, max_players: u8) {
+        self.mark_revealed(seat_index);
+        self.next_to_reveal = (seat_index + 1) % max_players;
+    }
+
+    /// Record a seat leaving the table mid-hand, as opposed to just folding
+    pub fn mark_departed(&mut self, seat_index: u8) {
+        self.departed_players |= 1 << seat_index;
+    }
+
+    /// Hand emptied out before the flop by players leaving the table, with
+    /// nobody folding and staying. There was no contest, so it is cancelled
+    /// and bets are refunded rather than awarded
+    pub fn is_cancelled_by_departures(&self) -> bool {
+        self.community_revealed == 0
+            && self.active_count == 1
+            && self.departed_players != 0
+            && self.folded_players & !self.departed_players == 0
     }
 
     /// Check if player has acted this betting round
@@ -126,10 +316,202 @@ impl HandState {
         self.acted_this_round |= 1 << seat_index;
     }
 
-    /// Reset acted flags for new betting round
-    pub fn reset_betting_round(&mut self) {
+    /// Add a seat's chips to the pot, recording them against the seat. A pot
+    /// that would overflow means chips were counted twice somewhere, so fail
+    /// loudly rather than saturate
+    pub fn add_to_pot(&mut self, seat_index: u8, amount: u64) -> Result<()> {
+        let seat = seat_index as usize;
+        require!(seat < SEAT_BITMAP_SIZE, HiddenHandError::InvalidSeatIndex);
+        let pot = self
+            .pot
+            .checked_add(amount)
+            .ok_or(HiddenHandError::ArithmeticOverflow)?;
+        self.contributed[seat] = self.contributed[seat]
+            .checked_add(amount)
+            .ok_or(HiddenHandError::ArithmeticOverflow)?;
+        self.pot = pot;
+        Ok(())
+    }
+
+    /// Hand a seat back chips it put in the pot (an unmatched bet, or a
+    /// refund when the hand is cancelled). Returns the amount taken out
+    pub fn take_from_pot(&mut self, seat_index: u8, amount: u64) -> Result<u64> {
+        let seat = seat_index as usize;
+        require!(seat < SEAT_BITMAP_SIZE, HiddenHandError::InvalidSeatIndex);
+        let taken = amount.min(self.pot).min(self.contributed[seat]);
+        self.pot -= taken;
+        self.contributed[seat] -= taken;
+        Ok(taken)
+    }
+
+    /// Seats that have put chips in this hand, with how much each
+    pub fn contributions(&self) -> Vec<(u8, u64)> {
+        self.contributed
+            .iter()
+            .enumerate()
+            .filter(|(_, amount)| **amount > 0)
+            .map(|(seat, amount)| (seat as u8, *amount))
+            .collect()
+    }
+
+    /// Seconds since the last action at `now`. A last_action_time ahead of
+    /// the clock (skew between the rollup and base layer) counts as zero
+    pub fn seconds_since_last_action(&self, now: i64) -> i64 {
+        now.saturating_sub(self.last_action_time).max(0)
+    }
+
+    /// Whether the player on the clock has run out of time at `now`
+    /// A time bank pushes last_action_time forward, so it delays this too
+    pub fn action_timed_out(&self, now: i64) -> bool {
+        self.seconds_since_last_action(now) >= ACTION_TIMEOUT_SECONDS
+    }
+
+    /// Reset acted flags for new betting round. A new street opens at zero,
+    /// so the minimum bet goes back to the big blind rather than carrying
+    /// the last raise size from the street before
+    pub fn reset_betting_round(&mut self, big_blind: u64) {
         self.acted_this_round = 0;
         self.current_bet = 0;
+        self.min_raise = big_blind;
+        self.raises_this_street = 0;
+        self.street_start_pot = self.pot;
+        self.any_bet_made_this_street = false;
+        self.matched_bet = 0;
+        self.raise_closed = 0;
+        self.last_aggressor = NO_SEAT;
+    }
+
+    /// Whether this hand is a bomb pot (antes, no pre-flop betting)
+    pub fn is_bomb_pot(&self) -> bool {
+        self.bomb_pot_ante > 0
+    }
+
+    /// Forced bet a seat posts as it is dealt in: the ante on a bomb pot,
+    /// otherwise its blind (0 for seats outside the blinds)
+    pub fn forced_bet(&self, blind: u64) -> u64 {
+        if self.is_bomb_pot() {
+            self.bomb_pot_ante
+        } else {
+            blind
+        }
+    }
+
+    /// Forced bet a seat posts as it is dealt in, for the blind seats
+    /// start_hand recorded: its blind, the ante on a bomb pot, otherwise 0
+    pub fn forced_bet_for_seat(&self, seat_index: u8, small_blind: u64, big_blind: u64) -> u64 {
+        let blind = if seat_index == self.sb_seat {
+            small_blind
+        } else if seat_index == self.bb_seat {
+            big_blind
+        } else {
+            0
+        };
+        self.forced_bet(blind)
+    }
+
+    /// Open a dealt bomb pot. Everyone is in for the ante, so there is no
+    /// pre-flop betting and the hand waits straight away for the flop.
+    /// `all_in_seats` are the seats the ante put all-in
+    pub fn skip_preflop_betting(&mut self, all_in_seats: u8) {
+        self.phase = GamePhase::PreFlop;
+        self.all_in_players = all_in_seats & self.active_players;
+        self.current_bet = self.bomb_pot_ante;
+        self.matched_bet = self.bomb_pot_ante;
+        self.acted_this_round = 0;
+        self.any_bet_made_this_street = true;
+        self.awaiting_community_reveal = true;
+    }
+
+    /// Start the pre-flop raise count. The big blind is the opening bet, so it
+    /// counts against the cap like the first bet on a later street
+    pub fn count_blind_as_opening_bet(&mut self) {
+        self.raises_this_street = if self.is_bomb_pot() { 0 } else { 1 };
+    }
+
+    /// Check another bet or full raise is allowed this street under the
+    /// table's raise cap
+    pub fn check_raise_cap(&self) -> Result<()> {
+        require!(
+            self.max_raises_per_street == 0 || self.raises_this_street < self.max_raises_per_street,
+            HiddenHandError::RaiseCapReached
+        );
+        Ok(())
+    }
+
+    /// Check a bet or raise to `new_bet` is exactly one `bet_size` over the
+    /// current bet on a fixed-limit table. An all-in may come up short of
+    /// that, but never past it. No-limit tables (`None`) pass
+    pub fn check_fixed_limit(&self, bet_size: Option<u64>, new_bet: u64, all_in: bool) -> Result<()> {
+        let Some(bet_size) = bet_size else {
+            return Ok(());
+        };
+        let limit = self.current_bet.saturating_add(bet_size);
+        require!(
+            new_bet == limit || (all_in && new_bet < limit),
+            HiddenHandError::FixedLimitBetSize
+        );
+        Ok(())
+    }
+
+    /// Whether a seat may raise, or only call or fold, facing the current bet
+    pub fn can_raise(&self, seat_index: u8) -> bool {
+        self.raise_closed & (1 << seat_index) == 0
+    }
+
+    /// Raise the current bet to `new_bet` with a full raise, reopening the
+    /// betting for every seat still able to act
+    pub fn record_full_raise(&mut self, new_bet: u64) {
+        // The bet being raised over is now matched
+        self.record_matched(self.current_bet);
+        self.raises_this_street = self.raises_this_street.saturating_add(1);
+        self.min_raise = new_bet.saturating_sub(self.current_bet);
+        self.current_bet = new_bet;
+        self.acted_this_round = 0;
+        self.raise_closed = 0;
+    }
+
+    /// Raise the current bet to an all-in of `new_bet`. Only a full raise
+    /// (at least min_raise more) reopens the betting. A shorter all-in still
+    /// has to be answered, but seats that already acted may only call or
+    /// fold it, and min_raise is unchanged. Returns whether it was a full raise
+    pub fn record_all_in_raise(&mut self, new_bet: u64) -> bool {
+        let raise_amount = new_bet.saturating_sub(self.current_bet);
+        if raise_amount >= self.min_raise {
+            self.record_full_raise(new_bet);
+            return true;
+        }
+
+        self.record_matched(self.current_bet);
+        self.current_bet = new_bet;
+        self.raise_closed |= self.acted_this_round;
+        // Everyone still in has to answer the extra chips
+        self.acted_this_round = 0;
+        false
+    }
+
+    /// Record that a player has put `bet` in this street without raising
+    /// (a call, or the previous bet level when someone raises over it)
+    pub fn record_matched(&mut self, bet: u64) {
+        self.matched_bet = self.matched_bet.max(bet);
+    }
+
+    /// Take the uncalled part of the current bet back out of the pot.
+    /// Called when everyone else folds, on whatever street that happens.
+    /// Returns the amount removed from the pot
+    pub fn return_uncalled_bet(&mut self) -> u64 {
+        let uncalled = self
+            .current_bet
+            .saturating_sub(self.matched_bet)
+            .min(self.pot);
+        self.pot -= uncalled;
+        self.current_bet -= uncalled;
+        self.uncalled_bet = self.uncalled_bet.saturating_add(uncalled);
+        uncalled
+    }
+
+    /// Street closed with every remaining player checking (no chips went in)
+    pub fn checked_around(&self) -> bool {
+        self.is_betting_complete() && !self.any_bet_made_this_street
     }
 
     /// Mark player as all-in
@@ -155,6 +537,12 @@ impl HandState {
         can_bet.count_ones() >= 2
     }
 
+    /// Betting is closed and the board is being run out with at most one
+    /// player able to bet - the point where all-in hands can be tabled
+    pub fn is_all_in_runout(&self) -> bool {
+        self.awaiting_community_reveal && !self.can_anyone_bet()
+    }
+
     /// Check if betting round is complete
     pub fn is_betting_complete(&self) -> bool {
         // Players who can bet and haven't acted yet
@@ -165,20 +553,207 @@ impl HandState {
         need_to_act == 0
     }
 
+    /// Seats still in the hand, clockwise from `start` (inclusive)
+    pub fn active_seats_clockwise(&self, start: u8, max_players: u8) -> impl Iterator<Item = u8> + '_ {
+        seats_from(start, max_players).filter(move |&seat| self.is_player_active(seat))
+    }
+
     /// Find next active player after given seat
     pub fn next_active_player(&self, after_seat: u8, max_players: u8) -> Option<u8> {
-        let mut next = (after_seat + 1) % max_players;
-        for _ in 0..max_players {
-            if self.is_player_active(next) {
-                return Some(next);
-            }
-            next = (next + 1) % max_players;
+        self.active_seats_clockwise(after_seat + 1, max_players).next()
+    }
+
+    /// First seat to act on a post-flop street: the first active seat left of
+    /// the dealer that can still bet, falling back to any active seat
+    pub fn first_active_left_of_dealer(&self, max_players: u8) -> u8 {
+        let start = self.dealer_position + 1;
+        self.active_seats_clockwise(start, max_players)
+            .find(|&seat| !self.is_player_all_in(seat))
+            .or_else(|| self.active_seats_clockwise(start, max_players).next())
+            // Fallback (shouldn't happen with 2+ active players)
+            .unwrap_or(self.dealer_position)
+    }
+
+    /// Seats in the hand that a deal would skip (not among `seats_present`)
+    pub fn misdeal_seats(&self, seats_present: u8) -> u8 {
+        self.active_players & !seats_present
+    }
+
+    /// A deal that would skip a seat in the hand is a misdeal
+    pub fn is_misdeal(&self, seats_present: u8) -> bool {
+        self.misdeal_seats(seats_present) != 0
+    }
+
+    /// Whether a finished deal handed out the wrong cards: a seat outside the
+    /// hand was dealt, or the hole cards aren't HOLE_CARDS for each dealt seat
+    /// (a seat account passed twice is dealt twice)
+    pub fn is_wrong_deal(&self, dealt_seats: u8, hole_cards_dealt: usize) -> bool {
+        dealt_seats & !self.active_players != 0
+            || hole_cards_dealt != HOLE_CARDS * dealt_seats.count_ones() as usize
+    }
+
+    /// End the hand after a misdeal. Blinds already posted must be taken back
+    /// out of the pot for the seats first (`PlayerSeat::refund_misdeal`)
+    pub fn abort_misdeal(&mut self) {
+        self.phase = GamePhase::Settled;
+        self.pot = 0;
+        self.contributed = [0; SEAT_BITMAP_SIZE];
+        self.active_count = 0;
+        self.acted_this_round = 0;
+        self.all_in_players = 0;
+        self.awaiting_community_reveal = false;
+    }
+
+    /// Whether the hand is over and paid out, so its accounts can be closed.
+    /// A fold-out is Settled before showdown pays the pot, so the table's
+    /// current hand also needs the table back in Waiting
+    pub fn is_closable(&self, table_hand_number: u64, table_status: TableStatus) -> bool {
+        self.phase == GamePhase::Settled
+            && (self.hand_number < table_hand_number
+                || matches!(table_status, TableStatus::Waiting | TableStatus::Paused))
+    }
+
+    /// Hand that never reached a flop and has at most one player left in it
+    /// (everyone else folded or left), so nothing in the pot is contested
+    pub fn is_abandoned(&self) -> bool {
+        self.community_revealed == 0 && self.active_count <= 1
+    }
+
+    /// Close an abandoned hand (see is_abandoned) and return everything still
+    /// owed from it - the pot, including the remaining player's blind, plus
+    /// any uncalled bet set aside at fold-out
+    pub fn settle_abandoned(&mut self) -> u64 {
+        let refund = self.pot.saturating_add(self.uncalled_bet);
+        self.pot = 0;
+        self.uncalled_bet = 0;
+        self.contributed = [0; SEAT_BITMAP_SIZE];
+        self.active_players = 0;
+        self.active_count = 0;
+        self.awaiting_community_reveal = false;
+        self.phase = GamePhase::Settled;
+        refund
+    }
+
+    /// Find next player who needs to act (not folded, not all-in, hasn't acted this round)
+    ///
+    /// Blinds are posted without marking the blind seats as acted, so pre-flop the
+    /// big blind keeps a live option: when everyone just calls, action still comes
+    /// back to the BB to check or raise before the round closes.
+    pub fn next_to_act(&self, after_seat: u8, max_players: u8) -> Option<u8> {
+        self.active_seats_clockwise(after_seat + 1, max_players)
+            .find(|&seat| !self.is_player_all_in(seat) && !self.has_player_acted(seat))
+    }
+
+    /// Chips a seat with `seat_current_bet` in this street must add to call
+    pub fn amount_to_call(&self, seat_current_bet: u64) -> u64 {
+        self.current_bet.saturating_sub(seat_current_bet)
+    }
+
+    /// Whether a seat with `seat_current_bet` in this street can check
+    pub fn can_check(&self, seat_current_bet: u64) -> bool {
+        self.amount_to_call(seat_current_bet) == 0
+    }
+
+    /// Minimum total bet a raise must reach (`current_bet + min_raise`)
+    /// Capped at the seat's stack - a short stack's only raise is all-in
+    pub fn min_raise_to(&self, seat_current_bet: u64, seat_chips: u64) -> u64 {
+        let min_total = self.current_bet.saturating_add(self.min_raise);
+        let max_total = seat_current_bet.saturating_add(seat_chips);
+        min_total.min(max_total)
+    }
+
+    /// Maximum chips a player can put in with a pot-limit raise:
+    /// call first, then raise the size of the pot after the call
+    /// (`street_start_pot + 2 * to_call + already_in_this_street`)
+    pub fn max_pot_limit_raise(&self, to_call: u64) -> u64 {
+        let already_in_this_street = self.pot.saturating_sub(self.street_start_pot);
+        self.street_start_pot
+            .saturating_add(to_call.saturating_mul(2))
+            .saturating_add(already_in_this_street)
+    }
+
+    /// Check if any community cards are still hidden
+    pub fn has_community_to_reveal(&self) -> bool {
+        self.community_revealed < self.community_card_count
+    }
+
+    /// Community cards to reveal once the current betting round closes,
+    /// as (start index, count). An all-in runout reveals the rest of the board.
+    /// Returns None when nothing is left to reveal for this street.
+    pub fn next_community_reveal(&self, all_in_runout: bool) -> Option<(usize, usize)> {
+        let street_target: u8 = match self.phase {
+            GamePhase::PreFlop => 3,
+            GamePhase::Flop => 4,
+            GamePhase::Turn => 5,
+            _ => return None,
+        };
+
+        let target = if all_in_runout {
+            self.community_card_count
+        } else {
+            street_target.min(self.community_card_count)
+        };
+
+        if target <= self.community_revealed {
+            return None;
         }
-        None
+
+        Some((
+            self.community_revealed as usize,
+            (target - self.community_revealed) as usize,
+        ))
     }
 
-    /// Advance to next phase
-    pub fn advance_phase(&mut self) {
+    /// Store revealed community cards starting at `start_idx`
+    /// Single write path for both the plaintext (timeout) and attested (reveal_community) reveals.
+    /// Writes past the 5-card board (or a short stored vec) are rejected instead of panicking,
+    /// and so are retries: the revealed count must grow and the target slots must still be empty
+    pub fn reveal_community_cards(&mut self, start_idx: usize, cards: &[u8]) -> Result<()> {
+        let end = start_idx
+            .checked_add(cards.len())
+            .ok_or(HiddenHandError::InvalidCardIndex)?;
+        require!(
+            end <= self.community_cards.len().min(COMMUNITY_CARD_COUNT),
+            HiddenHandError::InvalidCardIndex
+        );
+        require!(
+            end > self.community_revealed as usize
+                && self.community_cards[start_idx..end].iter().all(|&card| card == CARD_SENTINEL),
+            HiddenHandError::CommunityAlreadyRevealed
+        );
+
+        self.community_cards[start_idx..end].copy_from_slice(cards);
+        self.community_revealed = self.community_revealed.max(end as u8);
+        Ok(())
+    }
+
+    /// Move the hand on once reveal_community has stored a street's cards.
+    /// An all-in runout has just revealed the rest of the board, from whatever
+    /// street betting closed on, so it goes straight to showdown. Otherwise the
+    /// next betting street opens with `first_to_act`
+    pub fn finish_community_reveal(&mut self, all_in_runout: bool, first_to_act: u8, big_blind: u64) {
+        if all_in_runout {
+            self.phase = GamePhase::Showdown;
+        } else if matches!(self.phase, GamePhase::PreFlop | GamePhase::Flop | GamePhase::Turn) {
+            self.advance_phase(big_blind);
+            self.action_on = first_to_act;
+        }
+        self.awaiting_community_reveal = false;
+    }
+
+    /// Display name of the street the hand is on
+    pub fn current_street_name(&self) -> &'static str {
+        self.phase.as_str()
+    }
+
+    /// Community cards revealed so far, in board order
+    pub fn revealed_community_cards(&self) -> Vec<u8> {
+        let revealed = (self.community_revealed as usize).min(self.community_cards.len());
+        self.community_cards[..revealed].to_vec()
+    }
+
+    /// Advance to next phase, opening a fresh betting round
+    pub fn advance_phase(&mut self, big_blind: u64) {
         self.phase = match self.phase {
             GamePhase::Dealing => GamePhase::PreFlop,
             GamePhase::PreFlop => GamePhase::Flop,
@@ -188,6 +763,1027 @@ impl HandState {
             GamePhase::Showdown => GamePhase::Settled,
             GamePhase::Settled => GamePhase::Settled,
         };
-        self.reset_betting_round();
+        self.reset_betting_round(big_blind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DECK_SIZE;
+    use crate::state::{PlayerSeat, PlayerStatus};
+    use crate::test_fixtures::{deck_state, hand_state, player_seat, table_state};
+
+    /// Test each phase maps to its display name
+    #[test]
+    fn test_game_phase_as_str() {
+        let names = [
+            (GamePhase::Dealing, "dealing"),
+            (GamePhase::PreFlop, "preflop"),
+            (GamePhase::Flop, "flop"),
+            (GamePhase::Turn, "turn"),
+            (GamePhase::River, "river"),
+            (GamePhase::Showdown, "showdown"),
+            (GamePhase::Settled, "settled"),
+        ];
+        for (phase, name) in names {
+            assert_eq!(phase.as_str(), name);
+            assert_eq!(hand_state(phase).current_street_name(), name);
+        }
+        assert_eq!(GamePhase::Showdown as u8, 5, "final_phase encoding");
+        assert_eq!(GamePhase::Settled as u8, 6);
+    }
+
+    /// Test community reveal schedule for a full board
+    #[test]
+    fn test_community_reveal_schedule() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        assert!(hand.has_community_to_reveal());
+        assert_eq!(hand.next_community_reveal(false), Some((0, 3)));
+        assert_eq!(hand.next_community_reveal(true), Some((0, 5)));
+
+        hand.phase = GamePhase::Flop;
+        hand.community_revealed = 3;
+        assert_eq!(hand.next_community_reveal(false), Some((3, 1)));
+        assert_eq!(hand.next_community_reveal(true), Some((3, 2)));
+
+        hand.phase = GamePhase::Turn;
+        hand.community_revealed = 4;
+        assert_eq!(hand.next_community_reveal(false), Some((4, 1)));
+
+        hand.phase = GamePhase::River;
+        hand.community_revealed = 5;
+        assert!(!hand.has_community_to_reveal());
+        assert_eq!(hand.next_community_reveal(false), None);
+    }
+
+    /// Test a board revealed partly by the timeout path and partly by
+    /// reveal_community reads back as one consistent board at showdown
+    #[test]
+    fn test_mixed_reveal_paths_share_board() {
+        let deck = deck_state(&[12, 25, 38, 51, 0]);
+        let mut hand = hand_state(GamePhase::PreFlop);
+
+        // Flop revealed inline by timeout_player from the plaintext deck
+        let (start, count) = hand.next_community_reveal(false).unwrap();
+        let flop = deck.plaintext_cards(start, count).unwrap();
+        hand.reveal_community_cards(start, &flop).unwrap();
+        hand.advance_phase(20);
+        assert_eq!(hand.phase, GamePhase::Flop);
+        assert_eq!(hand.community_revealed, 3);
+
+        // Turn + river revealed by reveal_community on an all-in runout
+        let (start, count) = hand.next_community_reveal(true).unwrap();
+        assert_eq!((start, count), (3, 2));
+        hand.reveal_community_cards(start, &[51, 0]).unwrap();
+
+        assert_eq!(hand.community_revealed, 5);
+        assert_eq!(hand.revealed_community_cards(), vec![12, 25, 38, 51, 0]);
+        assert!(!hand.has_community_to_reveal());
+    }
+
+    /// Test the call/check boundary for a seat's bet this street
+    #[test]
+    fn test_amount_to_call_and_can_check() {
+        let mut hand = hand_state(GamePhase::Flop);
+
+        // Nothing bet: everyone can check
+        assert_eq!(hand.amount_to_call(0), 0);
+        assert!(hand.can_check(0));
+
+        // Facing a bet of 100
+        hand.current_bet = 100;
+        assert_eq!(hand.amount_to_call(0), 100);
+        assert!(!hand.can_check(0));
+        assert_eq!(hand.amount_to_call(99), 1);
+        assert!(!hand.can_check(99), "One chip short still has to call");
+
+        // Matched exactly: the bettor (or a caller) can check
+        assert_eq!(hand.amount_to_call(100), 0);
+        assert!(hand.can_check(100));
+
+        // A seat ahead of the current bet never owes chips
+        assert_eq!(hand.amount_to_call(150), 0);
+        assert!(hand.can_check(150));
+    }
+
+    /// Test fixed-limit tables cap bets plus raises each street
+    #[test]
+    fn test_fixed_limit_raise_cap() {
+        use crate::state::BettingMode;
+
+        let mut table = table_state();
+        table.max_raises_per_street = 4;
+        assert_eq!(table.raise_cap(), 0, "No-limit is never capped");
+        table.betting_mode = BettingMode::FixedLimit;
+        assert_eq!(table.raise_cap(), 4);
+
+        // Flop: a bet and three raises reach the cap
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.max_raises_per_street = table.raise_cap();
+        hand.reset_betting_round(20);
+        for bet in [20, 40, 60, 80] {
+            assert!(hand.check_raise_cap().is_ok());
+            hand.record_full_raise(bet);
+        }
+        assert_eq!(hand.raises_this_street, 4);
+
+        // The 5th aggressive action is blocked
+        assert_eq!(
+            hand.check_raise_cap().unwrap_err(),
+            HiddenHandError::RaiseCapReached.into()
+        );
+
+        // A short all-in raise doesn't count toward the cap
+        hand.min_raise = 20;
+        assert!(!hand.record_all_in_raise(90));
+        assert_eq!(hand.raises_this_street, 4);
+
+        // The next street starts uncapped again
+        hand.advance_phase(20);
+        assert_eq!(hand.raises_this_street, 0);
+        assert!(hand.check_raise_cap().is_ok());
+
+        // Pre-flop the big blind is the opening bet: three raises reach the cap
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.max_raises_per_street = table.raise_cap();
+        hand.current_bet = 20;
+        hand.min_raise = 20;
+        hand.count_blind_as_opening_bet();
+        for bet in [40, 60, 80] {
+            assert!(hand.check_raise_cap().is_ok());
+            hand.record_full_raise(bet);
+        }
+        assert_eq!(
+            hand.check_raise_cap().unwrap_err(),
+            HiddenHandError::RaiseCapReached.into()
+        );
+
+        // A bomb pot has no blind, and no pre-flop betting to cap
+        hand.bomb_pot_ante = 50;
+        hand.count_blind_as_opening_bet();
+        assert_eq!(hand.raises_this_street, 0);
+    }
+
+    /// Test fixed-limit tables size bets and raises by street
+    #[test]
+    fn test_fixed_limit_bet_sizes() {
+        use crate::state::BettingMode;
+
+        let mut table = table_state();
+        table.big_blind = 20;
+        assert_eq!(table.fixed_bet_size(GamePhase::Flop), None);
+        table.betting_mode = BettingMode::FixedLimit;
+        assert_eq!(table.fixed_bet_size(GamePhase::PreFlop), Some(table.big_blind));
+        assert_eq!(table.fixed_bet_size(GamePhase::Flop), Some(table.big_blind));
+        assert_eq!(table.fixed_bet_size(GamePhase::Turn), Some(2 * table.big_blind));
+        assert_eq!(table.fixed_bet_size(GamePhase::River), Some(2 * table.big_blind));
+
+        // Turn with a big bet of 40 already in: the only raise is to 80
+        let mut hand = hand_state(GamePhase::Turn);
+        hand.current_bet = 40;
+        let size = table.fixed_bet_size(hand.phase);
+        assert!(hand.check_fixed_limit(size, 80, false).is_ok());
+        for new_bet in [60, 100] {
+            assert_eq!(
+                hand.check_fixed_limit(size, new_bet, false).unwrap_err(),
+                HiddenHandError::FixedLimitBetSize.into()
+            );
+        }
+
+        // All-in may fall short of a full raise but not go past it
+        assert!(hand.check_fixed_limit(size, 60, true).is_ok());
+        assert_eq!(
+            hand.check_fixed_limit(size, 100, true).unwrap_err(),
+            HiddenHandError::FixedLimitBetSize.into()
+        );
+
+        // No-limit bets any size
+        assert!(hand.check_fixed_limit(None, 1_000, false).is_ok());
+    }
+
+    /// Test a big pre-flop raise size doesn't carry over to the flop
+    #[test]
+    fn test_min_raise_resets_each_street() {
+        let big_blind = 20;
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.current_bet = big_blind;
+        hand.min_raise = big_blind;
+
+        // Raise to 500 over the big blind: the next raise must be 480 more
+        hand.record_full_raise(500);
+        assert_eq!(hand.min_raise, 480);
+        assert_eq!(hand.min_raise_to(0, 10_000), 980);
+
+        // Flop: betting reopens at zero and the minimum opening bet is one big blind
+        hand.advance_phase(big_blind);
+        assert_eq!(hand.phase, GamePhase::Flop);
+        assert_eq!(hand.min_raise, big_blind);
+        assert_eq!(hand.min_raise_to(0, 10_000), big_blind);
+
+        // Same when the street opens through reveal_community
+        hand.record_full_raise(300);
+        hand.finish_community_reveal(false, 0, big_blind);
+        assert_eq!(hand.phase, GamePhase::Turn);
+        assert_eq!(hand.min_raise_to(0, 10_000), big_blind);
+    }
+
+    /// Test minimum raise totals across betting states
+    #[test]
+    fn test_min_raise_to() {
+        let big_blind = 20;
+        let mut hand = hand_state(GamePhase::PreFlop);
+
+        // Preflop limp: facing the big blind, raise to at least 2 BB
+        hand.current_bet = big_blind;
+        hand.min_raise = big_blind;
+        assert_eq!(hand.min_raise_to(big_blind, 1_000), 40);
+
+        // Postflop open: no bet yet, minimum bet is one BB
+        hand.phase = GamePhase::Flop;
+        hand.reset_betting_round(big_blind);
+        assert_eq!(hand.min_raise_to(0, 1_000), 20);
+
+        // After a raise to 60 (raise size 40): re-raise to at least 100
+        hand.current_bet = 60;
+        hand.min_raise = 40;
+        assert_eq!(hand.min_raise_to(0, 1_000), 100);
+
+        // Short stack can only raise all-in
+        assert_eq!(hand.min_raise_to(0, 70), 70);
+    }
+
+    /// Test a short all-in over the bet doesn't reopen the betting
+    #[test]
+    fn test_short_all_in_does_not_reopen_action() {
+        // Three-handed flop: seat 0 bets 100, seat 1 calls
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.min_raise = 20;
+        hand.record_full_raise(100);
+        hand.mark_acted(0);
+        hand.record_matched(100);
+        hand.mark_acted(1);
+        assert_eq!(hand.min_raise, 100);
+
+        // Seat 2 shoves for 150: 50 more, short of a full raise to 200
+        assert!(!hand.record_all_in_raise(150));
+        hand.mark_all_in(2);
+        hand.mark_acted(2);
+        assert_eq!(hand.current_bet, 150);
+        assert_eq!(hand.min_raise, 100, "A short all-in doesn't set the raise size");
+
+        // Seats 0 and 1 still owe the extra 50, but may only call or fold it
+        assert_eq!(hand.next_to_act(2, 3), Some(0));
+        assert!(!hand.can_raise(0));
+        assert!(!hand.can_raise(1));
+        assert!(!hand.is_betting_complete());
+
+        // Once both call, the round closes without anyone getting to re-raise
+        hand.mark_acted(0);
+        hand.mark_acted(1);
+        assert!(hand.is_betting_complete());
+
+        // A full raise all-in reopens the betting for everyone
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.record_full_raise(100);
+        hand.mark_acted(0);
+        hand.mark_acted(1);
+        assert!(hand.record_all_in_raise(200));
+        assert_eq!((hand.current_bet, hand.min_raise), (200, 100));
+        assert!(hand.can_raise(0) && hand.can_raise(1));
+
+        // A new street clears the restriction
+        hand.raise_closed = 0b11;
+        hand.reset_betting_round(20);
+        assert!(hand.can_raise(0));
+    }
+
+    /// Test the standard pot-limit raise formula
+    #[test]
+    fn test_max_pot_limit_raise() {
+        // Preflop with blinds 10/20: UTG faces 20 in a 30 pot
+        // Call 20 -> pot 50, raise 50 -> puts in 70 (raise to 70)
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.pot = 30;
+        assert_eq!(hand.max_pot_limit_raise(20), 70);
+
+        // Flop opens with 100 in the pot: street starts fresh
+        hand.pot = 100;
+        hand.reset_betting_round(20);
+        assert_eq!(hand.street_start_pot, 100);
+        assert_eq!(hand.max_pot_limit_raise(0), 100);
+
+        // First player bets 50, next player faces 50:
+        // call 50 -> pot 200, raise 200 -> puts in 250
+        hand.pot = 150;
+        assert_eq!(hand.max_pot_limit_raise(50), 250);
+    }
+
+    /// Test rotating-operator config and operator checks
+    #[test]
+    fn test_hand_operator() {
+        use crate::state::TableConfig;
+
+        let config = TableConfig { rotating_operator: true, ..Default::default() };
+        assert!(config.rotating_operator);
+        assert!(!TableConfig::default().rotating_operator);
+
+        let dealer = Pubkey::new_unique();
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.hand_operator = dealer;
+
+        assert!(hand.is_operator(&dealer));
+        assert!(!hand.is_operator(&Pubkey::new_unique()));
+    }
+
+    /// Test a short stack calling a bigger bet goes all-in and closes the round
+    #[test]
+    fn test_short_stack_call_closes_round() {
+        // Seat 0 bet 200, seat 1 has only 50 behind
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.current_bet = 200;
+        hand.pot = 200;
+        hand.mark_acted(0);
+
+        let mut seat = player_seat(1, 50);
+        let to_call = hand.current_bet - seat.current_bet;
+        let actual_bet = seat.place_bet(to_call).unwrap();
+        hand.pot += actual_bet;
+        assert_eq!(actual_bet, 50);
+        assert!(actual_bet < to_call);
+
+        hand.mark_all_in(seat.seat_index);
+        hand.mark_acted(seat.seat_index);
+
+        assert_eq!(seat.status, PlayerStatus::AllIn);
+        assert_eq!(seat.current_bet, 50); // Still below the 200 bet
+        assert_eq!(hand.pot, 250);
+        // The all-in seat isn't waited on to match the full bet
+        assert!(hand.is_betting_complete());
+        assert!(!hand.can_anyone_bet());
+    }
+
+    /// Test community card writes stay within the 5-card board
+    #[test]
+    fn test_community_cards_bounds() {
+        // All-in runout from preflop reveals the whole board at once
+        let mut hand = hand_state(GamePhase::PreFlop);
+        let (start, count) = hand.next_community_reveal(true).unwrap();
+        assert_eq!((start, count), (0, 5));
+        hand.reveal_community_cards(start, &[10, 20, 30, 40, 50]).unwrap();
+        assert_eq!(hand.community_cards, vec![10, 20, 30, 40, 50]);
+        assert_eq!(hand.community_revealed, 5);
+        assert_eq!(hand.revealed_community_cards().len(), 5);
+        assert!(!hand.has_community_to_reveal());
+
+        // Anything past the fifth slot is rejected and leaves the board untouched
+        assert!(hand.reveal_community_cards(4, &[1, 2]).is_err());
+        assert!(hand.reveal_community_cards(5, &[1]).is_err());
+        assert!(hand.reveal_community_cards(usize::MAX, &[1]).is_err());
+        assert_eq!(hand.community_cards, vec![10, 20, 30, 40, 50]);
+        assert_eq!(hand.community_revealed, 5);
+
+        // A stored vec shorter than the board bounds writes too
+        let mut short = hand_state(GamePhase::PreFlop);
+        short.community_cards = vec![CARD_SENTINEL; 3];
+        assert!(short.reveal_community_cards(0, &[1, 2, 3, 4]).is_err());
+        short.community_revealed = 5;
+        assert_eq!(short.revealed_community_cards().len(), 3);
+    }
+
+    /// Test a repeated community reveal is rejected instead of re-writing the board
+    #[test]
+    fn test_community_reveal_never_regresses() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.reveal_community_cards(0, &[1, 2, 3]).unwrap();
+        assert_eq!(hand.community_revealed, 3);
+
+        // Same flop again (client retry) - rejected, board untouched
+        assert!(hand.reveal_community_cards(0, &[4, 5, 6]).is_err());
+        assert_eq!(hand.revealed_community_cards(), vec![1, 2, 3]);
+        assert_eq!(hand.community_revealed, 3);
+
+        // Overlapping the flop is rejected too
+        assert!(hand.reveal_community_cards(2, &[7, 8]).is_err());
+
+        // The turn still goes in
+        hand.reveal_community_cards(3, &[9]).unwrap();
+        assert_eq!(hand.community_revealed, 4);
+    }
+
+    /// Test detecting a street that was checked through
+    #[test]
+    fn test_checked_around() {
+        // Both players check the flop
+        let mut hand = hand_state(GamePhase::Flop);
+        assert!(!hand.checked_around()); // Round not finished yet
+        hand.mark_acted(0);
+        hand.mark_acted(1);
+        assert!(hand.checked_around());
+
+        // Turn: bet and call
+        hand.reset_betting_round(20);
+        hand.mark_acted(0);
+        hand.any_bet_made_this_street = true;
+        hand.mark_acted(1);
+        assert!(hand.is_betting_complete());
+        assert!(!hand.checked_around());
+
+        // River starts fresh
+        hand.reset_betting_round(20);
+        assert!(!hand.any_bet_made_this_street);
+    }
+
+    /// Test the big blind keeps the option when everyone limps pre-flop
+    #[test]
+    fn test_big_blind_option() {
+        // 3-handed: dealer seat 0, SB seat 1, BB seat 2 - blinds posted, nobody acted
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.current_bet = 20;
+        hand.min_raise = 20;
+
+        // UTG (dealer) limps, SB completes
+        hand.mark_acted(0);
+        assert_eq!(hand.next_to_act(0, 6), Some(1));
+        hand.mark_acted(1);
+
+        // Round isn't over - the BB gets the action
+        assert!(!hand.is_betting_complete());
+        assert_eq!(hand.next_to_act(1, 6), Some(2));
+
+        // BB checks their option and the round closes
+        hand.mark_acted(2);
+        assert_eq!(hand.next_to_act(2, 6), None);
+        assert!(hand.is_betting_complete());
+
+        // If the BB raises instead, the limpers must act again
+        hand.acted_this_round = 0;
+        hand.mark_acted(2);
+        assert_eq!(hand.next_to_act(2, 6), Some(0));
+
+        // Heads-up: the dealer is the SB and acts first - completing doesn't close the round
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.current_bet = 20;
+        hand.mark_acted(0);
+        assert!(!hand.is_betting_complete());
+        assert_eq!(hand.next_to_act(0, 2), Some(1));
+        hand.mark_acted(1);
+        assert!(hand.is_betting_complete());
+    }
+
+    /// Test a bomb pot collects the ante from every seat and goes straight to the flop
+    #[test]
+    fn test_bomb_pot_skips_preflop() {
+        let ante = 50;
+        let mut hand = hand_state(GamePhase::Dealing);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.current_bet = 0;
+        hand.bomb_pot_ante = ante;
+        assert!(hand.is_bomb_pot());
+
+        // Seat 2 is short: the ante puts it all-in for 30
+        let mut seats = [player_seat(0, 1_000), player_seat(1, 1_000), player_seat(2, 30)];
+        let blinds = [1, 2, 0];
+        let mut all_in_seats = 0u8;
+        for (seat, blind) in seats.iter_mut().zip(blinds) {
+            seat.clear_for_new_hand();
+            let posted = seat.place_bet(hand.forced_bet(blind)).unwrap();
+            hand.add_to_pot(seat.seat_index, posted).unwrap();
+            if seat.chips == 0 {
+                all_in_seats |= 1 << seat.seat_index;
+            }
+        }
+        hand.skip_preflop_betting(all_in_seats);
+
+        // Full ante pot, nothing to act on pre-flop
+        assert_eq!(hand.pot, 50 + 50 + 30);
+        assert_eq!(hand.phase, GamePhase::PreFlop);
+        assert!(hand.awaiting_community_reveal, "Flop is next, not pre-flop action");
+        assert_eq!(hand.current_bet, ante);
+        assert!(hand.is_player_all_in(2));
+        assert!(hand.can_anyone_bet(), "Two covered seats still bet on the flop");
+        assert!(!hand.is_all_in_runout());
+
+        // Everyone all-in on the ante runs the board out
+        hand.all_in_players = 0b110;
+        assert!(hand.is_all_in_runout());
+
+        // Regular hands post their blinds
+        let regular = hand_state(GamePhase::Dealing);
+        assert!(!regular.is_bomb_pot());
+        assert_eq!(regular.forced_bet(2), 2);
+        assert_eq!(regular.forced_bet(0), 0);
+    }
+
+    /// Test side pots come from the hand's record, not the seats passed to showdown
+    #[test]
+    fn test_side_pots_keep_departed_seat_chips() {
+        use crate::state::build_side_pots;
+
+        // A is all-in for 50, B and C put in 200 each, then C folds and
+        // leaves on the river - its seat account is closed
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.add_to_pot(0, 50).unwrap();
+        hand.add_to_pot(1, 200).unwrap();
+        hand.add_to_pot(2, 200).unwrap();
+        hand.active_players = 0b011;
+        hand.active_count = 2;
+        assert_eq!(hand.pot, 450);
+
+        let contributions: Vec<(u8, u64, bool)> = hand
+            .contributions()
+            .into_iter()
+            .map(|(seat, amount)| (seat, amount, hand.is_player_active(seat)))
+            .collect();
+        let pots = build_side_pots(&contributions);
+        assert_eq!(pots.len(), 2);
+        assert_eq!(pots[0].amount, 150);
+        assert_eq!(pots[0].eligible, vec![0, 1]);
+        assert_eq!(pots[1].amount, 300);
+        assert_eq!(pots[1].eligible, vec![1]);
+        assert_eq!(pots.iter().map(|pot| pot.amount).sum::<u64>(), hand.pot);
+
+        // Chips handed back come off both the pot and the seat's record
+        assert_eq!(hand.take_from_pot(1, 20).unwrap(), 20);
+        assert_eq!(hand.pot, 430);
+        assert_eq!(hand.contributed[1], 180);
+        assert_eq!(hand.take_from_pot(0, 80).unwrap(), 50, "Never more than the seat put in");
+        let err = hand.take_from_pot(SEAT_BITMAP_SIZE as u8, 1).unwrap_err();
+        assert_eq!(err, HiddenHandError::InvalidSeatIndex.into());
+    }
+
+    /// Test a hand only counts as cancelled when players left rather than folded
+    #[test]
+    fn test_hand_cancelled_by_departures() {
+        // Three players pre-flop; seats 0 and 1 both leave the table
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.mark_departed(0);
+        hand.fold_player(0);
+        assert!(!hand.is_cancelled_by_departures(), "Two players can still play");
+        hand.mark_departed(1);
+        hand.fold_player(1);
+        assert!(hand.is_cancelled_by_departures());
+
+        // A fold that stays at the table makes it a normal fold-out
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.active_players = 0b111;
+        hand.active_count = 3;
+        hand.fold_player(0);
+        hand.mark_departed(1);
+        hand.fold_player(1);
+        assert!(!hand.is_cancelled_by_departures());
+
+        // Once the flop is out the pot was contested
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.community_revealed = 3;
+        hand.mark_departed(0);
+        hand.fold_player(0);
+        assert!(!hand.is_cancelled_by_departures());
+    }
+
+    /// Test an uncalled bet comes back out of the pot when everyone folds to it
+    #[test]
+    fn test_uncalled_bet_returned_on_fold_out() {
+        // Turn: 100 in the pot from earlier streets, seat 0 bets 40 and seat 1 folds
+        let mut hand = hand_state(GamePhase::Turn);
+        hand.pot = 100;
+        hand.reset_betting_round(20);
+        hand.current_bet = 40;
+        hand.pot += 40;
+
+        assert_eq!(hand.return_uncalled_bet(), 40);
+        assert_eq!(hand.pot, 100);
+        assert_eq!(hand.uncalled_bet, 40);
+
+        // Bet 40, called, then raised to 120 and folded to: only the raise is uncalled
+        let mut hand = hand_state(GamePhase::Turn);
+        hand.pot = 100;
+        hand.reset_betting_round(20);
+        hand.current_bet = 40;
+        hand.record_matched(40);
+        hand.current_bet = 120;
+        hand.pot += 40 + 40 + 120;
+
+        assert_eq!(hand.return_uncalled_bet(), 80);
+        assert_eq!(hand.pot, 220);
+
+        // Short all-in call for 25 of a 40 bet leaves 15 uncalled
+        let mut hand = hand_state(GamePhase::Turn);
+        hand.reset_betting_round(20);
+        hand.current_bet = 40;
+        hand.record_matched(25);
+        hand.pot = 65;
+        assert_eq!(hand.return_uncalled_bet(), 15);
+
+        // New street forgets last street's matched bet
+        hand.reset_betting_round(20);
+        assert_eq!(hand.matched_bet, 0);
+        assert_eq!(hand.return_uncalled_bet(), 0);
+    }
+
+    /// Test chips are conserved across a full multi-hand session.
+    /// A fixed-seed generator drives the shuffles and actions so every run plays
+    /// the same hands; after every action, stacks + pot + uncalled bet must equal
+    /// the chips the table started with
+    #[test]
+    fn test_chip_conservation_across_session() {
+        use crate::state::{find_winners, split_pot};
+
+        const SEATS: u8 = 4;
+        const BUY_IN: u64 = 1_000;
+        const SMALL_BLIND: u64 = 10;
+        const BIG_BLIND: u64 = 20;
+        const TOTAL: u64 = BUY_IN * SEATS as u64;
+
+        // 64-bit LCG with a fixed seed
+        let mut rng: u64 = 0x4869_6464_656e_4861;
+        let mut next = move |bound: u64| {
+            rng = rng
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (rng >> 33) % bound
+        };
+
+        let mut seats: Vec<PlayerSeat> =
+            (0..SEATS).map(|i| player_seat(i, BUY_IN)).collect();
+        let conserved = |seats: &[PlayerSeat], hand: &HandState| {
+            seats.iter().map(|s| s.chips).sum::<u64>() + hand.pot + hand.uncalled_bet
+        };
+
+        let mut hands_played = 0;
+        for hand_number in 0..40u64 {
+            let live: Vec<u8> = seats.iter().filter(|s| s.chips > 0).map(|s| s.seat_index).collect();
+            if live.len() < 2 {
+                break;
+            }
+
+            // start_hand: reset seats and post blinds
+            let mut hand = hand_state(GamePhase::PreFlop);
+            hand.hand_number = hand_number;
+            hand.active_players = live.iter().fold(0, |bits, &i| bits | (1 << i));
+            hand.active_count = live.len() as u8;
+            for seat in seats.iter_mut() {
+                if seat.chips > 0 {
+                    seat.clear_for_new_hand();
+                    seat.status = PlayerStatus::Playing;
+                } else {
+                    seat.status = PlayerStatus::Sitting;
+                }
+            }
+
+            let dealer = (hand_number as usize) % live.len();
+            let sb = live[(dealer + 1) % live.len()];
+            let bb = live[(dealer + 2) % live.len()];
+            hand.pot += seats[sb as usize].place_bet(SMALL_BLIND).unwrap();
+            hand.pot += seats[bb as usize].place_bet(BIG_BLIND).unwrap();
+            let (sb_bet, bb_bet) = (seats[sb as usize].current_bet, seats[bb as usize].current_bet);
+            hand.current_bet = sb_bet.max(bb_bet);
+            hand.matched_bet = sb_bet.min(bb_bet);
+            hand.min_raise = BIG_BLIND;
+            for &i in &[sb, bb] {
+                if seats[i as usize].status == PlayerStatus::AllIn {
+                    hand.mark_all_in(i);
+                }
+            }
+            assert_eq!(conserved(&seats, &hand), TOTAL);
+
+            // Shuffle: board is deck[0..5], seat i holds deck[5 + 2i..7 + 2i]
+            let mut deck: Vec<u8> = (0..DECK_SIZE as u8).collect();
+            for i in (1..deck.len()).rev() {
+                deck.swap(i, next(i as u64 + 1) as usize);
+            }
+
+            let mut folded_out = false;
+            let mut after = bb;
+            for street in [GamePhase::PreFlop, GamePhase::Flop, GamePhase::Turn, GamePhase::River] {
+                if street != GamePhase::PreFlop {
+                    hand.phase = street;
+                    hand.reset_betting_round(BIG_BLIND);
+                    seats.iter_mut().for_each(|s| s.reset_for_betting_round());
+                    after = live[dealer];
+                    if !hand.can_anyone_bet() {
+                        // All-in runout: no more betting on later streets
+                        continue;
+                    }
+                }
+
+                while let Some(i) = hand.next_to_act(after, SEATS) {
+                    let seat = &mut seats[i as usize];
+                    let to_call = hand.current_bet - seat.current_bet;
+                    match next(3) {
+                        0 if to_call > 0 => {
+                            seat.fold();
+                            hand.fold_player(i);
+                        }
+                        2 if seat.chips > to_call => {
+                            let target = hand.min_raise_to(seat.current_bet, seat.chips);
+                            hand.pot += seat.place_bet(target - seat.current_bet).unwrap();
+                            let raised_over = hand.current_bet;
+                            hand.record_matched(raised_over);
+                            hand.min_raise = hand.min_raise.max(target - raised_over);
+                            hand.current_bet = target;
+                            hand.acted_this_round = 0;
+                        }
+                        _ => {
+                            hand.pot += seat.place_bet(to_call).unwrap();
+                            hand.record_matched(seat.current_bet);
+                        }
+                    }
+                    if seats[i as usize].status == PlayerStatus::AllIn {
+                        hand.mark_all_in(i);
+                    }
+                    hand.mark_acted(i);
+                    after = i;
+                    assert_eq!(conserved(&seats, &hand), TOTAL);
+
+                    // Fold-out: the uncalled bet comes back and the last player takes it all
+                    if hand.active_count == 1 {
+                        hand.return_uncalled_bet();
+                        let winner = (0..SEATS).find(|&s| hand.is_player_active(s)).unwrap();
+                        assert_eq!(conserved(&seats, &hand), TOTAL);
+                        seats[winner as usize].award_chips(hand.pot + hand.uncalled_bet);
+                        hand.pot = 0;
+                        hand.uncalled_bet = 0;
+                        folded_out = true;
+                        break;
+                    }
+                }
+                if folded_out {
+                    break;
+                }
+            }
+
+            // Showdown: evaluate every remaining hand and split the pot
+            if !folded_out {
+                hand.phase = GamePhase::Showdown;
+                let player_cards: Vec<(u8, [u8; 7])> = (0..SEATS)
+                    .filter(|&s| hand.is_player_active(s))
+                    .map(|s| {
+                        let hole = 5 + 2 * s as usize;
+                        let mut cards = [0u8; 7];
+                        cards[..5].copy_from_slice(&deck[..5]);
+                        cards[5..].copy_from_slice(&deck[hole..hole + 2]);
+                        (s, cards)
+                    })
+                    .collect();
+                for (seat, amount) in split_pot(hand.pot, &find_winners(&player_cards)) {
+                    seats[seat as usize].award_chips(amount);
+                }
+                hand.pot = 0;
+            }
+            hand.phase = GamePhase::Settled;
+
+            assert_eq!(conserved(&seats, &hand), TOTAL);
+            assert_eq!(seats.iter().map(|s| s.chips).sum::<u64>(), TOTAL);
+            hands_played += 1;
+        }
+
+        assert!(hands_played >= 10, "session ended after {} hands", hands_played);
+    }
+
+    /// Test only a settled, paid-out hand can be closed
+    #[test]
+    fn test_hand_closable_once_settled() {
+        let mut hand = hand_state(GamePhase::River);
+        hand.hand_number = 3;
+        assert!(!hand.is_closable(3, TableStatus::Playing), "Live hand stays open");
+        assert!(!hand.is_closable(3, TableStatus::Waiting));
+
+        // Fold-out: Settled, but showdown hasn't paid the pot yet
+        hand.phase = GamePhase::Settled;
+        assert!(!hand.is_closable(3, TableStatus::Playing));
+        assert!(hand.is_closable(3, TableStatus::Waiting), "Paid out");
+
+        // An earlier hand is done whatever the table is doing now
+        assert!(hand.is_closable(4, TableStatus::Playing));
+    }
+
+    /// Test a hand account only counts as current for the table's hand number
+    #[test]
+    fn test_stale_hand_is_not_current() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.hand_number = 4;
+
+        assert!(hand.is_current(4));
+        assert!(!hand.is_current(5), "Previous hand is stale once the table moves on");
+        assert!(!hand.is_current(3));
+    }
+
+    /// Test a last action time ahead of the clock never underflows or times out
+    #[test]
+    fn test_elapsed_with_future_timestamp() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.last_action_time = 1_000;
+
+        // Clock behind the recorded action (ER/base layer skew)
+        assert_eq!(hand.seconds_since_last_action(900), 0);
+        assert!(!hand.action_timed_out(900));
+        assert_eq!(hand.seconds_since_last_action(i64::MIN), 0, "No overflow on extreme skew");
+        assert_eq!(hand.seconds_since_last_action(1_000 + ACTION_TIMEOUT_SECONDS), ACTION_TIMEOUT_SECONDS);
+        assert!(hand.action_timed_out(1_000 + ACTION_TIMEOUT_SECONDS));
+
+        hand.last_action_time = i64::MAX;
+        assert_eq!(hand.seconds_since_last_action(0), 0);
+
+        let mut table = table_state();
+        table.last_ready_time = 5_000;
+        assert_eq!(table.seconds_since_ready(4_000), 0);
+        assert_eq!(table.seconds_since_ready(5_030), 30);
+        table.last_ready_time = i64::MIN;
+        assert_eq!(table.seconds_since_ready(i64::MAX), i64::MAX, "Saturates instead of wrapping");
+    }
+
+    /// Test a time bank holds off the action timeout and resets each hand
+    #[test]
+    fn test_time_bank_delays_timeout() {
+        use crate::state::TableConfig;
+
+        let mut table = table_state();
+        assert_eq!(table.time_bank_secs, 0, "No time bank by default");
+        table.apply_config(&TableConfig { time_bank_secs: 90, ..Default::default() });
+
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.last_action_time = 1_000;
+        assert!(hand.action_timed_out(1_000 + ACTION_TIMEOUT_SECONDS));
+
+        // Player on the clock spends the bank: last_action_time moves forward
+        hand.last_action_time += table.time_bank_secs as i64;
+        assert!(!hand.action_timed_out(1_000 + ACTION_TIMEOUT_SECONDS));
+        assert!(!hand.action_timed_out(1_000 + ACTION_TIMEOUT_SECONDS + 89));
+        assert!(hand.action_timed_out(1_000 + ACTION_TIMEOUT_SECONDS + 90));
+
+        let mut seat = player_seat(0, 100);
+        seat.time_bank_used = true;
+        seat.clear_for_new_hand();
+        assert!(!seat.time_bank_used, "Bank is available again next hand");
+    }
+
+    /// Test a blind is refunded when the hand is abandoned before the flop
+    #[test]
+    fn test_abandoned_hand_refunds_blind() {
+        // Heads-up: SB (seat 0) posts 10 and folds, BB (seat 1) posted 20
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.pot = 30;
+        hand.current_bet = 20;
+        hand.record_matched(10);
+        assert!(!hand.is_abandoned(), "Two players still in");
+
+        hand.fold_player(0);
+        hand.return_uncalled_bet();
+        hand.phase = GamePhase::Settled;
+        assert!(hand.is_abandoned());
+        assert!(hand.is_player_active(1));
+
+        // BB leaves: their blind plus the SB's dead blind come back
+        assert_eq!(hand.settle_abandoned(), 30);
+        assert_eq!((hand.pot, hand.uncalled_bet, hand.active_count), (0, 0, 0));
+        assert!(!hand.is_player_active(1), "Refund can't be claimed twice");
+
+        // Once a flop is out the hand isn't abandoned
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.community_revealed = 3;
+        hand.fold_player(0);
+        assert!(!hand.is_abandoned());
+    }
+
+    /// Test pot and bet overflow is a clean error rather than a silently capped total
+    #[test]
+    fn test_pot_overflow_is_an_error() {
+        let mut hand = hand_state(GamePhase::PreFlop);
+        hand.pot = u64::MAX - 10;
+        hand.add_to_pot(0, 10).unwrap();
+        assert_eq!(hand.pot, u64::MAX);
+
+        let err = hand.add_to_pot(1, 1).unwrap_err();
+        assert_eq!(err, HiddenHandError::ArithmeticOverflow.into());
+        assert_eq!(hand.pot, u64::MAX, "Pot is unchanged on overflow");
+
+        // A seat whose running total would wrap keeps its chips
+        let mut seat = player_seat(0, 100);
+        seat.total_bet_this_hand = u64::MAX - 50;
+        let err = seat.place_bet(100).unwrap_err();
+        assert_eq!(err, HiddenHandError::ArithmeticOverflow.into());
+        assert_eq!(seat.chips, 100);
+        assert_eq!(seat.total_bet_this_hand, u64::MAX - 50);
+
+        seat.total_bet_this_hand = 0;
+        assert_eq!(seat.place_bet(100).unwrap(), 100);
+        assert_eq!(seat.status, PlayerStatus::AllIn);
+    }
+
+    /// Test the showdown reveal count reaches zero with the last active reveal,
+    /// and a seat that mucks stops counting
+    #[test]
+    fn test_players_remaining_to_reveal() {
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.active_players = 0b1011;
+        hand.active_count = 3;
+        assert_eq!(hand.players_remaining_to_reveal(), 3);
+
+        hand.mark_revealed(0);
+        assert_eq!(hand.players_remaining_to_reveal(), 2);
+
+        // Seat 3 mucks instead of showing
+        hand.fold_player(3);
+        assert_eq!(hand.players_remaining_to_reveal(), 1);
+
+        hand.mark_revealed(1);
+        assert_eq!(hand.players_remaining_to_reveal(), 0);
+    }
+
+    /// Test clockwise seat ordering wraps past the last seat, visits each
+    /// seat once, and skips seats no longer in the hand
+    #[test]
+    fn test_seat_ordering_wraps_clockwise() {
+        assert_eq!(seats_from(4, 6).collect::<Vec<_>>(), vec![4, 5, 0, 1, 2, 3]);
+        assert_eq!(seats_from(6, 6).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(seats_from(0, 0).count(), 0);
+
+        let mut hand = hand_state(GamePhase::Flop);
+        hand.active_players = 0b10_0101; // seats 0, 2, 5
+        hand.dealer_position = 5;
+        assert_eq!(hand.active_seats_clockwise(3, 6).collect::<Vec<_>>(), vec![5, 0, 2]);
+        assert_eq!(hand.next_active_player(5, 6), Some(0));
+        assert_eq!(hand.next_active_player(2, 6), Some(5));
+
+        // Seat 0 is all-in, so post-flop action opens on seat 2
+        hand.mark_all_in(0);
+        assert_eq!(hand.first_active_left_of_dealer(6), 2);
+        assert_eq!(hand.next_to_act(5, 6), Some(2));
+
+        // Seat 2 has acted: next to act wraps round to seat 5
+        hand.mark_acted(2);
+        assert_eq!(hand.next_to_act(2, 6), Some(5));
+        hand.mark_acted(5);
+        assert_eq!(hand.next_to_act(5, 6), None);
+
+        let mut table = table_state();
+        table.max_players = 6;
+        table.occupied_seats = 0b10_0011; // seats 0, 1, 5
+        table.current_players = 3;
+        table.dealer_position = 5;
+        assert_eq!(table.next_dealer_position(), Some(0));
+        assert_eq!(table.blind_positions(), (0, 1, 5));
+    }
+
+    /// Test ordered reveals open with the last aggressor and go clockwise,
+    /// and a stalled seat lets the next one show after the action timeout
+    #[test]
+    fn test_ordered_reveal() {
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.active_players = 0b1_1011; // seats 0, 1, 3, 4
+        hand.active_count = 4;
+        hand.dealer_position = 0;
+        hand.last_action_time = 1_000;
+
+        // Seat 3 bet the river and is called: it shows first
+        hand.last_aggressor = 3;
+        assert_eq!(hand.expected_revealer(6), Some(3));
+        let err = hand.check_reveal_turn(0, 6, 1_010).unwrap_err();
+        assert_eq!(err, HiddenHandError::RevealOutOfOrder.into());
+        hand.check_reveal_turn(3, 6, 1_010).unwrap();
+
+        // Then clockwise from the aggressor, wrapping past the last seat
+        hand.record_ordered_reveal(3, 6);
+        assert_eq!(hand.expected_revealer(6), Some(4));
+        hand.record_ordered_reveal(4, 6);
+        assert_eq!(hand.expected_revealer(6), Some(0));
+
+        // Seat 0 mucks instead; seat 1 is up
+        hand.fold_player(0);
+        assert_eq!(hand.expected_revealer(6), Some(1));
+        hand.record_ordered_reveal(1, 6);
+        assert_eq!(hand.expected_revealer(6), None);
+
+        // Checked down (no aggressor): first seat left of the dealer opens
+        let mut hand = hand_state(GamePhase::Showdown);
+        hand.active_players = 0b1_1011;
+        hand.dealer_position = 1;
+        hand.last_action_time = 1_000;
+        assert_eq!(hand.expected_revealer(6), Some(3));
+
+        // An aggressor who has since folded doesn't open either
+        hand.last_aggressor = 2;
+        assert_eq!(hand.expected_revealer(6), Some(3));
+
+        // Seat 3 stalls: seat 4 may go ahead once the action timeout passes
+        let err = hand.check_reveal_turn(4, 6, 1_000 + ACTION_TIMEOUT_SECONDS - 1).unwrap_err();
+        assert_eq!(err, HiddenHandError::RevealOutOfOrder.into());
+        hand.check_reveal_turn(4, 6, 1_000 + ACTION_TIMEOUT_SECONDS).unwrap();
+        hand.record_ordered_reveal(4, 6);
+
+        // The stalled seat comes back round once the others have shown
+        hand.record_ordered_reveal(0, 6);
+        hand.record_ordered_reveal(1, 6);
+        assert_eq!(hand.expected_revealer(6), Some(3));
     }
 }
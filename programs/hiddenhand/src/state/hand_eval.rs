@@ -103,6 +103,29 @@ pub fn evaluate_hand(cards: &[u8; 7]) -> EvaluatedHand {
     best_hand.unwrap()
 }
 
+/// Evaluate the best 5-card hand from 5, 6 or 7 cards (an incomplete board)
+/// Seven cards take the evaluate_hand path; six try each card left out
+///
+/// Panics on any other card count
+pub fn evaluate_best(cards: &[u8]) -> EvaluatedHand {
+    match cards.len() {
+        7 => evaluate_hand(&[cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], cards[6]]),
+        6 => (0..6)
+            .map(|skip| {
+                let mut five = [0u8; 5];
+                let kept = cards.iter().enumerate().filter(|&(i, _)| i != skip);
+                for (slot, (_, &card)) in five.iter_mut().zip(kept) {
+                    *slot = card;
+                }
+                evaluate_five_cards(&five)
+            })
+            .max_by(|a, b| a.compare(b))
+            .unwrap(),
+        5 => evaluate_five_cards(&[cards[0], cards[1], cards[2], cards[3], cards[4]]),
+        n => panic!("evaluate_best needs 5 to 7 cards, got {}", n),
+    }
+}
+
 /// Evaluate exactly 5 cards
 fn evaluate_five_cards(cards: &[u8; 5]) -> EvaluatedHand {
     // Extract ranks and suits
@@ -269,8 +292,37 @@ fn is_straight_ranks(ranks: &[u8; 5]) -> bool {
 }
 
 /// Find winners from a list of players with their 7 cards
-/// Returns indices of winning players (multiple = split pot)
+/// Returns seat indices of winning players in ascending order (multiple = split pot),
+/// so the result never depends on the order seats were passed in
 pub fn find_winners(player_cards: &[(u8, [u8; 7])]) -> Vec<u8> {
+    find_best(player_cards, evaluate_hand)
+}
+
+/// Evaluate two hole cards on their own, for variants with no board:
+/// a pair beats any two unpaired cards, then the higher cards play
+pub fn evaluate_hole_cards(cards: &[u8; 2]) -> EvaluatedHand {
+    let (high, low) = {
+        let (a, b) = (get_rank(cards[0]), get_rank(cards[1]));
+        (a.max(b), a.min(b))
+    };
+    if high == low {
+        EvaluatedHand { rank: HandRank::OnePair, kickers: [high, 0, 0, 0, 0] }
+    } else {
+        EvaluatedHand { rank: HandRank::HighCard, kickers: [high, low, 0, 0, 0] }
+    }
+}
+
+/// Find winners on hole cards alone (see evaluate_hole_cards), in ascending
+/// seat order like find_winners
+pub fn find_hole_card_winners(player_cards: &[(u8, [u8; 2])]) -> Vec<u8> {
+    find_best(player_cards, evaluate_hole_cards)
+}
+
+/// Seats holding the best hand under `evaluate`, in ascending order
+fn find_best<const N: usize>(
+    player_cards: &[(u8, [u8; N])],
+    evaluate: fn(&[u8; N]) -> EvaluatedHand,
+) -> Vec<u8> {
     if player_cards.is_empty() {
         return vec![];
     }
@@ -279,7 +331,7 @@ pub fn find_winners(player_cards: &[(u8, [u8; 7])]) -> Vec<u8> {
     let mut winners: Vec<u8> = vec![];
 
     for &(seat_index, cards) in player_cards {
-        let eval = evaluate_hand(&cards);
+        let eval = evaluate(&cards);
 
         match &best_eval {
             None => {
@@ -301,7 +353,175 @@ pub fn find_winners(player_cards: &[(u8, [u8; 7])]) -> Vec<u8> {
         }
     }
 
+    winners.sort_unstable();
+    winners
+}
+
+/// Evaluate the best 8-or-better low from 7 cards (ace-to-five: aces play low,
+/// straights and flushes don't count against the low)
+/// Returns the five low card values (A=1 .. 8) highest first, or None if no low qualifies.
+/// Lower arrays are better lows, e.g. [5, 4, 3, 2, 1] is the nut low (the wheel)
+pub fn evaluate_low(cards: &[u8; 7]) -> Option<[u8; 5]> {
+    // Bit i set = low value i held (1 = ace .. 8)
+    let mut held: u16 = 0;
+    for &card in cards {
+        let low_value = match get_rank(card) {
+            12 => 1,                       // Ace
+            rank if rank <= 6 => rank + 2, // 2-8
+            _ => continue,
+        };
+        held |= 1 << low_value;
+    }
+
+    // Take the five lowest distinct values
+    let mut low = [0u8; 5];
+    let mut count = 0;
+    for value in 1..=8u8 {
+        if held & (1 << value) != 0 {
+            low[count] = value;
+            count += 1;
+            if count == 5 {
+                low.reverse();
+                return Some(low);
+            }
+        }
+    }
+    None
+}
+
+/// Find the best qualifying low hands
+/// Returns seat indices of the low winners in ascending order
+/// (empty if nobody qualifies, multiple = low split)
+pub fn find_low_winners(player_cards: &[(u8, [u8; 7])]) -> Vec<u8> {
+    let mut best_low: Option<[u8; 5]> = None;
+    let mut winners: Vec<u8> = vec![];
+
+    for &(seat_index, cards) in player_cards {
+        let Some(low) = evaluate_low(&cards) else {
+            continue;
+        };
+
+        match best_low {
+            Some(best) if low > best => {}
+            Some(best) if low == best => winners.push(seat_index),
+            _ => {
+                best_low = Some(low);
+                winners = vec![seat_index];
+            }
+        }
+    }
+
+    winners.sort_unstable();
+    winners
+}
+
+/// Split a pot evenly between winners. The first winner receives any odd chips
+/// (the lowest seat index, as find_winners returns winners sorted)
+/// Returns (seat_index, amount) payouts
+pub fn split_pot(pot: u64, winners: &[u8]) -> Vec<(u8, u64)> {
+    if winners.is_empty() {
+        return vec![];
+    }
+
+    let share = pot / winners.len() as u64;
+    let remainder = pot % winners.len() as u64;
+
     winners
+        .iter()
+        .enumerate()
+        .map(|(i, &seat)| (seat, if i == 0 { share + remainder } else { share }))
+        .collect()
+}
+
+/// One layer of the pot and the seats that can win it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidePot {
+    pub amount: u64,
+    /// Contesting seats that put in at least this layer's level, sorted
+    pub eligible: Vec<u8>,
+}
+
+/// Bet that nobody matched: the top contributor's chips above the
+/// second-highest contribution. Every seat's total bet this hand counts,
+/// folded seats included, since their chips matched up to where they folded.
+/// Returns (seat_index, excess), or None when the top bet was matched
+pub fn uncalled_excess(contributions: &[(u8, u64)]) -> Option<(u8, u64)> {
+    let &(top_seat, top_bet) = contributions.iter().max_by_key(|(_, bet)| *bet)?;
+    let second_bet = contributions
+        .iter()
+        .filter(|(seat, _)| *seat != top_seat)
+        .map(|(_, bet)| *bet)
+        .max()
+        .unwrap_or(0);
+    (top_bet > second_bet).then_some((top_seat, top_bet - second_bet))
+}
+
+/// Layer the pot into a main pot and side pots, lowest level first.
+/// Each distinct bet level of a contesting seat closes a layer that every
+/// seat contributes to up to that level (folded seats included), and that
+/// only contesting seats who reached the level can win. Chips above the top
+/// contesting level (a folded seat's unmatched bet) go to the last layer.
+/// `contributions` are (seat_index, total bet this hand, still contesting)
+pub fn build_side_pots(contributions: &[(u8, u64, bool)]) -> Vec<SidePot> {
+    let mut levels: Vec<u64> = contributions
+        .iter()
+        .filter(|(_, bet, contesting)| *contesting && *bet > 0)
+        .map(|(_, bet, _)| *bet)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<SidePot> = Vec::with_capacity(levels.len());
+    let mut previous = 0u64;
+    for &level in levels.iter() {
+        let amount = contributions
+            .iter()
+            .map(|(_, bet, _)| (*bet).min(level) - (*bet).min(previous))
+            .sum();
+        let mut eligible: Vec<u8> = contributions
+            .iter()
+            .filter(|(_, bet, contesting)| *contesting && *bet >= level)
+            .map(|(seat, _, _)| *seat)
+            .collect();
+        eligible.sort_unstable();
+        pots.push(SidePot { amount, eligible });
+        previous = level;
+    }
+
+    let leftover: u64 = contributions
+        .iter()
+        .map(|(_, bet, _)| bet.saturating_sub(previous))
+        .sum();
+    if let Some(last) = pots.last_mut() {
+        last.amount += leftover;
+    }
+
+    pots
+}
+
+/// Split a hi-lo pot: half to the best high hand(s), half to the best qualifying low(s)
+/// - No qualifying low: the high hand scoops the whole pot
+/// - Tied lows split the low half (quartering when two players share the nut low)
+/// - The odd chip of an uneven pot goes to the high half
+///
+/// Returns (seat_index, amount) payouts, one entry per seat
+pub fn split_hi_lo(pot: u64, high_winners: &[u8], low_winners: &[u8]) -> Vec<(u8, u64)> {
+    if low_winners.is_empty() {
+        return split_pot(pot, high_winners);
+    }
+
+    let low_half = pot / 2;
+    let high_half = pot - low_half;
+
+    let mut payouts = split_pot(high_half, high_winners);
+    for (seat, amount) in split_pot(low_half, low_winners) {
+        match payouts.iter_mut().find(|(s, _)| *s == seat) {
+            Some((_, total)) => *total += amount,
+            None => payouts.push((seat, amount)),
+        }
+    }
+
+    payouts
 }
 
 #[cfg(test)]
@@ -345,6 +565,34 @@ mod tests {
         assert_eq!(eval.kickers[0], 7); // 9-high
     }
 
+    #[test]
+    fn test_steel_wheel_beats_king_high_flush() {
+        // Six hearts: A-2-3-4-5 and K. The K-high flush (K-5-4-3-2) is only a
+        // flush; the 5-high straight flush ranks above every flush
+        let hearts = [
+            card(12, 0), // Ah
+            card(0, 0),  // 2h
+            card(1, 0),  // 3h
+            card(2, 0),  // 4h
+            card(3, 0),  // 5h
+            card(11, 0), // Kh
+        ];
+        let cards = [hearts[0], hearts[1], hearts[2], hearts[3], hearts[4], hearts[5], card(7, 1)];
+        let eval = evaluate_hand(&cards);
+        assert_eq!(eval.rank, HandRank::StraightFlush);
+        assert_eq!(eval.kickers[0], 3); // 5-high (wheel)
+
+        let eval = evaluate_best(&hearts);
+        assert_eq!(eval.rank, HandRank::StraightFlush);
+        assert_eq!(eval.kickers[0], 3);
+
+        // Against a plain K-high heart flush on the same board, the wheel wins
+        let board = [card(0, 0), card(1, 0), card(2, 0), card(11, 0), card(7, 1)];
+        let wheel = (0u8, [card(12, 0), card(3, 0), board[0], board[1], board[2], board[3], board[4]]);
+        let flush = (1u8, [card(9, 0), card(8, 0), board[0], board[1], board[2], board[3], board[4]]);
+        assert_eq!(find_winners(&[wheel, flush]), vec![0]);
+    }
+
     #[test]
     fn test_four_of_a_kind() {
         let cards = [
@@ -511,6 +759,25 @@ mod tests {
         assert_eq!(winners, vec![0]); // Aces beats Kings
     }
 
+    #[test]
+    fn test_find_hole_card_winners() {
+        // A pair beats two high cards; unpaired hands compare high then low card
+        assert_eq!(evaluate_hole_cards(&[card(0, 0), card(0, 1)]).rank, HandRank::OnePair);
+        assert_eq!(evaluate_hole_cards(&[card(12, 0), card(11, 0)]).rank, HandRank::HighCard);
+        let hands = [
+            (0, [card(12, 0), card(3, 1)]), // A5
+            (2, [card(0, 2), card(0, 3)]),  // 22
+            (5, [card(12, 1), card(11, 2)]), // AK
+        ];
+        assert_eq!(find_hole_card_winners(&hands), vec![2]);
+        assert_eq!(find_hole_card_winners(&[hands[0], hands[2]]), vec![5]);
+
+        // Same ranks in different suits chop
+        let chop = [(1, [card(12, 0), card(11, 1)]), (0, [card(11, 0), card(12, 1)])];
+        assert_eq!(find_hole_card_winners(&chop), vec![0, 1]);
+        assert!(find_hole_card_winners(&[]).is_empty());
+    }
+
     #[test]
     fn test_find_winners_split() {
         // Both players have same straight
@@ -528,6 +795,34 @@ mod tests {
         assert_eq!(winners.len(), 2);
     }
 
+    #[test]
+    fn test_find_winners_sorted_regardless_of_input_order() {
+        // Every seat plays the straight on the board, passed in seat-scrambled order
+        let board = [card(8, 0), card(7, 1), card(6, 2), card(5, 3), card(4, 0)];
+        let hand = |seat: u8, hole: [u8; 2]| {
+            (seat, [hole[0], hole[1], board[0], board[1], board[2], board[3], board[4]])
+        };
+        let mut player_cards = vec![
+            hand(4, [card(0, 1), card(1, 2)]),
+            hand(0, [card(0, 2), card(1, 3)]),
+            hand(3, [card(0, 3), card(1, 0)]),
+            hand(1, [card(0, 0), card(1, 1)]),
+        ];
+
+        assert_eq!(find_winners(&player_cards), vec![0, 1, 3, 4]);
+        player_cards.reverse();
+        assert_eq!(find_winners(&player_cards), vec![0, 1, 3, 4]);
+
+        // Odd chip goes to the lowest winning seat either way
+        let payouts = split_pot(101, &find_winners(&player_cards));
+        assert_eq!(payouts[0], (0, 26));
+
+        // Low winners are canonical too: seats 5 and 2 share the wheel
+        let wheel = [card(12, 0), card(0, 1), card(1, 2), card(2, 3), card(3, 0), card(11, 1), card(10, 2)];
+        let lows = [(5, wheel), (2, wheel)];
+        assert_eq!(find_low_winners(&lows), vec![2, 5]);
+    }
+
     // ==================== EDGE CASE TESTS ====================
 
     #[test]
@@ -1172,4 +1467,190 @@ mod tests {
 
         println!("✅ All edge cases passed");
     }
+
+    #[test]
+    fn test_evaluate_low() {
+        // Wheel (A-2-3-4-5) is the nut low even though it's also a straight
+        let wheel = [
+            card(12, 0), card(0, 1), card(1, 2), card(2, 3), card(3, 0),
+            card(11, 1), card(10, 2),
+        ];
+        assert_eq!(evaluate_low(&wheel), Some([5, 4, 3, 2, 1]));
+
+        // Pairs don't help: 8-7-6-4-2 with a paired deuce
+        let eight_low = [
+            card(6, 0), card(5, 1), card(4, 2), card(2, 3), card(0, 0),
+            card(0, 1), card(11, 2),
+        ];
+        assert_eq!(evaluate_low(&eight_low), Some([8, 7, 6, 4, 2]));
+
+        // Only four cards 8 or lower - no low
+        let no_low = [
+            card(12, 0), card(0, 1), card(1, 2), card(6, 3),
+            card(7, 0), card(8, 1), card(11, 2),
+        ];
+        assert_eq!(evaluate_low(&no_low), None);
+    }
+
+    #[test]
+    fn test_hi_lo_scoop() {
+        // Board: Kh Qd 9c 5s 4h - only two low cards, no low possible
+        let board = [card(11, 0), card(10, 1), card(7, 2), card(3, 3), card(2, 0)];
+        let aces = (0u8, [card(12, 1), card(12, 2), board[0], board[1], board[2], board[3], board[4]]);
+        let low_cards = (1u8, [card(0, 1), card(1, 2), board[0], board[1], board[2], board[3], board[4]]);
+        let hands = [aces, low_cards];
+
+        let high = find_winners(&hands);
+        let low = find_low_winners(&hands);
+        assert_eq!(high, vec![0]);
+        assert!(low.is_empty());
+
+        assert_eq!(split_hi_lo(1000, &high, &low), vec![(0, 1000)]);
+    }
+
+    #[test]
+    fn test_hi_lo_split() {
+        // Board: Ks 8d 6c 4h 3s
+        let board = [card(11, 3), card(6, 1), card(4, 2), card(2, 0), card(1, 3)];
+        // Seat 0: Kh Kd - set of kings, no low
+        let set = (0u8, [card(11, 0), card(11, 1), board[0], board[1], board[2], board[3], board[4]]);
+        // Seat 1: Ah 2c - 6-4-3-2-A low
+        let low_hand = (1u8, [card(12, 0), card(0, 2), board[0], board[1], board[2], board[3], board[4]]);
+        let hands = [set, low_hand];
+
+        let high = find_winners(&hands);
+        let low = find_low_winners(&hands);
+        assert_eq!(high, vec![0]);
+        assert_eq!(low, vec![1]);
+
+        // Odd chip goes to the high half
+        assert_eq!(split_hi_lo(1001, &high, &low), vec![(0, 501), (1, 500)]);
+    }
+
+    #[test]
+    fn test_hi_lo_quartered_low() {
+        // Board: Kc Qd 7h 5s 3c
+        let board = [card(11, 2), card(10, 1), card(5, 0), card(3, 3), card(1, 2)];
+        // Seat 0: Kh Ks - set of kings, no low
+        let set = (0u8, [card(11, 0), card(11, 3), board[0], board[1], board[2], board[3], board[4]]);
+        // Seats 1 and 2: both hold A-2 for the same 7-5-3-2-A nut low
+        let low_a = (1u8, [card(12, 0), card(0, 1), board[0], board[1], board[2], board[3], board[4]]);
+        let low_b = (2u8, [card(12, 1), card(0, 2), board[0], board[1], board[2], board[3], board[4]]);
+        let hands = [set, low_a, low_b];
+
+        let high = find_winners(&hands);
+        let low = find_low_winners(&hands);
+        assert_eq!(high, vec![0]);
+        assert_eq!(low, vec![1, 2]);
+
+        // High takes half, the tied lows get a quarter each
+        assert_eq!(
+            split_hi_lo(1200, &high, &low),
+            vec![(0, 600), (1, 300), (2, 300)]
+        );
+
+        // Same player winning both halves is combined into one payout
+        assert_eq!(split_hi_lo(1000, &[1], &[1, 2]), vec![(1, 750), (2, 250)]);
+    }
+
+    #[test]
+    fn test_uncalled_excess_above_second_highest() {
+        // 50/50/200 all-in: only 50 of the big stack is called, 150 comes back
+        assert_eq!(uncalled_excess(&[(0, 50), (1, 50), (2, 200)]), Some((2, 150)));
+
+        // 50/100/200: the 200 stack is covered up to 100, so 100 comes back
+        // and the 100 stack keeps a live side pot instead of a refund
+        assert_eq!(uncalled_excess(&[(0, 50), (1, 100), (2, 200)]), Some((2, 100)));
+
+        // A folded seat's chips still count as matched
+        assert_eq!(uncalled_excess(&[(0, 50), (1, 180), (2, 200)]), Some((2, 20)));
+
+        // Top bet matched: nothing to return
+        assert_eq!(uncalled_excess(&[(0, 100), (1, 100), (2, 40)]), None);
+        assert_eq!(uncalled_excess(&[]), None);
+    }
+
+    #[test]
+    fn test_build_side_pots_worked_example() {
+        // Stacks 50/100/200 all-in, after the 200 stack's uncalled 100 is returned:
+        // main pot 3 x 50 for everyone, side pot 2 x 50 for seats 1 and 2
+        let pots = build_side_pots(&[(0, 50, true), (1, 100, true), (2, 100, true)]);
+        assert_eq!(
+            pots,
+            vec![
+                SidePot { amount: 150, eligible: vec![0, 1, 2] },
+                SidePot { amount: 100, eligible: vec![1, 2] },
+            ]
+        );
+        assert_eq!(pots.iter().map(|pot| pot.amount).sum::<u64>(), 250);
+
+        // Best hand is the short stack: it wins the main pot only, and the side
+        // pot goes to the best of the two that covered it
+        let payouts: Vec<(u8, u64)> = pots
+            .iter()
+            .map(|pot| {
+                let winner = if pot.eligible.contains(&0) { 0 } else { 2 };
+                split_pot(pot.amount, &[winner])[0]
+            })
+            .collect();
+        assert_eq!(payouts, vec![(0, 150), (2, 100)]);
+
+        // 50/50/200 after the 150 refund is a single pot
+        let pots = build_side_pots(&[(0, 50, true), (1, 50, true), (2, 50, true)]);
+        assert_eq!(pots, vec![SidePot { amount: 150, eligible: vec![0, 1, 2] }]);
+    }
+
+    #[test]
+    fn test_build_side_pots_with_folded_money() {
+        // Seat 1 folded after putting in 80: it funds both layers but wins neither
+        let pots = build_side_pots(&[(0, 30, true), (1, 80, false), (2, 100, true)]);
+        assert_eq!(
+            pots,
+            vec![
+                SidePot { amount: 90, eligible: vec![0, 2] },
+                SidePot { amount: 120, eligible: vec![2] },
+            ]
+        );
+
+        // A folded bet above every contesting level lands in the last layer
+        let pots = build_side_pots(&[(0, 40, true), (1, 60, false), (2, 40, true)]);
+        assert_eq!(pots, vec![SidePot { amount: 140, eligible: vec![0, 2] }]);
+    }
+
+    #[test]
+    fn test_evaluate_best_five_cards() {
+        // Five cards are the hand as-is
+        let cards = [card(8, 0), card(8, 1), card(3, 2), card(3, 3), card(12, 0)];
+        let eval = evaluate_best(&cards);
+        assert_eq!(eval.rank, HandRank::TwoPair);
+        assert_eq!(eval.kickers, evaluate_five_cards(&cards).kickers);
+    }
+
+    #[test]
+    fn test_evaluate_best_six_cards() {
+        // Flop plus turn: the straight uses five of the six cards
+        let cards = [
+            card(8, 0),  // Th
+            card(7, 1),  // 9d
+            card(6, 2),  // 8c
+            card(5, 3),  // 7s
+            card(4, 0),  // 6h
+            card(0, 1),  // 2d
+        ];
+        let eval = evaluate_best(&cards);
+        assert_eq!(eval.rank, HandRank::Straight);
+        assert_eq!(eval.kickers[0], 8); // T-high
+
+        // Best five kickers: the 2 is left out
+        let cards = [card(12, 0), card(10, 1), card(8, 2), card(6, 3), card(4, 0), card(0, 1)];
+        let eval = evaluate_best(&cards);
+        assert_eq!(eval.rank, HandRank::HighCard);
+        assert_eq!(eval.kickers, [12, 10, 8, 6, 4]);
+
+        // Seven cards agree with evaluate_hand
+        let seven = [card(12, 0), card(12, 1), card(5, 2), card(5, 3), card(5, 0), card(0, 1), card(1, 2)];
+        let (best, hand) = (evaluate_best(&seven), evaluate_hand(&seven));
+        assert_eq!(best.rank, HandRank::FullHouse);
+        assert_eq!(best.compare(&hand), std::cmp::Ordering::Equal);
+    }
 }
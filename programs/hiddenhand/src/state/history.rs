@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{COMMUNITY_CARD_COUNT, SEAT_BITMAP_SIZE};
+
+/// One seat's line in a recorded hand
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct HistorySeat {
+    /// Player's wallet pubkey
+    pub player: Pubkey,
+
+    /// Seat index (0-7)
+    pub seat_index: u8,
+
+    /// Hole cards (255 = not shown / folded)
+    pub hole_card_1: u8,
+    pub hole_card_2: u8,
+
+    /// Total bet this hand (chips put into pot)
+    pub chips_bet: u64,
+
+    /// Chips paid out to this seat at settlement
+    pub chips_won: u64,
+
+    /// Whether player folded
+    pub folded: bool,
+}
+
+impl HistorySeat {
+    pub const SIZE: usize = 32 + // player
+        1 +  // seat_index
+        1 +  // hole_card_1
+        1 +  // hole_card_2
+        8 +  // chips_bet
+        8 +  // chips_won
+        1;   // folded
+}
+
+/// Persistent record of a settled hand, written by showdown when the table
+/// has `record_history` enabled. Unlike HandCompleted events, this can be
+/// fetched from any RPC for dispute resolution and replay.
+#[account]
+pub struct HandHistory {
+    /// Reference to table
+    pub table: Pubkey,
+
+    /// Hand number this record belongs to
+    pub hand_number: u64,
+
+    /// Unix timestamp when the hand settled
+    pub timestamp: i64,
+
+    /// Final board (255 = not dealt)
+    pub community_cards: [u8; COMMUNITY_CARD_COUNT],
+
+    /// Total pot that was distributed
+    pub total_pot: u64,
+
+    /// Per-seat results (first `seat_count` entries are valid)
+    pub seats: [HistorySeat; SEAT_BITMAP_SIZE],
+
+    /// How many entries in `seats` are valid
+    pub seat_count: u8,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl HandHistory {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // table
+        8 +  // hand_number
+        8 +  // timestamp
+        COMMUNITY_CARD_COUNT + // community_cards
+        8 +  // total_pot
+        HistorySeat::SIZE * SEAT_BITMAP_SIZE + // seats
+        1 +  // seat_count
+        1;   // bump
+
+    /// Seats recorded for this hand
+    pub fn recorded_seats(&self) -> &[HistorySeat] {
+        &self.seats[..(self.seat_count as usize).min(self.seats.len())]
+    }
+}
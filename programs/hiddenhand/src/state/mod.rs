@@ -3,9 +3,17 @@ pub mod hand;
 pub mod player;
 pub mod deck;
 pub mod hand_eval;
+pub mod history;
+pub mod shuffle;
+pub mod stats;
+pub mod allowlist;
 
 pub use table::*;
 pub use hand::*;
 pub use player::*;
 pub use deck::*;
 pub use hand_eval::*;
+pub use history::*;
+pub use shuffle::*;
+pub use stats::*;
+pub use allowlist::*;
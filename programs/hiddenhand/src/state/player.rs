@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{AUTO_SIT_OUT_TIMEOUTS, CARD_SENTINEL};
+use crate::error::HiddenHandError;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum PlayerStatus {
     /// Seated but not in current hand
@@ -10,6 +13,8 @@ pub enum PlayerStatus {
     Folded,
     /// All-in this hand
     AllIn,
+    /// Sat out after timing out too many hands in a row - not dealt in until sit_in
+    Away,
 }
 
 impl Default for PlayerStatus {
@@ -63,6 +68,21 @@ pub struct PlayerSeat {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Add-ons bought at this seat (capped by table.max_addons)
+    pub addon_count: u8,
+
+    /// Whether the time bank was spent this hand
+    pub time_bank_used: bool,
+
+    /// Hands in a row this player was auto-folded by timeout_player
+    pub consecutive_timeouts: u8,
+
+    /// Hands this seat has been dealt into since sitting down
+    pub hands_played: u32,
+
+    /// Hands in which this seat won at least part of the pot
+    pub hands_won: u32,
 }
 
 impl PlayerSeat {
@@ -80,19 +100,110 @@ impl PlayerSeat {
         1 +  // cards_revealed
         1 +  // status
         1 +  // has_acted
-        1;   // bump
+        1 +  // bump
+        1 +  // addon_count
+        1 +  // time_bank_used
+        1 +  // consecutive_timeouts
+        4 +  // hands_played
+        4;   // hands_won
 
-    /// Reset for new hand
-    pub fn reset_for_new_hand(&mut self) {
+    /// Clear everything left over from the previous hand. Used by showdown
+    /// when a hand settles and by every deal path before dealing to a seat.
+    ///
+    /// Card sentinels after clearing:
+    /// - `hole_card_1/2 = CARD_SENTINEL`: not dealt. Dealt cards are either
+    ///   plaintext (0-51) or Inco handles (> 255), so the sentinel is never a real
+    ///   card - but it is `> 51`, so check `cards_not_dealt()` before treating a
+    ///   card as encrypted
+    /// - `revealed_card_1/2 = CARD_SENTINEL`: not revealed
+    ///
+    /// The seat is left Sitting (Away once it has timed out too often); the
+    /// deal marks it Playing when it receives cards
+    pub fn clear_for_new_hand(&mut self) {
         self.current_bet = 0;
         self.total_bet_this_hand = 0;
-        self.hole_card_1 = 255; // Sentinel: not dealt yet
-        self.hole_card_2 = 255; // Sentinel: not dealt yet
-        self.revealed_card_1 = 255; // Not revealed
-        self.revealed_card_2 = 255; // Not revealed
+        self.hole_card_1 = CARD_SENTINEL as u128; // Not dealt
+        self.hole_card_2 = CARD_SENTINEL as u128; // Not dealt
+        self.revealed_card_1 = CARD_SENTINEL; // Not revealed
+        self.revealed_card_2 = CARD_SENTINEL; // Not revealed
         self.cards_revealed = false;
-        self.status = PlayerStatus::Playing;
+        self.sit_out_hand();
         self.has_acted = false;
+        self.time_bank_used = false;
+    }
+
+    /// Deal the seat into the hand: it is now Playing, and the hand counts
+    /// towards its hands played
+    pub fn deal_in(&mut self) {
+        self.status = PlayerStatus::Playing;
+        self.hands_played = self.hands_played.saturating_add(1);
+    }
+
+    /// Count a hand this seat won (all or part of the pot)
+    pub fn record_win(&mut self) {
+        self.hands_won = self.hands_won.saturating_add(1);
+    }
+
+    /// Undo a misdealt hand: give back the `refund` the seat put in the pot
+    /// and clear its cards
+    pub fn refund_misdeal(&mut self, refund: u64) {
+        if self.is_in_hand() {
+            self.hands_played = self.hands_played.saturating_sub(1);
+        }
+        self.chips = self.chips.saturating_add(refund);
+        self.clear_for_new_hand();
+    }
+
+    /// Timed out often enough in a row to be sat out of new hands
+    pub fn is_away(&self) -> bool {
+        self.status == PlayerStatus::Away || self.consecutive_timeouts >= AUTO_SIT_OUT_TIMEOUTS
+    }
+
+    /// Whether a deal should give this seat cards
+    pub fn can_be_dealt(&self) -> bool {
+        self.chips > 0 && !self.is_away()
+    }
+
+    /// Leave the seat out of the hand being dealt
+    pub fn sit_out_hand(&mut self) {
+        self.status = if self.is_away() {
+            PlayerStatus::Away
+        } else {
+            PlayerStatus::Sitting
+        };
+    }
+
+    /// Count an auto-fold by timeout_player.
+    /// Returns true when this timeout is the one that sits the player out
+    pub fn record_timeout(&mut self) -> bool {
+        self.consecutive_timeouts = self.consecutive_timeouts.saturating_add(1);
+        self.consecutive_timeouts == AUTO_SIT_OUT_TIMEOUTS
+    }
+
+    /// Back from being away - dealt in again from the next hand
+    pub fn sit_in(&mut self) {
+        self.consecutive_timeouts = 0;
+        if self.status == PlayerStatus::Away {
+            self.status = PlayerStatus::Sitting;
+        }
+    }
+
+    /// No hole cards dealt to this seat (cleared, or sitting out this hand)
+    pub fn cards_not_dealt(&self) -> bool {
+        self.hole_card_1 == CARD_SENTINEL as u128 || self.hole_card_2 == CARD_SENTINEL as u128
+    }
+
+    /// Hole cards were dealt in plaintext (0-51, non-encrypted game) rather
+    /// than as Inco handles, so a reveal is checked against them directly
+    pub fn has_plaintext_cards(&self) -> bool {
+        !self.cards_not_dealt() && self.hole_card_1 <= 51 && self.hole_card_2 <= 51
+    }
+
+    /// Whether a reveal names exactly the plaintext cards dealt to this seat
+    pub fn plaintext_reveal_matches(&self, card1: u8, card2: u8) -> bool {
+        self.has_plaintext_cards()
+            && self.hole_card_1 == card1 as u128
+            && self.hole_card_2 == card2 as u128
     }
 
     /// Reset for new betting round
@@ -102,17 +213,27 @@ impl PlayerSeat {
     }
 
     /// Place a bet (returns actual amount bet, handles all-in)
-    pub fn place_bet(&mut self, amount: u64) -> u64 {
+    /// Errors instead of saturating if the seat's bet totals would overflow
+    pub fn place_bet(&mut self, amount: u64) -> Result<u64> {
         let actual_bet = amount.min(self.chips);
-        self.chips = self.chips.saturating_sub(actual_bet);
-        self.current_bet = self.current_bet.saturating_add(actual_bet);
-        self.total_bet_this_hand = self.total_bet_this_hand.saturating_add(actual_bet);
+        let current_bet = self
+            .current_bet
+            .checked_add(actual_bet)
+            .ok_or(HiddenHandError::ArithmeticOverflow)?;
+        let total_bet_this_hand = self
+            .total_bet_this_hand
+            .checked_add(actual_bet)
+            .ok_or(HiddenHandError::ArithmeticOverflow)?;
+
+        self.chips -= actual_bet;
+        self.current_bet = current_bet;
+        self.total_bet_this_hand = total_bet_this_hand;
 
         if self.chips == 0 {
             self.status = PlayerStatus::AllIn;
         }
 
-        actual_bet
+        Ok(actual_bet)
     }
 
     /// Award chips (from winning pot)
@@ -120,13 +241,133 @@ impl PlayerSeat {
         self.chips = self.chips.saturating_add(amount);
     }
 
+    /// Trim the stack down to `cap`, returning the chips taken off
+    pub fn trim_stack(&mut self, cap: u64) -> u64 {
+        let excess = self.chips.saturating_sub(cap);
+        self.chips -= excess;
+        excess
+    }
+
     /// Check if player can act (not folded or all-in)
     pub fn can_act(&self) -> bool {
         matches!(self.status, PlayerStatus::Playing)
     }
 
+    /// Check if player is still in the hand (playing or all-in, not folded or sitting out)
+    /// All-in players can't act but still owe reveals and contest the pot
+    pub fn is_in_hand(&self) -> bool {
+        matches!(self.status, PlayerStatus::Playing | PlayerStatus::AllIn)
+    }
+
     /// Fold the hand
     pub fn fold(&mut self) {
         self.status = PlayerStatus::Folded;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::player_seat;
+
+    /// Test hands played/won persist across hands while seated
+    #[test]
+    fn test_seat_hand_counters() {
+        let mut seat = player_seat(0, 1000);
+
+        seat.clear_for_new_hand();
+        seat.deal_in();
+        assert_eq!(seat.status, PlayerStatus::Playing);
+        seat.record_win();
+
+        // Next hand: clearing the seat keeps the counters
+        seat.clear_for_new_hand();
+        seat.deal_in();
+
+        assert_eq!(seat.hands_played, 2);
+        assert_eq!(seat.hands_won, 1);
+    }
+
+    /// Test three timeout folds in a row sit a player out until sit_in
+    #[test]
+    fn test_consecutive_timeouts_sit_player_out() {
+        let mut seat = player_seat(0, 1_000);
+
+        // Two timeouts, then a voluntary action resets the run
+        assert!(!seat.record_timeout());
+        assert!(!seat.record_timeout());
+        seat.consecutive_timeouts = 0;
+        seat.clear_for_new_hand();
+        assert_eq!(seat.status, PlayerStatus::Sitting);
+
+        // Three in a row - the third one sits the player out
+        assert!(!seat.record_timeout());
+        assert!(!seat.record_timeout());
+        assert!(seat.record_timeout());
+        assert!(seat.is_away());
+
+        // Settling the hand leaves the seat away, and no deal includes it
+        seat.clear_for_new_hand();
+        assert_eq!(seat.status, PlayerStatus::Away);
+        assert!(!seat.can_be_dealt());
+        seat.sit_out_hand();
+        assert_eq!(seat.status, PlayerStatus::Away);
+
+        // A fourth timeout can't re-trigger the event
+        assert!(!seat.record_timeout());
+
+        // Back in from the next hand
+        seat.sit_in();
+        assert_eq!(seat.status, PlayerStatus::Sitting);
+        assert_eq!(seat.consecutive_timeouts, 0);
+        assert!(seat.can_be_dealt());
+    }
+
+    /// Test a cleared seat uses the same not-dealt sentinels everywhere
+    #[test]
+    fn test_clear_for_new_hand() {
+        // Seat left over from a hand that reached showdown with encrypted cards
+        let mut seat = player_seat(3, 500);
+        seat.hole_card_1 = 1 << 100;
+        seat.hole_card_2 = (1 << 100) + 1;
+        seat.revealed_card_1 = 12;
+        seat.revealed_card_2 = 40;
+        seat.cards_revealed = true;
+        seat.current_bet = 50;
+        seat.total_bet_this_hand = 150;
+        seat.has_acted = true;
+        seat.status = PlayerStatus::AllIn;
+        assert!(!seat.cards_not_dealt());
+
+        seat.clear_for_new_hand();
+        assert!(seat.cards_not_dealt());
+        assert_eq!((seat.hole_card_1, seat.hole_card_2), (CARD_SENTINEL as u128, CARD_SENTINEL as u128));
+        assert_eq!((seat.revealed_card_1, seat.revealed_card_2), (CARD_SENTINEL, CARD_SENTINEL));
+        assert!(!seat.cards_revealed);
+        assert_eq!((seat.current_bet, seat.total_bet_this_hand), (0, 0));
+        assert!(!seat.has_acted);
+        assert_eq!(seat.status, PlayerStatus::Sitting);
+        assert!(!seat.is_in_hand());
+        assert_eq!(seat.chips, 500, "Stack is untouched");
+
+        // Plaintext card 0 is a dealt card, not the sentinel
+        seat.hole_card_1 = 0;
+        seat.hole_card_2 = 51;
+        assert!(!seat.cards_not_dealt());
+    }
+
+    /// Test stacks over the cap are trimmed back to it and the excess returned
+    #[test]
+    fn test_trim_stack() {
+        let mut seat = player_seat(0, 1_250);
+        assert_eq!(seat.trim_stack(1_000), 250);
+        assert_eq!(seat.chips, 1_000);
+
+        // At or under the cap nothing changes
+        assert_eq!(seat.trim_stack(1_000), 0);
+        assert_eq!(seat.chips, 1_000);
+        let mut short = player_seat(1, 400);
+        assert_eq!(short.trim_stack(1_000), 0);
+        assert_eq!(short.chips, 400);
+    }
+}
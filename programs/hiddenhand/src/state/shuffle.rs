@@ -0,0 +1,93 @@
+use sha2::{Digest, Sha256};
+
+use crate::constants::DECK_SIZE;
+
+/// Shuffle a fresh deck (cards 0-51) with VRF randomness.
+/// Deterministic, so verify_fairness can replay a hand's deal from its seed
+pub fn shuffle_deck(randomness: &[u8; 32]) -> [u8; DECK_SIZE] {
+    let word = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&randomness[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+
+    // Initialize deck with cards 0-51
+    let mut deck: [u8; DECK_SIZE] = core::array::from_fn(|i| i as u8);
+
+    // Convert randomness to u64 seed for Fisher-Yates shuffle
+    let mut seed = word(0);
+
+    // Fisher-Yates shuffle using VRF randomness
+    for i in (1..DECK_SIZE).rev() {
+        // Use different parts of randomness for each iteration
+        if i % 4 == 0 && i < 28 {
+            // Mix in more randomness periodically
+            let offset = (i / 4) * 8;
+            if offset + 8 <= 32 {
+                seed ^= word(offset);
+            }
+        }
+
+        // LCG step with VRF-seeded state
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let j = (seed % (i as u64 + 1)) as usize;
+        deck.swap(i, j);
+    }
+
+    deck
+}
+
+/// SHA-256 commitment to the VRF randomness stored in DeckState
+pub fn seed_commitment(randomness: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(randomness).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DeckState;
+
+    fn seed(byte: u8) -> [u8; 32] {
+        core::array::from_fn(|i| byte.wrapping_add(i as u8))
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        for byte in [0u8, 1, 7, 42, 200, 255] {
+            let deck = shuffle_deck(&seed(byte));
+            let mut seen = [false; DECK_SIZE];
+            for &card in deck.iter() {
+                assert!((card as usize) < DECK_SIZE, "Card {} out of range", card);
+                assert!(!seen[card as usize], "Card {} dealt twice", card);
+                seen[card as usize] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_shuffle_passes_permutation_check() {
+        for byte in 0..=u8::MAX {
+            assert!(DeckState::verify_permutation(&shuffle_deck(&seed(byte))).is_ok());
+        }
+
+        let mut duplicated = shuffle_deck(&seed(3));
+        duplicated[0] = duplicated[1];
+        assert!(DeckState::verify_permutation(&duplicated).is_err());
+
+        let mut out_of_range = shuffle_deck(&seed(3));
+        out_of_range[0] = 52;
+        assert!(DeckState::verify_permutation(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_shuffle_same_seed_same_order() {
+        assert_eq!(shuffle_deck(&seed(42)), shuffle_deck(&seed(42)));
+        assert_ne!(shuffle_deck(&seed(42)), shuffle_deck(&seed(43)));
+    }
+
+    #[test]
+    fn test_seed_commitment_binds_seed() {
+        assert_eq!(seed_commitment(&seed(9)), seed_commitment(&seed(9)));
+        assert_ne!(seed_commitment(&seed(9)), seed_commitment(&seed(10)));
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+/// Running totals for a table, kept by showdown when the table has
+/// `track_stats` enabled. Created on the first tracked showdown
+#[account]
+pub struct TableStats {
+    /// Reference to table
+    pub table: Pubkey,
+
+    /// Hands settled while tracking was on
+    pub hands_played: u64,
+
+    /// Total rake taken
+    pub total_raked: u64,
+
+    /// Largest pot settled
+    pub biggest_pot: u64,
+
+    /// Sum of every settled pot
+    pub total_volume: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TableStats {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // table
+        8 +  // hands_played
+        8 +  // total_raked
+        8 +  // biggest_pot
+        8 +  // total_volume
+        1;   // bump
+
+    /// Count a settled hand. Returns true if its pot is a new biggest pot
+    pub fn record_hand(&mut self, pot: u64, rake: u64) -> bool {
+        self.hands_played = self.hands_played.saturating_add(1);
+        self.total_raked = self.total_raked.saturating_add(rake);
+        self.total_volume = self.total_volume.saturating_add(pot);
+
+        let record = pot > self.biggest_pot;
+        if record {
+            self.biggest_pot = pot;
+        }
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test each hand result carries its net once the pot is awarded
+    #[test]
+    fn test_table_stats_record_hand() {
+        let mut stats = TableStats {
+            table: Pubkey::default(),
+            hands_played: 0,
+            total_raked: 0,
+            biggest_pot: 0,
+            total_volume: 0,
+            bump: 0,
+        };
+
+        assert!(stats.record_hand(20, 0));
+        assert!(stats.record_hand(40, 2), "bigger pot is a new record");
+        assert!(!stats.record_hand(30, 1), "smaller pot is not");
+
+        assert_eq!(stats.hands_played, 3);
+        assert_eq!(stats.biggest_pot, 40);
+        assert_eq!(stats.total_volume, 90);
+        assert_eq!(stats.total_raked, 3);
+        assert_eq!(TableStats::SIZE, 8 + 32 + 8 * 4 + 1);
+    }
+}
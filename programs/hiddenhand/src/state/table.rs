@@ -1,5 +1,17 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    MAX_ALLOWLIST_PROOF_LEN, MAX_WAITLIST, MIN_HAND_GAP_SLOTS, MIN_PLAYERS, TABLE_MAX_LIFETIME_SLOTS,
+};
+use crate::error::HiddenHandError;
+use crate::state::{verify_allowlist_proof, GamePhase};
+
+/// Seat indices in clockwise (dealing) order starting at `start` and wrapping
+/// round the table, each seat exactly once. Pass `seat + 1` to start after a seat
+pub fn seats_from(start: u8, max_players: u8) -> impl Iterator<Item = u8> {
+    (0..max_players as u16).map(move |offset| ((start as u16 + offset) % max_players as u16) as u8)
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum TableStatus {
     /// Waiting for players to join
@@ -8,6 +20,8 @@ pub enum TableStatus {
     Playing,
     /// Table is closed
     Closed,
+    /// Halted by the authority between hands - no new hands until resumed
+    Paused,
 }
 
 impl Default for TableStatus {
@@ -16,6 +30,95 @@ impl Default for TableStatus {
     }
 }
 
+/// Poker variant dealt at the table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace, Default)]
+pub enum GameType {
+    /// Two hole cards and a five card board
+    #[default]
+    TexasHoldem,
+    /// No shared board (e.g. draw games) - hands play out on hole cards alone
+    NoBoard,
+    /// Hold'em hi-lo split: half the pot to the best high, half to the best 8-or-better low
+    HoldemHiLo,
+}
+
+impl GameType {
+    /// Number of community cards dealt for this variant (0-5)
+    pub fn community_card_count(&self) -> u8 {
+        match self {
+            GameType::TexasHoldem | GameType::HoldemHiLo => 5,
+            GameType::NoBoard => 0,
+        }
+    }
+}
+
+/// How much a player may bet or raise
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace, Default)]
+pub enum BettingMode {
+    /// Any amount up to the player's stack
+    #[default]
+    NoLimit,
+    /// Bets and raises of exactly one small bet (the big blind) pre-flop and
+    /// on the flop, one big bet (two big blinds) on the turn and river, with
+    /// a cap on raises each street
+    FixedLimit,
+}
+
+/// Table options the authority can change between hands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TableConfig {
+    /// Poker variant to deal
+    pub game_type: GameType,
+    /// Rotate reveal/grant duties to the dealer-seat player each hand
+    pub rotating_operator: bool,
+    /// Write a HandHistory account at each showdown (caller pays the rent)
+    pub record_history: bool,
+    /// Add-ons each seat may buy (0 disables add-ons)
+    pub max_addons: u8,
+    /// Hand number from which add-ons open (the add-on break)
+    pub addon_after_hand: u64,
+    /// Seated players needed before a hand can start (2..=max_players)
+    pub min_players_to_start: u8,
+    /// Extra seconds a player can add to their clock once per hand (0 disables)
+    pub time_bank_secs: u32,
+    /// Burn a card before the flop, turn and river, as in live dealing
+    pub use_burn_cards: bool,
+    /// Betting structure
+    pub betting_mode: BettingMode,
+    /// Bets plus raises allowed each street in fixed-limit (0 = uncapped)
+    pub max_raises_per_street: u8,
+    /// Keep a TableStats account updated at each showdown (caller pays the rent once)
+    pub track_stats: bool,
+    /// Merkle root of the players allowed to sit (all zeroes = public table)
+    pub allowlist_root: [u8; 32],
+    /// Hands are shown in live order at showdown: last aggressor first, then clockwise
+    pub ordered_reveal: bool,
+    /// Stacks above max_buy_in after a hand are trimmed back to it, the excess
+    /// paid out to the player's wallet
+    pub cap_stack_per_hand: bool,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            game_type: GameType::default(),
+            rotating_operator: false,
+            record_history: false,
+            max_addons: 0,
+            addon_after_hand: 0,
+            min_players_to_start: MIN_PLAYERS,
+            time_bank_secs: 0,
+            use_burn_cards: false,
+            betting_mode: BettingMode::default(),
+            max_raises_per_street: 0,
+            track_stats: false,
+            allowlist_root: [0u8; 32],
+            ordered_reveal: false,
+            cap_stack_per_hand: false,
+        }
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Table {
@@ -60,6 +163,62 @@ pub struct Table {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Poker variant dealt at this table
+    pub game_type: GameType,
+
+    /// When set, the dealer-seat player operates each hand instead of the authority
+    pub rotating_operator: bool,
+
+    /// When set, showdown persists each hand to a HandHistory PDA
+    pub record_history: bool,
+
+    /// Add-ons each seat may buy between hands (0 = add-ons disabled)
+    pub max_addons: u8,
+
+    /// Add-ons open once this many hands have been played
+    pub addon_after_hand: u64,
+
+    /// Seated players needed before start_hand will deal
+    pub min_players_to_start: u8,
+
+    /// Time bank each seat can spend once per hand, in seconds (0 = disabled)
+    pub time_bank_secs: u32,
+
+    /// Wallet seated at each seat index (default pubkey = empty), one slot
+    /// per occupied_seats bit, so clients can find a player's seat without scanning
+    pub seat_owners: [Pubkey; 8],
+
+    /// Slot the last hand was started in (0 = no hand yet)
+    pub last_hand_start_slot: u64,
+
+    /// Slot the table was created in
+    pub created_slot: u64,
+
+    /// Burn a card before each street (cosmetic - the deck is already shuffled)
+    pub use_burn_cards: bool,
+
+    /// Betting structure
+    pub betting_mode: BettingMode,
+
+    /// Bets plus raises allowed each street in fixed-limit (0 = uncapped)
+    pub max_raises_per_street: u8,
+
+    /// Keep a TableStats account updated at each showdown
+    pub track_stats: bool,
+
+    /// Merkle root of the players allowed to sit (all zeroes = public table)
+    pub allowlist_root: [u8; 32],
+
+    /// Hands are shown in live order at showdown: last aggressor first, then clockwise
+    pub ordered_reveal: bool,
+
+    /// Stacks above max_buy_in are trimmed back to it when each hand settles
+    pub cap_stack_per_hand: bool,
+
+    /// Players waiting for a seat, in arrival order (front is next)
+    #[max_len(8)]
+    pub waitlist: Vec<Pubkey>,
 }
 
 impl Table {
@@ -77,23 +236,211 @@ impl Table {
         1 +  // occupied_seats
         1 +  // dealer_position
         8 +  // last_ready_time
-        1;   // bump
+        1 +  // bump
+        1 +  // game_type (enum)
+        1 +  // rotating_operator
+        1 +  // record_history
+        1 +  // max_addons
+        8 +  // addon_after_hand
+        1 +  // min_players_to_start
+        4 +  // time_bank_secs
+        32 * 8 + // seat_owners
+        8 +  // last_hand_start_slot
+        8 +  // created_slot
+        1 +  // use_burn_cards
+        1 +  // betting_mode (enum)
+        1 +  // max_raises_per_street
+        1 +  // track_stats
+        32 + // allowlist_root
+        1 +  // ordered_reveal
+        1 +  // cap_stack_per_hand
+        4 + 32 * MAX_WAITLIST; // waitlist vec (4 byte length + pubkeys)
+
+    /// Apply authority-configurable options
+    pub fn apply_config(&mut self, config: &TableConfig) {
+        self.game_type = config.game_type;
+        self.rotating_operator = config.rotating_operator;
+        self.record_history = config.record_history;
+        self.max_addons = config.max_addons;
+        self.addon_after_hand = config.addon_after_hand;
+        self.min_players_to_start = config.min_players_to_start;
+        self.time_bank_secs = config.time_bank_secs;
+        self.use_burn_cards = config.use_burn_cards;
+        self.betting_mode = config.betting_mode;
+        self.max_raises_per_street = config.max_raises_per_street;
+        self.track_stats = config.track_stats;
+        self.allowlist_root = config.allowlist_root;
+        self.ordered_reveal = config.ordered_reveal;
+        self.cap_stack_per_hand = config.cap_stack_per_hand;
+    }
+
+    /// Whether the table only seats allowlisted players
+    pub fn is_private(&self) -> bool {
+        self.allowlist_root != [0u8; 32]
+    }
+
+    /// Check a joining player against the allowlist. Public tables seat anyone
+    pub fn check_allowlisted(&self, player: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        if !self.is_private() {
+            return Ok(());
+        }
+        require!(
+            proof.len() <= MAX_ALLOWLIST_PROOF_LEN
+                && verify_allowlist_proof(&self.allowlist_root, player, proof),
+            HiddenHandError::NotAllowlisted
+        );
+        Ok(())
+    }
+
+    /// Raise cap the table's hands enforce each street (0 = uncapped).
+    /// Only fixed-limit caps raises
+    pub fn raise_cap(&self) -> u8 {
+        match self.betting_mode {
+            BettingMode::FixedLimit => self.max_raises_per_street,
+            BettingMode::NoLimit => 0,
+        }
+    }
+
+    /// Size of every bet and raise on `phase` at a fixed-limit table (None = no limit)
+    pub fn fixed_bet_size(&self, phase: GamePhase) -> Option<u64> {
+        match self.betting_mode {
+            BettingMode::NoLimit => None,
+            BettingMode::FixedLimit => match phase {
+                GamePhase::Turn | GamePhase::River => Some(self.big_blind.saturating_mul(2)),
+                _ => Some(self.big_blind),
+            },
+        }
+    }
+
+    /// Whether enough players are seated to start a hand
+    pub fn has_enough_players(&self) -> bool {
+        self.current_players >= self.min_players_to_start
+    }
+
+    /// Seconds since the table was last ready at `now`, zero if the recorded
+    /// time is ahead of the clock
+    pub fn seconds_since_ready(&self, now: i64) -> i64 {
+        now.saturating_sub(self.last_ready_time).max(0)
+    }
+
+    /// Check at least MIN_HAND_GAP_SLOTS have passed since the last hand started
+    pub fn check_hand_gap(&self, slot: u64) -> Result<()> {
+        require!(
+            self.last_hand_start_slot == 0
+                || slot.saturating_sub(self.last_hand_start_slot) >= MIN_HAND_GAP_SLOTS,
+            HiddenHandError::HandStartedTooSoon
+        );
+        Ok(())
+    }
+
+    /// Halt new hands. Only between hands, so a paused table never has a
+    /// hand left hanging; seats and chips stay put
+    pub fn pause(&mut self) -> Result<()> {
+        require!(self.status != TableStatus::Paused, HiddenHandError::TablePaused);
+        require!(
+            self.status == TableStatus::Waiting,
+            HiddenHandError::HandInProgress
+        );
+        self.status = TableStatus::Paused;
+        Ok(())
+    }
+
+    /// Let hands start again after a pause
+    pub fn resume(&mut self) -> Result<()> {
+        require!(
+            self.status == TableStatus::Paused,
+            HiddenHandError::TableNotPaused
+        );
+        self.status = TableStatus::Waiting;
+        Ok(())
+    }
+
+    /// Check the table can be archived at `slot`: no hand in progress, nobody
+    /// seated, and TABLE_MAX_LIFETIME_SLOTS since it was created
+    pub fn check_archivable(&self, slot: u64) -> Result<()> {
+        require!(
+            self.status != TableStatus::Playing,
+            HiddenHandError::HandInProgress
+        );
+        require!(self.current_players == 0, HiddenHandError::TableNotEmpty);
+        require!(
+            slot.saturating_sub(self.created_slot) >= TABLE_MAX_LIFETIME_SLOTS,
+            HiddenHandError::TableNotExpired
+        );
+        Ok(())
+    }
+
+    /// Whether the add-on break has been reached
+    pub fn addons_open(&self) -> bool {
+        self.max_addons > 0 && self.hand_number >= self.addon_after_hand
+    }
 
     /// Check if a seat is occupied
     pub fn is_seat_occupied(&self, seat_index: u8) -> bool {
         self.occupied_seats & (1 << seat_index) != 0
     }
 
-    /// Mark a seat as occupied
-    pub fn occupy_seat(&mut self, seat_index: u8) {
+    /// Check a player can sit at `seat_index`: in range for this table,
+    /// not already taken, and the table isn't full. Must pass before
+    /// occupy_seat, which would otherwise set a bit outside the seat range
+    pub fn check_seat_available(&self, seat_index: u8) -> Result<()> {
+        require!(
+            seat_index < self.max_players,
+            HiddenHandError::InvalidSeatIndex
+        );
+        require!(
+            !self.is_seat_occupied(seat_index),
+            HiddenHandError::SeatOccupied
+        );
+        require!(
+            self.current_players < self.max_players,
+            HiddenHandError::TableFull
+        );
+        Ok(())
+    }
+
+    /// Mark a seat as occupied by `player`
+    pub fn occupy_seat(&mut self, seat_index: u8, player: Pubkey) {
         self.occupied_seats |= 1 << seat_index;
         self.current_players += 1;
+        self.seat_owners[seat_index as usize] = player;
     }
 
     /// Mark a seat as vacant
     pub fn vacate_seat(&mut self, seat_index: u8) {
         self.occupied_seats &= !(1 << seat_index);
         self.current_players = self.current_players.saturating_sub(1);
+        if let Some(owner) = self.seat_owners.get_mut(seat_index as usize) {
+            *owner = Pubkey::default();
+        }
+    }
+
+    /// Seat index `player` is sitting at, if any
+    pub fn seat_of_player(&self, player: &Pubkey) -> Option<u8> {
+        if *player == Pubkey::default() {
+            return None;
+        }
+        (0..self.max_players.min(self.seat_owners.len() as u8))
+            .find(|&seat| self.is_seat_occupied(seat) && self.seat_owners[seat as usize] == *player)
+    }
+
+    /// Check if a player is already queued for a seat
+    pub fn is_on_waitlist(&self, player: &Pubkey) -> bool {
+        self.waitlist.contains(player)
+    }
+
+    /// Take the next player off the waitlist
+    pub fn pop_waitlist(&mut self) -> Option<Pubkey> {
+        if self.waitlist.is_empty() {
+            None
+        } else {
+            Some(self.waitlist.remove(0))
+        }
+    }
+
+    /// Drop a player from the waitlist (e.g. once they take a seat)
+    pub fn remove_from_waitlist(&mut self, player: &Pubkey) {
+        self.waitlist.retain(|p| p != player);
     }
 
     /// Find first available seat
@@ -106,15 +453,404 @@ impl Table {
         None
     }
 
+    /// Seat the button moves to next: the first occupied seat after the current button
+    /// Reads the live occupancy bitmap, so joins and leaves since the last hand are
+    /// honored - a vacated button seat is passed over rather than kept or counted twice
+    pub fn next_dealer_position(&self) -> Option<u8> {
+        seats_from(self.dealer_position + 1, self.max_players)
+            .find(|&seat| self.is_seat_occupied(seat))
+    }
+
+    /// Blind seats and the first to act pre-flop for the current button, as
+    /// (small blind, big blind, first to act). Heads-up the dealer posts the
+    /// small blind and acts first; otherwise the blinds are the next two
+    /// occupied seats after the button and UTG opens
+    pub fn blind_positions(&self) -> (u8, u8, u8) {
+        let next_occupied = |from: u8| {
+            seats_from(from + 1, self.max_players)
+                .find(|&seat| self.is_seat_occupied(seat))
+                .unwrap_or(from)
+        };
+
+        if self.current_players == 2 {
+            let sb = self.dealer_position;
+            (sb, next_occupied(sb), sb)
+        } else {
+            let sb = next_occupied(self.dealer_position);
+            let bb = next_occupied(sb);
+            (sb, bb, next_occupied(bb))
+        }
+    }
+
     /// Advance dealer button to next occupied seat
-    pub fn advance_dealer(&mut self) {
-        let mut next = (self.dealer_position + 1) % self.max_players;
-        for _ in 0..self.max_players {
-            if self.is_seat_occupied(next) {
-                self.dealer_position = next;
-                return;
+    pub fn advance_dealer(&mut self) -> Result<()> {
+        let next = self
+            .next_dealer_position()
+            .ok_or(HiddenHandError::NotEnoughPlayers)?;
+        self.dealer_position = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{allowlist_leaf, allowlist_node};
+    use crate::test_fixtures::{hand_state, player_seat, table_state};
+
+    /// Test the button follows occupancy changes between hands
+    #[test]
+    fn test_button_after_seat_change() {
+        let mut table = table_state();
+        table.max_players = 6;
+        table.occupied_seats = 0b00_0111; // seats 0, 1, 2
+        table.current_players = 3;
+        table.dealer_position = 1;
+
+        table.advance_dealer().unwrap();
+        assert_eq!(table.dealer_position, 2);
+
+        // Button seat leaves and a new player sits at seat 4 before the next hand
+        table.vacate_seat(2);
+        table.occupy_seat(4, Pubkey::new_unique());
+        table.advance_dealer().unwrap();
+        assert_eq!(table.dealer_position, 4, "Vacated button seat is passed over");
+
+        // A join behind the button doesn't pull it backwards
+        table.occupy_seat(3, Pubkey::new_unique());
+        table.advance_dealer().unwrap();
+        assert_eq!(table.dealer_position, 0, "Button wraps to the next occupied seat");
+        table.advance_dealer().unwrap();
+        assert_eq!(table.dealer_position, 1);
+
+        // Empty table has nowhere to put the button
+        table.occupied_seats = 0;
+        assert_eq!(table.next_dealer_position(), None);
+        assert!(table.advance_dealer().is_err());
+        assert_eq!(table.dealer_position, 1);
+    }
+
+    /// Test add-ons open at the break level and stop at the per-seat cap
+    #[test]
+    fn test_addon_window() {
+        let mut table = table_state();
+        assert!(!table.addons_open(), "Add-ons are off by default");
+
+        table.max_addons = 2;
+        table.addon_after_hand = 10;
+        table.hand_number = 9;
+        assert!(!table.addons_open(), "Not before the break level");
+        table.hand_number = 10;
+        assert!(table.addons_open());
+
+        let mut seat = player_seat(0, 0);
+        seat.addon_count = 1;
+        assert!(seat.addon_count < table.max_addons);
+        seat.addon_count = 2;
+        assert!(seat.addon_count >= table.max_addons, "Cap reached");
+    }
+
+    /// Test a table configured for more players won't start short-handed
+    #[test]
+    fn test_min_players_to_start() {
+        assert_eq!(TableConfig::default().min_players_to_start, MIN_PLAYERS);
+
+        let mut table = table_state();
+        table.max_players = 6;
+        table.apply_config(&TableConfig { min_players_to_start: 3, ..Default::default() });
+        assert!(!table.has_enough_players(), "2 seated, 3 required");
+
+        table.occupy_seat(2, Pubkey::new_unique());
+        assert!(table.has_enough_players());
+
+        table.apply_config(&TableConfig::default());
+        table.current_players = 2;
+        assert!(table.has_enough_players());
+    }
+
+    /// Test start_hand's slot gap: a second start in the same slot is refused
+    #[test]
+    fn test_hand_start_gap() {
+        let mut table = table_state();
+        assert!(table.check_hand_gap(0).is_ok(), "First hand is never rate limited");
+
+        // First start_hand at slot 1_000
+        table.check_hand_gap(1_000).unwrap();
+        table.last_hand_start_slot = 1_000;
+
+        assert_eq!(
+            table.check_hand_gap(1_000).unwrap_err(),
+            HiddenHandError::HandStartedTooSoon.into()
+        );
+        assert_eq!(
+            table.check_hand_gap(1_000 + MIN_HAND_GAP_SLOTS - 1).unwrap_err(),
+            HiddenHandError::HandStartedTooSoon.into()
+        );
+        assert!(table.check_hand_gap(1_000 + MIN_HAND_GAP_SLOTS).is_ok());
+    }
+
+    /// Test a table can only be archived once empty and past its lifetime
+    #[test]
+    fn test_archive_table_lifetime() {
+        let mut table = table_state();
+        table.created_slot = 5_000;
+        let expired = 5_000 + TABLE_MAX_LIFETIME_SLOTS;
+
+        // Seated players keep the table alive - their chips need close_inactive_table
+        assert_eq!(
+            table.check_archivable(expired).unwrap_err(),
+            HiddenHandError::TableNotEmpty.into()
+        );
+
+        table.current_players = 0;
+        table.occupied_seats = 0;
+        assert_eq!(
+            table.check_archivable(expired - 1).unwrap_err(),
+            HiddenHandError::TableNotExpired.into()
+        );
+        assert!(table.check_archivable(expired).is_ok());
+
+        // Closed tables are archivable too, but never one mid-hand
+        table.status = TableStatus::Closed;
+        assert!(table.check_archivable(expired).is_ok());
+        table.status = TableStatus::Playing;
+        assert_eq!(
+            table.check_archivable(expired).unwrap_err(),
+            HiddenHandError::HandInProgress.into()
+        );
+    }
+
+    /// Test seat lookup by wallet follows joins and leaves
+    #[test]
+    fn test_seat_of_player() {
+        let mut table = table_state();
+        table.max_players = 6;
+        table.occupied_seats = 0;
+        table.current_players = 0;
+
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        assert_eq!(table.seat_of_player(&alice), None);
+
+        table.occupy_seat(4, alice);
+        table.occupy_seat(1, bob);
+        assert_eq!(table.seat_of_player(&alice), Some(4));
+        assert_eq!(table.seat_of_player(&bob), Some(1));
+
+        table.vacate_seat(4);
+        assert_eq!(table.seat_of_player(&alice), None);
+        assert_eq!(table.seat_of_player(&bob), Some(1));
+        assert_eq!(table.seat_of_player(&Pubkey::default()), None, "Empty slots never match");
+
+        // Rejoining at another seat is found there
+        table.occupy_seat(0, alice);
+        assert_eq!(table.seat_of_player(&alice), Some(0));
+    }
+
+    /// Test private-table joins: allowlisted players with a valid Merkle proof
+    /// are seated, outsiders and bad proofs are not, public tables seat anyone
+    #[test]
+    fn test_allowlist_proof() {
+        let players: Vec<Pubkey> = (1..=3u8).map(|b| Pubkey::new_from_array([b; 32])).collect();
+        let leaves: Vec<[u8; 32]> = players.iter().map(allowlist_leaf).collect();
+        // Three leaves: the odd one out is paired up a level higher
+        let ab = allowlist_node(&leaves[0], &leaves[1]);
+        let root = allowlist_node(&ab, &leaves[2]);
+
+        let mut table = table_state();
+        let outsider = Pubkey::new_from_array([9; 32]);
+        assert!(!table.is_private());
+        assert!(table.check_allowlisted(&outsider, &[]).is_ok(), "Public table");
+
+        table.allowlist_root = root;
+        assert!(table.is_private());
+        assert!(table.check_allowlisted(&players[0], &[leaves[1], leaves[2]]).is_ok());
+        assert!(table.check_allowlisted(&players[1], &[leaves[0], leaves[2]]).is_ok());
+        assert!(table.check_allowlisted(&players[2], &[ab]).is_ok());
+
+        assert!(table.check_allowlisted(&outsider, &[leaves[1], leaves[2]]).is_err());
+        assert!(table.check_allowlisted(&players[0], &[]).is_err(), "Missing proof");
+        assert!(table.check_allowlisted(&players[2], &[leaves[0]]).is_err(), "Wrong proof");
+        let too_long = vec![[0u8; 32]; MAX_ALLOWLIST_PROOF_LEN + 1];
+        assert!(table.check_allowlisted(&players[0], &too_long).is_err());
+    }
+
+    /// Test pausing only between hands, and resuming only a paused table
+    #[test]
+    fn test_pause_and_resume() {
+        let mut table = table_state();
+        assert!(table.resume().is_err(), "Not paused");
+
+        table.pause().unwrap();
+        assert_eq!(table.status, TableStatus::Paused);
+        assert!(table.pause().is_err(), "Already paused");
+
+        table.resume().unwrap();
+        assert_eq!(table.status, TableStatus::Waiting);
+
+        table.status = TableStatus::Playing;
+        assert!(table.pause().is_err(), "Mid-hand");
+    }
+
+    /// Test join seat validation: range, occupancy, and a full table
+    #[test]
+    fn test_check_seat_available() {
+        let mut table = table_state();
+        table.max_players = 4;
+        table.occupied_seats = 0b0001;
+        table.current_players = 1;
+
+        assert!(table.check_seat_available(1).is_ok());
+        assert!(table.check_seat_available(3).is_ok());
+
+        // Past this table's max_players, even though MAX_PLAYERS allows it
+        assert_eq!(
+            table.check_seat_available(4).unwrap_err(),
+            HiddenHandError::InvalidSeatIndex.into()
+        );
+        assert_eq!(
+            table.check_seat_available(u8::MAX).unwrap_err(),
+            HiddenHandError::InvalidSeatIndex.into()
+        );
+
+        assert_eq!(
+            table.check_seat_available(0).unwrap_err(),
+            HiddenHandError::SeatOccupied.into()
+        );
+
+        table.occupied_seats = 0b0111;
+        table.current_players = 4; // out of sync bitmap still can't overfill
+        assert_eq!(
+            table.check_seat_available(3).unwrap_err(),
+            HiddenHandError::TableFull.into()
+        );
+    }
+
+    /// Test join_table_auto's lowest-free-seat rule hands out distinct seats
+    #[test]
+    fn test_auto_join_picks_distinct_seats() {
+        let mut table = table_state();
+        table.max_players = 4;
+        table.occupied_seats = 0b0010;
+        table.current_players = 1;
+
+        // Joins landing one after another each take the next gap
+        let mut picked = Vec::new();
+        while let Some(seat) = table.find_empty_seat() {
+            assert!(table.check_seat_available(seat).is_ok());
+            table.occupy_seat(seat, Pubkey::new_unique());
+            picked.push(seat);
+        }
+        assert_eq!(picked, vec![0, 2, 3]);
+
+        // Full table: nothing to pick
+        assert_eq!(table.find_empty_seat(), None);
+
+        // A freed seat is the next one handed out
+        table.vacate_seat(2);
+        assert_eq!(table.find_empty_seat(), Some(2));
+    }
+
+    /// Test waitlist queue order and removal
+    #[test]
+    fn test_waitlist() {
+        let mut table = table_state();
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(table.pop_waitlist(), None);
+
+        table.waitlist.extend([alice, bob, carol]);
+        assert!(table.is_on_waitlist(&bob));
+
+        // First in, first out
+        assert_eq!(table.pop_waitlist(), Some(alice));
+        assert!(!table.is_on_waitlist(&alice));
+
+        // Taking a seat directly drops the player from the queue
+        table.remove_from_waitlist(&carol);
+        assert_eq!(table.waitlist, vec![bob]);
+    }
+
+    /// Test community card counts per game type
+    #[test]
+    fn test_game_type_community_cards() {
+        assert_eq!(GameType::default(), GameType::TexasHoldem);
+        assert_eq!(GameType::TexasHoldem.community_card_count(), 5);
+        assert_eq!(GameType::NoBoard.community_card_count(), 0);
+        assert_eq!(GameType::HoldemHiLo.community_card_count(), 5);
+    }
+
+    /// Test the heads-up dealer posts the small blind and acts first pre-flop
+    #[test]
+    fn test_heads_up_dealer_acts_first_preflop() {
+        let mut table = table_state();
+        table.max_players = 6;
+        table.occupied_seats = 0;
+        table.current_players = 0;
+        table.occupy_seat(1, Pubkey::new_unique());
+        table.occupy_seat(4, Pubkey::new_unique());
+        assert_eq!(table.current_players, 2);
+
+        for dealer in [1u8, 4] {
+            table.dealer_position = dealer;
+            let other = if dealer == 1 { 4 } else { 1 };
+            let (sb, bb, first_to_act) = table.blind_positions();
+            assert_eq!(sb, dealer, "Dealer posts the small blind heads-up");
+            assert_eq!(bb, other);
+            assert_eq!(first_to_act, dealer, "Dealer acts first pre-flop heads-up");
+        }
+
+        // Three-handed the blinds move left of the button and UTG opens
+        table.occupy_seat(5, Pubkey::new_unique());
+        table.dealer_position = 1;
+        assert_eq!(table.blind_positions(), (4, 5, 1));
+        table.dealer_position = 5;
+        assert_eq!(table.blind_positions(), (1, 4, 5));
+    }
+
+    /// Test the deal posts blinds to exactly the seats start_hand recorded
+    #[test]
+    fn test_blinds_posted_to_recorded_seats() {
+        let mut table = table_state();
+        table.max_players = 6;
+        table.occupied_seats = 0;
+        table.current_players = 0;
+        for seat in [0u8, 2, 5] {
+            table.occupy_seat(seat, Pubkey::new_unique());
+        }
+
+        for (dealer, players) in [(2u8, 3u8), (5, 2)] {
+            if players == 2 {
+                table.vacate_seat(0);
             }
-            next = (next + 1) % self.max_players;
+            table.dealer_position = dealer;
+
+            // start_hand records the blinds once
+            let (sb_pos, bb_pos, _) = table.blind_positions();
+            let mut hand = hand_state(GamePhase::Dealing);
+            hand.dealer_position = dealer;
+            hand.sb_seat = sb_pos;
+            hand.bb_seat = bb_pos;
+
+            // callback_shuffle posts what the hand recorded, nothing else
+            let posted: Vec<(u8, u64)> = (0..table.max_players)
+                .filter(|&seat| table.is_seat_occupied(seat))
+                .map(|seat| (seat, hand.forced_bet_for_seat(seat, 10, 20)))
+                .filter(|&(_, bet)| bet > 0)
+                .collect();
+            assert_eq!(posted, {
+                let mut expected = vec![(sb_pos, 10), (bb_pos, 20)];
+                expected.sort();
+                expected
+            });
         }
+
+        // Bomb pot: every seat antes, blind or not
+        let mut hand = hand_state(GamePhase::Dealing);
+        hand.sb_seat = 5;
+        hand.bb_seat = 2;
+        hand.bomb_pot_ante = 7;
+        assert_eq!(hand.forced_bet_for_seat(5, 10, 20), 7);
+        assert_eq!(hand.forced_bet_for_seat(0, 10, 20), 7);
     }
 }
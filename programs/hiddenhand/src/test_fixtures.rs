@@ -0,0 +1,127 @@
+//! Account fixtures shared by the unit tests
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{BettingMode, DeckState, GamePhase, GameType, HandState, PlayerSeat, PlayerStatus, Table, TableStatus};
+
+/// Build a full two-seat table
+pub(crate) fn table_state() -> Table {
+    Table {
+        authority: Pubkey::default(),
+        table_id: [0; 32],
+        small_blind: 1,
+        big_blind: 2,
+        min_buy_in: 20,
+        max_buy_in: 100,
+        max_players: 2,
+        current_players: 2,
+        status: TableStatus::Waiting,
+        hand_number: 0,
+        occupied_seats: 0b11,
+        dealer_position: 0,
+        last_ready_time: 0,
+        bump: 0,
+        game_type: GameType::TexasHoldem,
+        rotating_operator: false,
+        record_history: false,
+        max_addons: 0,
+        addon_after_hand: 0,
+        min_players_to_start: MIN_PLAYERS,
+        time_bank_secs: 0,
+        seat_owners: [Pubkey::default(); 8],
+        last_hand_start_slot: 0,
+        created_slot: 0,
+        use_burn_cards: false,
+        betting_mode: BettingMode::NoLimit,
+        max_raises_per_street: 0,
+        track_stats: false,
+        allowlist_root: [0u8; 32],
+        ordered_reveal: false,
+        cap_stack_per_hand: false,
+        waitlist: vec![],
+    }
+}
+
+/// Build a two-player hand state for the given phase
+pub(crate) fn hand_state(phase: GamePhase) -> HandState {
+    HandState {
+        table: Pubkey::default(),
+        hand_number: 1,
+        phase,
+        pot: 0,
+        current_bet: 0,
+        min_raise: 0,
+        dealer_position: 0,
+        action_on: 0,
+        community_cards: vec![CARD_SENTINEL; 5],
+        community_revealed: 0,
+        active_players: 0b11,
+        acted_this_round: 0,
+        active_count: 2,
+        all_in_players: 0,
+        last_action_time: 0,
+        hand_start_time: 0,
+        awaiting_community_reveal: false,
+        bump: 0,
+        community_card_count: 5,
+        street_start_pot: 0,
+        hand_operator: Pubkey::default(),
+        any_bet_made_this_street: false,
+        matched_bet: 0,
+        uncalled_bet: 0,
+        raise_closed: 0,
+        bomb_pot_ante: 0,
+        folded_players: 0,
+        departed_players: 0,
+        max_raises_per_street: 0,
+        raises_this_street: 0,
+        sb_seat: 0,
+        bb_seat: 1,
+        payouts_done: false,
+        revealed_players: 0,
+        last_aggressor: NO_SEAT,
+        next_to_reveal: NO_SEAT,
+        contributed: [0; SEAT_BITMAP_SIZE],
+    }
+}
+
+/// Build a seated player with the given stack
+pub(crate) fn player_seat(seat_index: u8, chips: u64) -> PlayerSeat {
+    PlayerSeat {
+        table: Pubkey::default(),
+        player: Pubkey::new_unique(),
+        seat_index,
+        chips,
+        current_bet: 0,
+        total_bet_this_hand: 0,
+        hole_card_1: 0,
+        hole_card_2: 0,
+        revealed_card_1: CARD_SENTINEL,
+        revealed_card_2: CARD_SENTINEL,
+        cards_revealed: false,
+        status: PlayerStatus::Playing,
+        has_acted: false,
+        bump: 0,
+        addon_count: 0,
+        time_bank_used: false,
+        consecutive_timeouts: 0,
+        hands_played: 0,
+        hands_won: 0,
+    }
+}
+
+/// Build a shuffled deck whose cards are the given handles (rest plaintext 0..)
+pub(crate) fn deck_state(handles: &[u128]) -> DeckState {
+    let mut cards: [u128; DECK_SIZE] = core::array::from_fn(|i| i as u128);
+    cards[..handles.len()].copy_from_slice(handles);
+    DeckState {
+        hand: Pubkey::default(),
+        cards,
+        deal_index: 5,
+        is_shuffled: true,
+        bump: 0,
+        seed_commitment: [0; 32],
+        burn_cards: false,
+    }
+}
@@ -147,7 +147,7 @@ describe("Edge Cases & Security", () => {
       const [seatPDA] = getSeatPDA(this.tablePDA, seatIndex);
 
       await program.methods
-        .joinTable(seatIndex, new anchor.BN(buyIn))
+        .joinTable(seatIndex, new anchor.BN(buyIn), [])
         .accounts({
           player: keypair.publicKey,
           table: this.tablePDA,
@@ -254,6 +254,8 @@ describe("Edge Cases & Security", () => {
           table: this.tablePDA,
           handState: this.handPDA!,
           vault: this.vaultPDA,
+          handHistory: null,
+          tableStats: null,
         })
         .remainingAccounts(remainingAccounts)
         .signers([this.authority])
@@ -262,7 +264,7 @@ describe("Edge Cases & Security", () => {
       return this;
     }
 
-    async leaveTable(playerIndex: number) {
+    async leaveTable(playerIndex: number, handState: PublicKey | null = null) {
       const player = this.players[playerIndex];
 
       await program.methods
@@ -272,6 +274,27 @@ describe("Edge Cases & Security", () => {
           table: this.tablePDA,
           playerSeat: player.seatPDA,
           vault: this.vaultPDA,
+          handState,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player.keypair])
+        .rpc();
+
+      return this;
+    }
+
+    async foldAndLeave(playerIndex: number) {
+      const player = this.players[playerIndex];
+
+      await program.methods
+        .foldAndLeave()
+        .accounts({
+          player: player.keypair.publicKey,
+          table: this.tablePDA,
+          playerSeat: player.seatPDA,
+          vault: this.vaultPDA,
+          handState: this.handPDA,
+          deckState: this.deckPDA,
           systemProgram: SystemProgram.programId,
         })
         .signers([player.keypair])
@@ -569,6 +592,60 @@ describe("Edge Cases & Security", () => {
         expect(err.error.errorCode.code).to.equal("RaiseTooSmall");
       }
     });
+
+    it("should reject a zero-amount raise", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, 5 * LAMPORTS_PER_SOL);
+      await game.addPlayer(1, 5 * LAMPORTS_PER_SOL);
+      await game.startHand();
+      await game.dealCards();
+
+      const before = await game.getHandState();
+      try {
+        await game.actCurrentPlayer({ raise: { amount: new anchor.BN(0) } });
+        expect.fail("Should have thrown InvalidAction");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAction");
+      }
+
+      // Nothing moved: same seat on the action, same pot
+      const after = await game.getHandState();
+      expect(after.actionOn).to.equal(before.actionOn);
+      expect(after.pot.toNumber()).to.equal(before.pot.toNumber());
+    });
+
+    it("should treat an oversized raise from a short stack as a short all-in", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // First to act raises to just under the other player's stack
+      const hand = await game.getHandState();
+      const raiserIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const shortIdx = raiserIdx === 0 ? 1 : 0;
+      const raiser = await game.getSeat(raiserIdx);
+      const raiseTo = MIN_BUY_IN - SMALL_BLIND;
+      await game.playerAction(raiserIdx, {
+        raise: { amount: new anchor.BN(raiseTo - raiser.currentBet.toNumber()) },
+      });
+      const fullRaise = raiseTo - BIG_BLIND;
+      expect((await game.getHandState()).minRaise.toNumber()).to.equal(fullRaise);
+
+      // Asking to raise far more than the stack only puts the stack in:
+      // SMALL_BLIND over the bet, short of a full raise, so nothing reopens
+      await game.playerAction(shortIdx, { raise: { amount: new anchor.BN("18446744073709551615") } });
+
+      const after = await game.getHandState();
+      expect(after.currentBet.toNumber()).to.equal(MIN_BUY_IN);
+      expect(after.minRaise.toNumber()).to.equal(fullRaise);
+      const shortSeat = await game.getSeat(shortIdx);
+      expect(shortSeat.chips.toNumber()).to.equal(0);
+      expect(shortSeat.status).to.deep.equal({ allIn: {} });
+    });
   });
 
   // ==================== LEAVING/JOINING DURING HAND ====================
@@ -590,6 +667,177 @@ describe("Edge Cases & Security", () => {
         expect(err.error.errorCode.code).to.equal("CannotLeaveDuringHand");
       }
     });
+
+    it("refunds the blind to the last player when the hand is abandoned preflop", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // Heads-up: the small blind folds, leaving the big blind alone in the hand
+      await game.actCurrentPlayer({ fold: {} });
+      const hand = await game.getHandState();
+      expect(hand.communityRevealed).to.equal(0);
+
+      const stayerIdx = game.players.findIndex(p => (hand.activePlayers & (1 << p.seatIndex)) !== 0);
+      const stayer = game.players[stayerIdx];
+      const seat = await program.account.playerSeat.fetch(stayer.seatPDA);
+      const expectedRefund = seat.chips.toNumber() + hand.pot.toNumber() + hand.uncalledBet.toNumber();
+      expect(expectedRefund).to.equal(MIN_BUY_IN + SMALL_BLIND);
+
+      const balanceBefore = await provider.connection.getBalance(stayer.keypair.publicKey);
+      await game.leaveTable(stayerIdx, game.handPDA!);
+      const balanceAfter = await provider.connection.getBalance(stayer.keypair.publicKey);
+
+      // Stack plus blind come back, along with the seat account's rent
+      expect(balanceAfter - balanceBefore).to.be.greaterThanOrEqual(expectedRefund);
+
+      const table = await game.getTable();
+      expect(table.status).to.deep.equal({ waiting: {} });
+      expect(table.occupiedSeats & (1 << stayer.seatIndex)).to.equal(0);
+
+      const settled = await game.getHandState();
+      expect(settled.pot.toNumber()).to.equal(0);
+      expect(settled.activeCount).to.equal(0);
+    });
+
+    it("fold_and_leave folds out of turn and cashes out mid-hand", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const before = await game.getHandState();
+      const leaverIdx = game.players.findIndex(p => p.seatIndex !== before.actionOn);
+      const leaver = game.players[leaverIdx];
+      const stack = (await program.account.playerSeat.fetch(leaver.seatPDA)).chips.toNumber();
+
+      const balanceBefore = await provider.connection.getBalance(leaver.keypair.publicKey);
+      await game.foldAndLeave(leaverIdx);
+      const balanceAfter = await provider.connection.getBalance(leaver.keypair.publicKey);
+      expect(balanceAfter - balanceBefore).to.be.greaterThanOrEqual(stack);
+
+      // Out of the hand and off the table, but the action stays where it was
+      const hand = await game.getHandState();
+      expect(hand.activeCount).to.equal(2);
+      expect(hand.activePlayers & (1 << leaver.seatIndex)).to.equal(0);
+      expect(hand.actionOn).to.equal(before.actionOn);
+      expect(await provider.connection.getAccountInfo(leaver.seatPDA)).to.be.null;
+
+      const table = await game.getTable();
+      expect(table.status).to.deep.equal({ playing: {} });
+      expect(table.occupiedSeats & (1 << leaver.seatIndex)).to.equal(0);
+
+      // The remaining players finish the hand
+      game.players.splice(leaverIdx, 1);
+      await game.actCurrentPlayer({ fold: {} });
+      await game.showdown();
+      expect((await game.getTable()).status).to.deep.equal({ waiting: {} });
+    });
+
+    it("fold_and_leave on the action passes the action on", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const before = await game.getHandState();
+      const leaverIdx = game.players.findIndex(p => p.seatIndex === before.actionOn);
+      await game.foldAndLeave(leaverIdx);
+
+      const hand = await game.getHandState();
+      expect(hand.activeCount).to.equal(2);
+      expect(hand.actionOn).to.not.equal(before.actionOn);
+      expect(hand.activePlayers & (1 << hand.actionOn)).to.not.equal(0);
+    });
+
+    it("fold_and_leave between hands cashes out without hand accounts", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+
+      const balanceBefore = await provider.connection.getBalance(game.players[1].keypair.publicKey);
+      await game.foldAndLeave(1);
+      const balanceAfter = await provider.connection.getBalance(game.players[1].keypair.publicKey);
+      expect(balanceAfter - balanceBefore).to.be.greaterThanOrEqual(MIN_BUY_IN);
+
+      const table = await game.getTable();
+      expect(table.currentPlayers).to.equal(1);
+      expect(table.occupiedSeats).to.equal(1);
+    });
+
+    it("cancels the hand and refunds bets when everyone but one player leaves preflop", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // The big blind stays; the other two walk away without acting
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const leavers = game.players.filter(p => p.seatIndex !== bbPos);
+      const stayer = game.players.find(p => p.seatIndex === bbPos)!;
+
+      // The first one out forfeits whatever they had in - the hand could still be played
+      const firstBet = (await program.account.playerSeat.fetch(leavers[0].seatPDA)).totalBetThisHand.toNumber();
+      await game.foldAndLeave(game.players.indexOf(leavers[0]));
+
+      // The last one out gets their own bet back with their stack
+      const last = leavers[1];
+      const lastSeat = await program.account.playerSeat.fetch(last.seatPDA);
+      const expected = lastSeat.chips.toNumber() + lastSeat.totalBetThisHand.toNumber();
+      const balanceBefore = await provider.connection.getBalance(last.keypair.publicKey);
+      await game.foldAndLeave(game.players.indexOf(last));
+      const balanceAfter = await provider.connection.getBalance(last.keypair.publicKey);
+      expect(balanceAfter - balanceBefore).to.be.greaterThanOrEqual(expected);
+
+      const hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ settled: {} });
+      expect(hand.activeCount).to.equal(1);
+
+      // Only the big blind's own bet and the first leaver's forfeit are left to settle
+      const leftToSettle = hand.pot.toNumber() + hand.uncalledBet.toNumber();
+      expect(leftToSettle).to.equal(BIG_BLIND + firstBet);
+
+      game.players = [stayer];
+      await game.showdown();
+      const stayerSeat = await program.account.playerSeat.fetch(stayer.seatPDA);
+      expect(stayerSeat.chips.toNumber()).to.equal(MIN_BUY_IN - BIG_BLIND + leftToSettle);
+    });
+
+    it("still blocks a folded player from leaving an abandoned hand", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      await game.actCurrentPlayer({ fold: {} });
+
+      try {
+        await game.leaveTable(folderIdx, game.handPDA!);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CannotLeaveDuringHand");
+      }
+    });
   });
 
   // ==================== CONSECUTIVE HANDS ====================
@@ -649,6 +897,40 @@ describe("Edge Cases & Security", () => {
       expect(dealerPositions.filter(p => p === 0).length).to.equal(3);
       expect(dealerPositions.filter(p => p === 1).length).to.equal(3);
     });
+
+    it("places the button on an occupied seat after a mid-session seat change", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, 3 * LAMPORTS_PER_SOL);
+      await game.addPlayer(1, 3 * LAMPORTS_PER_SOL);
+      await game.addPlayer(2, 3 * LAMPORTS_PER_SOL);
+
+      await game.startHand();
+      const dealer1 = (await game.getTable()).dealerPosition;
+      expect(dealer1).to.equal(1);
+
+      await game.dealCards();
+      await game.actCurrentPlayer({ fold: {} });
+      await game.actCurrentPlayer({ fold: {} });
+      await game.showdown();
+
+      // The seat the button would move to empties and a new player sits further round
+      await game.leaveTable(2);
+      game.players.splice(2, 1);
+      await game.addPlayer(4, 3 * LAMPORTS_PER_SOL);
+
+      await game.startHand();
+      const table = await game.getTable();
+      expect(table.dealerPosition).to.equal(4);
+      expect(table.occupiedSeats & (1 << table.dealerPosition)).to.not.equal(0);
+
+      // Blinds follow the new button: SB seat 0, BB seat 1
+      await game.dealCards();
+      const seat0 = await game.getSeat(game.players.findIndex(p => p.seatIndex === 0));
+      const seat1 = await game.getSeat(game.players.findIndex(p => p.seatIndex === 1));
+      expect(seat0.currentBet.toNumber()).to.equal(SMALL_BLIND);
+      expect(seat1.currentBet.toNumber()).to.equal(BIG_BLIND);
+    });
   });
 
   // ==================== CHIP CONSERVATION ====================
@@ -729,6 +1011,69 @@ describe("Edge Cases & Security", () => {
     });
   });
 
+  // ==================== CLOSING SETTLED HANDS ====================
+
+  describe("Closing Settled Hands", () => {
+    async function closeHand(game: GameSetup, handNumber: number) {
+      const [handPDA] = getHandPDA(game.tablePDA, handNumber);
+      const [deckPDA] = getDeckPDA(game.tablePDA, handNumber);
+      await program.methods
+        .closeHand(new anchor.BN(handNumber))
+        .accounts({
+          caller: game.authority.publicKey,
+          table: game.tablePDA,
+          handState: handPDA,
+          deckState: deckPDA,
+          authority: game.authority.publicKey,
+        })
+        .signers([game.authority])
+        .rpc();
+    }
+
+    it("reclaims hand and deck rent after settlement, and only once", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // A live hand can't be closed
+      try {
+        await closeHand(game, game.handNumber);
+        expect.fail("Should have thrown HandInProgress");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HandInProgress");
+      }
+
+      await game.actCurrentPlayer({ fold: {} });
+      await game.showdown();
+
+      const handRent = await provider.connection.getBalance(game.handPDA!);
+      const deckRent = await provider.connection.getBalance(game.deckPDA!);
+      const before = await provider.connection.getBalance(game.authority.publicKey);
+
+      await closeHand(game, game.handNumber);
+
+      const after = await provider.connection.getBalance(game.authority.publicKey);
+      // Authority also paid the transaction fee
+      expect(after).to.be.greaterThan(before + handRent + deckRent - 10_000);
+      expect(await provider.connection.getAccountInfo(game.handPDA!)).to.be.null;
+      expect(await provider.connection.getAccountInfo(game.deckPDA!)).to.be.null;
+
+      try {
+        await closeHand(game, game.handNumber);
+        expect.fail("Second close should fail");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+      }
+
+      // The table plays on without the closed hand
+      await game.startHand();
+      await game.dealCards();
+    });
+  });
+
   // ==================== SECURITY TESTS ====================
 
   describe("Security", () => {
@@ -789,16 +1134,151 @@ describe("Edge Cases & Security", () => {
             table: game.tablePDA,
             handState: game.handPDA!,
             vault: game.vaultPDA,
+            handHistory: null,
+            tableStats: null,
           })
           .remainingAccounts(remainingAccounts)
           .signers([game.players[0].keypair])
           .rpc();
-        expect.fail("Should have thrown UnauthorizedAuthority");
+        expect.fail("Should have thrown ShowdownNotTimedOut");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("UnauthorizedAuthority");
+        expect(err.error.errorCode.code).to.equal("ShowdownNotTimedOut");
       }
     });
 
+    it("rejects a deal that repeats the SB seat in remaining_accounts", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const [sbSeatPDA] = getSeatPDA(game.tablePDA, sbPos);
+      const [bbSeatPDA] = getSeatPDA(game.tablePDA, bbPos);
+
+      // Every seat, blinds included
+      const allSeats = game.players.map(p => ({
+        pubkey: p.seatPDA,
+        isSigner: false,
+        isWritable: true,
+      }));
+
+      try {
+        await program.methods
+          .dealCards()
+          .accounts({
+            authority: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            sbSeat: sbSeatPDA,
+            bbSeat: bbSeatPDA,
+          })
+          .remainingAccounts(allSeats)
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown DuplicateAccount");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DuplicateAccount");
+      }
+
+      // Nothing was dealt - the hand is still waiting on a correct deal
+      const hand = await program.account.handState.fetch(game.handPDA!);
+      expect(hand.phase).to.deep.equal({ dealing: {} });
+      expect(hand.pot.toNumber()).to.equal(0);
+    });
+
+    it("rejects showdown that leaves out an active player's seat", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      await game.actCurrentPlayer({ allIn: {} });
+      await game.actCurrentPlayer({ allIn: {} });
+      await game.actCurrentPlayer({ allIn: {} });
+
+      // Omit one of the three players still in the hand
+      const partialSeats = game.players.slice(1).map(p => ({
+        pubkey: p.seatPDA,
+        isSigner: false,
+        isWritable: true,
+      }));
+
+      try {
+        await program.methods
+          .showdown()
+          .accounts({
+            authority: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            vault: game.vaultPDA,
+            handHistory: null,
+            tableStats: null,
+          })
+          .remainingAccounts(partialSeats)
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown MissingSeatAccounts");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingSeatAccounts");
+      }
+
+      // The hand is untouched and settles normally with every seat
+      const hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ showdown: {} });
+      await game.showdown();
+    });
+
+    it("rejects an action sent against the previous hand", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // Finish hand 1 and move the table on to hand 2
+      await game.actCurrentPlayer({ fold: {} });
+      await game.showdown();
+      const staleHandPDA = game.handPDA!;
+      const staleDeckPDA = game.deckPDA!;
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const actor = game.getPlayerBySeat(hand.actionOn)!;
+
+      try {
+        await program.methods
+          .playerAction({ call: {} })
+          .accounts({
+            player: actor.keypair.publicKey,
+            table: game.tablePDA,
+            handState: staleHandPDA,
+            deckState: staleDeckPDA,
+            playerSeat: actor.seatPDA,
+          })
+          .signers([actor.keypair])
+          .rpc();
+        expect.fail("Should have rejected the stale hand");
+      } catch (err: any) {
+        expect(["ConstraintSeeds", "StaleHand"]).to.include(err.error.errorCode.code);
+      }
+
+      // The current hand is untouched
+      const after = await game.getHandState();
+      expect(after.actionOn).to.equal(hand.actionOn);
+    });
+
     it("prevents player from acting for another player", async () => {
       const game = new GameSetup();
       await game.createTable();
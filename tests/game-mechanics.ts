@@ -6,8 +6,10 @@ import {
   LAMPORTS_PER_SOL,
   PublicKey,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 /**
  * Comprehensive game mechanics tests for HiddenHand poker
@@ -62,6 +64,22 @@ describe("Game Mechanics", () => {
     );
   }
 
+  function getHistoryPDA(table: PublicKey, handNumber: number): [PublicKey, number] {
+    const handNumberBuffer = Buffer.alloc(8);
+    handNumberBuffer.writeBigUInt64LE(BigInt(handNumber));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("history"), table.toBuffer(), handNumberBuffer],
+      program.programId
+    );
+  }
+
+  function getStatsPDA(table: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("stats"), table.toBuffer()],
+      program.programId
+    );
+  }
+
   function getDeckPDA(table: PublicKey, handNumber: number): [PublicKey, number] {
     const handNumberBuffer = Buffer.alloc(8);
     handNumberBuffer.writeBigUInt64LE(BigInt(handNumber));
@@ -144,12 +162,48 @@ describe("Game Mechanics", () => {
       return this;
     }
 
-    async addPlayer(seatIndex: number, buyIn: number = MIN_BUY_IN) {
-      const keypair = await createFundedKeypair();
+    // Unspecified TableConfig fields fall back to the defaults
+    async configure(config: object) {
+      const defaults = {
+        gameType: { texasHoldem: {} },
+        rotatingOperator: false,
+        recordHistory: false,
+        maxAddons: 0,
+        addonAfterHand: new anchor.BN(0),
+        minPlayersToStart: 2,
+        timeBankSecs: 0,
+        useBurnCards: false,
+        bettingMode: { noLimit: {} },
+        maxRaisesPerStreet: 0,
+        trackStats: false,
+        allowlistRoot: Array(32).fill(0),
+        orderedReveal: false,
+        capStackPerHand: false,
+      };
+
+      await program.methods
+        .configureTable({ ...defaults, ...config })
+        .accounts({
+          authority: this.authority.publicKey,
+          table: this.tablePDA,
+        })
+        .signers([this.authority])
+        .rpc();
+
+      return this;
+    }
+
+    async addPlayer(
+      seatIndex: number,
+      buyIn: number = MIN_BUY_IN,
+      keypair?: Keypair,
+      allowlistProof: number[][] = []
+    ) {
+      keypair = keypair || await createFundedKeypair();
       const [seatPDA] = getSeatPDA(this.tablePDA, seatIndex);
 
       await program.methods
-        .joinTable(seatIndex, new anchor.BN(buyIn))
+        .joinTable(seatIndex, new anchor.BN(buyIn), allowlistProof)
         .accounts({
           player: keypair.publicKey,
           table: this.tablePDA,
@@ -164,7 +218,7 @@ describe("Game Mechanics", () => {
       return this;
     }
 
-    async startHand() {
+    async startHand(remainingAccounts: anchor.web3.AccountMeta[] = []) {
       const table = await program.account.table.fetch(this.tablePDA);
       this.handNumber = table.handNumber.toNumber() + 1;
       [this.handPDA] = getHandPDA(this.tablePDA, this.handNumber);
@@ -179,12 +233,42 @@ describe("Game Mechanics", () => {
           deckState: this.deckPDA,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(remainingAccounts)
+        .signers([this.authority])
+        .rpc();
+
+      return this;
+    }
+
+    async startBombPot(ante: number) {
+      const table = await program.account.table.fetch(this.tablePDA);
+      this.handNumber = table.handNumber.toNumber() + 1;
+      [this.handPDA] = getHandPDA(this.tablePDA, this.handNumber);
+      [this.deckPDA] = getDeckPDA(this.tablePDA, this.handNumber);
+
+      await program.methods
+        .startBombPot(new anchor.BN(ante))
+        .accounts({
+          authority: this.authority.publicKey,
+          table: this.tablePDA,
+          handState: this.handPDA,
+          deckState: this.deckPDA,
+          systemProgram: SystemProgram.programId,
+        })
         .signers([this.authority])
         .rpc();
 
       return this;
     }
 
+    // Seat the button moves to when the next hand starts
+    async nextDealerSeat() {
+      const table = await program.account.table.fetch(this.tablePDA);
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const seatIndex = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      return this.getPlayerBySeat(seatIndex)!;
+    }
+
     async dealCards() {
       const table = await program.account.table.fetch(this.tablePDA);
       const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
@@ -237,9 +321,24 @@ describe("Game Mechanics", () => {
       return this;
     }
 
-    async showdown() {
-      const remainingAccounts = this.players.map(p => ({
-        pubkey: p.seatPDA,
+    // Call the blinds and check every street until the hand reaches showdown
+    async checkDown() {
+      for (;;) {
+        const hand = await this.getHandState();
+        if ("showdown" in hand.phase) {
+          return this;
+        }
+        const actionIdx = this.players.findIndex(p => p.seatIndex === hand.actionOn);
+        const seat = await this.getSeat(actionIdx);
+        const toCall = hand.currentBet.toNumber() - seat.currentBet.toNumber();
+        await this.playerAction(actionIdx, toCall > 0 ? { call: {} } : { check: {} });
+      }
+    }
+
+    // Wallets follow the seats, for stacks trimmed on cap_stack_per_hand tables
+    async showdown(tableStats: PublicKey | null = null, wallets: PublicKey[] = []) {
+      const remainingAccounts = [...this.players.map(p => p.seatPDA), ...wallets].map(pubkey => ({
+        pubkey,
         isSigner: false,
         isWritable: true,
       }));
@@ -251,6 +350,8 @@ describe("Game Mechanics", () => {
           table: this.tablePDA,
           handState: this.handPDA!,
           vault: this.vaultPDA,
+          handHistory: null,
+          tableStats,
         })
         .remainingAccounts(remainingAccounts)
         .signers([this.authority])
@@ -304,6 +405,70 @@ describe("Game Mechanics", () => {
       expect(handAfter.activeCount).to.equal(1);
     });
 
+    it("counts hands played and won on each seat", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const winnerIdx = folderIdx === 0 ? 1 : 0;
+      await game.playerAction(folderIdx, { fold: {} });
+      await game.showdown();
+
+      const winner = await game.getSeat(winnerIdx);
+      const folder = await game.getSeat(folderIdx);
+      expect(winner.handsPlayed).to.equal(1);
+      expect(winner.handsWon).to.equal(1);
+      expect(folder.handsPlayed).to.equal(1);
+      expect(folder.handsWon).to.equal(0);
+    });
+
+    it("pays out a settled hand only once", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const winnerIdx = folderIdx === 0 ? 1 : 0;
+      await game.playerAction(folderIdx, { fold: {} });
+      await game.showdown();
+
+      expect((await game.getHandState()).payoutsDone).to.equal(true);
+      const chipsAfterPayout = (await game.getSeat(winnerIdx)).chips.toNumber();
+      expect(chipsAfterPayout).to.equal(MIN_BUY_IN + SMALL_BLIND);
+
+      try {
+        // Seats in a different order so this isn't a byte-identical resend
+        await program.methods
+          .showdown()
+          .accounts({
+            authority: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            vault: game.vaultPDA,
+            handHistory: null,
+            tableStats: null,
+          })
+          .remainingAccounts(
+            [...game.players].reverse().map(p => ({ pubkey: p.seatPDA, isSigner: false, isWritable: true }))
+          )
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown AlreadySettled");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadySettled");
+      }
+      expect((await game.getSeat(winnerIdx)).chips.toNumber()).to.equal(chipsAfterPayout);
+    });
+
     it("completes a full hand with betting to showdown", async () => {
       const game = new GameSetup();
       await game.createTable();
@@ -478,6 +643,157 @@ describe("Game Mechanics", () => {
       hand = await game.getHandState();
       expect(hand.phase).to.deep.equal({ settled: {} });
     });
+
+    it("gives the big blind the option when everyone limps", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const actWithCurrentPlayer = async (action: object) => {
+        const hand = await game.getHandState();
+        const idx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        await game.playerAction(idx, action);
+      };
+
+      // UTG limps, SB completes
+      await actWithCurrentPlayer({ call: {} });
+      await actWithCurrentPlayer({ call: {} });
+
+      // Nobody raised, but the round stays open for the BB
+      let hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ preFlop: {} });
+      expect(hand.awaitingCommunityReveal).to.equal(false);
+      expect(hand.actionOn).to.equal(bbPos);
+
+      // BB uses the option to raise - the limpers must act again
+      await actWithCurrentPlayer({ raise: { amount: new anchor.BN(BIG_BLIND) } });
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ preFlop: {} });
+      expect(hand.actionOn).to.not.equal(bbPos);
+      expect(hand.currentBet.toNumber()).to.equal(BIG_BLIND * 2);
+    });
+
+    it("closes preflop betting when the big blind checks its option", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const actWithCurrentPlayer = async (action: object) => {
+        const hand = await game.getHandState();
+        const idx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        await game.playerAction(idx, action);
+      };
+
+      await actWithCurrentPlayer({ call: {} });
+      await actWithCurrentPlayer({ call: {} });
+
+      let hand = await game.getHandState();
+      expect(hand.actionOn).to.equal(bbPos);
+      expect(hand.awaitingCommunityReveal).to.equal(false);
+
+      await actWithCurrentPlayer({ check: {} });
+
+      // Everyone has matched the big blind - the round is over, and the
+      // plaintext flop is revealed with the check instead of a reveal_community
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ flop: {} });
+      expect(hand.awaitingCommunityReveal).to.equal(false);
+      expect(hand.communityRevealed).to.equal(3);
+      expect(hand.pot.toNumber()).to.equal(BIG_BLIND * 3);
+    });
+  });
+
+  describe("Misdeal", () => {
+    it("rejects a partial deal so it can be retried with every seat", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const [sbSeatPDA] = getSeatPDA(game.tablePDA, sbPos);
+      const [bbSeatPDA] = getSeatPDA(game.tablePDA, bbPos);
+
+      // Deal without the third seat in remaining_accounts
+      try {
+        await program.methods
+          .dealCards()
+          .accounts({
+            authority: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            sbSeat: sbSeatPDA,
+            bbSeat: bbSeatPDA,
+          })
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown MissingSeatAccounts");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingSeatAccounts");
+      }
+
+      // Nothing happened: the hand is still waiting to be dealt, no blinds taken
+      const hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ dealing: {} });
+      expect(hand.pot.toNumber()).to.equal(0);
+      for (let i = 0; i < game.players.length; i++) {
+        const seat = await game.getSeat(i);
+        expect(seat.chips.toNumber()).to.equal(MIN_BUY_IN);
+      }
+
+      await game.dealCards();
+      expect((await game.getHandState()).phase).to.deep.equal({ preFlop: {} });
+    });
+  });
+
+  describe("Reveal Status View", () => {
+    it("reports unrevealed seats as not yet muckable", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const status = await program.methods
+        .getRevealStatus()
+        .accounts({
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          playerSeat: game.players[0].seatPDA,
+        })
+        .view();
+
+      expect(status.seatIndex).to.equal(0);
+      expect(status.cardsRevealed).to.equal(false);
+      expect(status.revealedCard1).to.be.null;
+      expect(status.revealedCard2).to.be.null;
+      // Not at showdown yet, so nothing is owed
+      expect(status.awaitingReveal).to.equal(false);
+      expect(status.canBeMucked).to.equal(false);
+    });
   });
 
   describe("Side Pot Scenarios", () => {
@@ -594,6 +910,76 @@ describe("Game Mechanics", () => {
   });
 
   describe("Phase Transitions", () => {
+    it("rejects revealing the flop a second time", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      let hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      await game.playerAction(actionIdx, { call: {} });
+      await game.playerAction(actionIdx === 0 ? 1 : 0, { check: {} });
+
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ flop: {} });
+      const board = hand.communityCards.slice(0, 3);
+
+      // Retrying the flop reveal must not touch the board
+      try {
+        await program.methods
+          .revealCommunity(Buffer.from(board))
+          .accounts({
+            caller: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown CommunityAlreadyRevealed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommunityAlreadyRevealed");
+      }
+
+      const after = await game.getHandState();
+      expect(after.communityRevealed).to.equal(3);
+      expect(after.communityCards).to.deep.equal(hand.communityCards);
+    });
+
+    it("rejects a community reveal with more cards than the board holds", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const before = await game.getHandState();
+      try {
+        await program.methods
+          .revealCommunity(Buffer.from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]))
+          .accounts({
+            caller: game.authority.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .signers([game.authority])
+          .rpc();
+        expect.fail("Should have thrown InvalidCommunityCards");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidCommunityCards");
+      }
+
+      const after = await game.getHandState();
+      expect(after.communityRevealed).to.equal(before.communityRevealed);
+    });
+
     it("transitions through all phases correctly", async () => {
       const game = new GameSetup();
       await game.createTable();
@@ -653,44 +1039,968 @@ describe("Game Mechanics", () => {
       hand = await game.getHandState();
       expect(hand.phase).to.deep.equal({ showdown: {} });
     });
-  });
 
-  describe("Betting Mechanics", () => {
-    it("enforces minimum raise", async () => {
+    it("plays a board-less variant to showdown on hole cards alone", async () => {
       const game = new GameSetup();
       await game.createTable();
+      await game.configure({ gameType: { noBoard: {} } });
       await game.addPlayer(0, MIN_BUY_IN);
       await game.addPlayer(1, MIN_BUY_IN);
       await game.startHand();
       await game.dealCards();
 
-      const hand = await game.getHandState();
-      const actionOn = hand.actionOn;
-      const actionIdx = game.players.findIndex(p => p.seatIndex === actionOn);
+      let hand = await game.getHandState();
+      expect(hand.communityCardCount).to.equal(0);
+
+      // One betting round, then straight to showdown - no board to reveal
+      await game.checkDown();
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ showdown: {} });
+      expect(hand.communityRevealed).to.equal(0);
+      expect(hand.awaitingCommunityReveal).to.be.false;
+      expect(hand.communityCards.every((card: number) => card === 255)).to.be.true;
+
+      // The hole cards decide the pot and every chip is paid out
+      await game.showdown();
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ settled: {} });
+      expect(hand.pot.toNumber()).to.equal(0);
+      const stacks = (await game.getSeat(0)).chips.toNumber() + (await game.getSeat(1)).chips.toNumber();
+      expect(stacks).to.equal(2 * MIN_BUY_IN);
+    });
+  });
+
+  describe("Minimum Players To Start", () => {
+    it("won't start a 3-player table with only 2 seated", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ minPlayersToStart: 3 });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
 
-      // Try to raise less than min raise (should fail)
       try {
-        await game.playerAction(actionIdx, { raise: { amount: new anchor.BN(BIG_BLIND / 2) } });
-        expect.fail("Should have thrown error");
+        await game.startHand();
+        expect.fail("Should have thrown NotEnoughPlayers");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("RaiseTooSmall");
+        expect(err.error.errorCode.code).to.equal("NotEnoughPlayers");
       }
+
+      await game.addPlayer(2, MIN_BUY_IN);
+      await game.startHand();
+      const table = await game.getTable();
+      expect(table.status).to.deep.equal({ playing: {} });
     });
 
-    it("allows valid raise", async () => {
+    it("rejects a threshold outside 2..=max_players", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+
+      for (const minPlayersToStart of [1, MAX_PLAYERS + 1]) {
+        try {
+          await game.configure({ minPlayersToStart });
+          expect.fail("Should have thrown InvalidMinPlayersToStart");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("InvalidMinPlayersToStart");
+        }
+      }
+    });
+  });
+
+  describe("Stakes Changes", () => {
+    const updateBlinds = (game: GameSetup, sb: number, bb: number, minBuyIn: number, maxBuyIn: number) =>
+      program.methods
+        .updateBlinds(new anchor.BN(sb), new anchor.BN(bb), new anchor.BN(minBuyIn), new anchor.BN(maxBuyIn))
+        .accounts({
+          authority: game.authority.publicKey,
+          table: game.tablePDA,
+        })
+        .signers([game.authority])
+        .rpc();
+
+    it("raises the stakes between hands", async () => {
       const game = new GameSetup();
       await game.createTable();
       await game.addPlayer(0, MIN_BUY_IN);
       await game.addPlayer(1, MIN_BUY_IN);
+
+      await updateBlinds(game, 2 * SMALL_BLIND, 2 * BIG_BLIND, MIN_BUY_IN, MAX_BUY_IN);
+
+      const table = await game.getTable();
+      expect(table.smallBlind.toNumber()).to.equal(2 * SMALL_BLIND);
+      expect(table.bigBlind.toNumber()).to.equal(2 * BIG_BLIND);
+
+      // The next hand posts the new blinds
       await game.startHand();
       await game.dealCards();
-
       const hand = await game.getHandState();
-      const actionOn = hand.actionOn;
-      const actionIdx = game.players.findIndex(p => p.seatIndex === actionOn);
-
-      const seatBefore = await game.getSeat(actionIdx);
-      const alreadyBet = seatBefore.currentBet.toNumber();
+      expect(hand.pot.toNumber()).to.equal(2 * SMALL_BLIND + 2 * BIG_BLIND);
+    });
+
+    it("rejects stakes changes mid-hand and invalid stakes", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+
+      try {
+        await updateBlinds(game, BIG_BLIND, SMALL_BLIND, MIN_BUY_IN, MAX_BUY_IN);
+        expect.fail("Should have thrown BigBlindBelowSmallBlind");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BigBlindBelowSmallBlind");
+      }
+
+      await game.startHand();
+      try {
+        await updateBlinds(game, 2 * SMALL_BLIND, 2 * BIG_BLIND, MIN_BUY_IN, MAX_BUY_IN);
+        expect.fail("Should have thrown HandInProgress");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HandInProgress");
+      }
+      expect((await game.getTable()).bigBlind.toNumber()).to.equal(BIG_BLIND);
+    });
+  });
+
+  describe("Pausing", () => {
+    const setPaused = (game: GameSetup, paused: boolean) => {
+      const method = paused ? program.methods.pauseTable() : program.methods.resumeTable();
+      return method
+        .accounts({
+          authority: game.authority.publicKey,
+          table: game.tablePDA,
+        })
+        .signers([game.authority])
+        .rpc();
+    };
+
+    it("blocks start_hand while paused and resumes play", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+
+      await setPaused(game, true);
+      expect((await game.getTable()).status).to.deep.equal({ paused: {} });
+
+      try {
+        await game.startHand();
+        expect.fail("Should have thrown TablePaused");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TablePaused");
+      }
+
+      // Seats and chips are untouched
+      const table = await game.getTable();
+      expect(table.currentPlayers).to.equal(2);
+      expect((await game.getSeat(0)).chips.toNumber()).to.equal(MIN_BUY_IN);
+
+      await setPaused(game, false);
+      await game.startHand();
+      expect((await game.getTable()).status).to.deep.equal({ playing: {} });
+    });
+  });
+
+  describe("Settle And Start", () => {
+    const settleAndStart = async (game: GameSetup) => {
+      const nextHandNumber = game.handNumber + 1;
+      const [nextHandPDA] = getHandPDA(game.tablePDA, nextHandNumber);
+      const [nextDeckPDA] = getDeckPDA(game.tablePDA, nextHandNumber);
+
+      await program.methods
+        .settleAndStart()
+        .accounts({
+          authority: game.authority.publicKey,
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          vault: game.vaultPDA,
+          handHistory: null,
+          tableStats: null,
+          nextHandState: nextHandPDA,
+          nextDeckState: nextDeckPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(game.players.map(p => ({
+          pubkey: p.seatPDA,
+          isSigner: false,
+          isWritable: true,
+        })))
+        .signers([game.authority])
+        .rpc();
+
+      const settledHandPDA = game.handPDA!;
+      game.handNumber = nextHandNumber;
+      game.handPDA = nextHandPDA;
+      game.deckPDA = nextDeckPDA;
+      return settledHandPDA;
+    };
+
+    it("settles a folded-out hand and deals the next one back to back", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      let hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      await game.playerAction(folderIdx, { fold: {} });
+
+      const dealerBefore = (await game.getTable()).dealerPosition;
+      const settledHandPDA = await settleAndStart(game);
+
+      // The first hand is paid out and left open for verify_fairness / close_hand
+      const settled = await program.account.handState.fetch(settledHandPDA);
+      expect(settled.phase).to.deep.equal({ settled: {} });
+      expect(settled.payoutsDone).to.equal(true);
+
+      // ...and the next hand is already under way with the button moved
+      const table = await game.getTable();
+      expect(table.handNumber.toNumber()).to.equal(game.handNumber);
+      expect(table.status).to.deep.equal({ playing: {} });
+      expect(table.dealerPosition).to.not.equal(dealerBefore);
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ dealing: {} });
+
+      // Chips are conserved across the settlement
+      const seats = await Promise.all([game.getSeat(0), game.getSeat(1)]);
+      expect(seats.reduce((sum, seat) => sum + seat.chips.toNumber(), 0)).to.equal(2 * MIN_BUY_IN);
+
+      await game.dealCards();
+      expect((await game.getHandState()).phase).to.deep.equal({ preFlop: {} });
+    });
+
+    it("refuses to settle while players still have the action", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const handNumber = game.handNumber;
+      try {
+        await settleAndStart(game);
+        expect.fail("Should have thrown InvalidPhase");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidPhase");
+      }
+
+      const table = await game.getTable();
+      expect(table.handNumber.toNumber()).to.equal(handNumber);
+      expect((await game.getHandState()).phase).to.deep.equal({ preFlop: {} });
+    });
+  });
+
+  describe("Add-ons", () => {
+    const addOn = (game: GameSetup, playerIndex: number, amount: number) => {
+      const player = game.players[playerIndex];
+      return program.methods
+        .addOn(new anchor.BN(amount))
+        .accounts({
+          player: player.keypair.publicKey,
+          table: game.tablePDA,
+          playerSeat: player.seatPDA,
+          vault: game.vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player.keypair])
+        .rpc();
+    };
+
+    it("adds chips to the vault up to the per-seat cap", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ maxAddons: 1 });
+      await game.addPlayer(0, MIN_BUY_IN);
+
+      const vaultBefore = await provider.connection.getBalance(game.vaultPDA);
+      await addOn(game, 0, MIN_BUY_IN);
+
+      const vaultAfter = await provider.connection.getBalance(game.vaultPDA);
+      expect(vaultAfter - vaultBefore).to.equal(MIN_BUY_IN);
+
+      const seat = await game.getSeat(0);
+      expect(seat.chips.toNumber()).to.equal(2 * MIN_BUY_IN);
+      expect(seat.addonCount).to.equal(1);
+
+      try {
+        await addOn(game, 0, MIN_BUY_IN);
+        expect.fail("Should have thrown AddOnLimitReached");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AddOnLimitReached");
+      }
+      expect(await provider.connection.getBalance(game.vaultPDA)).to.equal(vaultAfter);
+    });
+
+    it("stays closed until the add-on break", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ maxAddons: 1, addonAfterHand: new anchor.BN(5) });
+      await game.addPlayer(0, MIN_BUY_IN);
+
+      try {
+        await addOn(game, 0, MIN_BUY_IN);
+        expect.fail("Should have thrown AddOnNotAvailable");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AddOnNotAvailable");
+      }
+    });
+  });
+
+  describe("Private Tables", () => {
+    // Same Merkle scheme as the program: SHA-256 leaves, sorted-pair parents
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+    const leaf = (key: PublicKey) => sha256(key.toBuffer());
+    const node = (a: Buffer, b: Buffer) => (Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a));
+    const asProof = (nodes: Buffer[]) => nodes.map(n => Array.from(n));
+
+    it("seats allowlisted players and rejects outsiders", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+
+      const guests = [await createFundedKeypair(), await createFundedKeypair()];
+      const [leafA, leafB] = guests.map(g => leaf(g.publicKey));
+      const root = node(leafA, leafB);
+      await game.configure({ allowlistRoot: Array.from(root) });
+
+      await game.addPlayer(0, MIN_BUY_IN, guests[0], asProof([leafB]));
+      await game.addPlayer(1, MIN_BUY_IN, guests[1], asProof([leafA]));
+      expect((await game.getTable()).currentPlayers).to.equal(2);
+
+      // An outsider can't borrow a guest's proof
+      const outsider = await createFundedKeypair();
+      try {
+        await game.addPlayer(2, MIN_BUY_IN, outsider, asProof([leafB]));
+        expect.fail("Should have thrown NotAllowlisted");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotAllowlisted");
+      }
+      expect((await game.getTable()).currentPlayers).to.equal(2);
+    });
+  });
+
+  describe("Hand History", () => {
+    it("records the settled hand when the table opts in", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ gameType: { texasHoldem: {} }, rotatingOperator: false, recordHistory: true });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // First to act folds, the other seat takes the blinds
+      const hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const winnerIdx = folderIdx === 0 ? 1 : 0;
+      await game.playerAction(folderIdx, { fold: {} });
+
+      const remainingAccounts = game.players.map(p => ({
+        pubkey: p.seatPDA,
+        isSigner: false,
+        isWritable: true,
+      }));
+
+      // The record is required once the table opts in
+      try {
+        await game.showdown();
+        expect.fail("Should have thrown HandHistoryMismatch");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HandHistoryMismatch");
+      }
+
+      const [historyPDA] = getHistoryPDA(game.tablePDA, game.handNumber);
+      await program.methods
+        .showdown()
+        .accounts({
+          caller: game.authority.publicKey,
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          vault: game.vaultPDA,
+          handHistory: historyPDA,
+          tableStats: null,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([game.authority])
+        .rpc();
+
+      const history = await program.account.handHistory.fetch(historyPDA);
+      expect(history.table.toBase58()).to.equal(game.tablePDA.toBase58());
+      expect(history.handNumber.toNumber()).to.equal(game.handNumber);
+      // The big blind's uncalled half came back out of the pot at fold-out
+      expect(history.totalPot.toNumber()).to.equal(2 * SMALL_BLIND);
+      expect(history.seatCount).to.equal(2);
+
+      const seats = history.seats.slice(0, history.seatCount);
+      const folder = seats.find(s => s.seatIndex === game.players[folderIdx].seatIndex)!;
+      const winner = seats.find(s => s.seatIndex === game.players[winnerIdx].seatIndex)!;
+      expect(folder.folded).to.equal(true);
+      expect(folder.chipsWon.toNumber()).to.equal(0);
+      expect(winner.folded).to.equal(false);
+      expect(winner.chipsWon.toNumber()).to.equal(2 * SMALL_BLIND);
+      expect(folder.chipsBet.toNumber() + winner.chipsBet.toNumber()).to.equal(2 * SMALL_BLIND);
+      expect(winner.player.toBase58()).to.equal(game.players[winnerIdx].keypair.publicKey.toBase58());
+    });
+
+    it("keeps table stats across hands when tracking is on", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ trackStats: true });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      const [statsPDA] = getStatsPDA(game.tablePDA);
+
+      const actWithCurrentPlayer = async (action: object) => {
+        const hand = await game.getHandState();
+        const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        await game.playerAction(actionIdx, action);
+      };
+
+      // Hand 1: fold-out, the big blind's uncalled half comes back
+      await game.startHand();
+      await game.dealCards();
+      await actWithCurrentPlayer({ fold: {} });
+
+      try {
+        await game.showdown();
+        expect.fail("Should have thrown TableStatsMismatch");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TableStatsMismatch");
+      }
+      await game.showdown(statsPDA);
+
+      let stats = await program.account.tableStats.fetch(statsPDA);
+      expect(stats.table.toBase58()).to.equal(game.tablePDA.toBase58());
+      expect(stats.handsPlayed.toNumber()).to.equal(1);
+      expect(stats.biggestPot.toNumber()).to.equal(2 * SMALL_BLIND);
+      expect(stats.totalVolume.toNumber()).to.equal(2 * SMALL_BLIND);
+
+      // Hand 2: call, the big blind raises and takes a bigger pot uncalled
+      await game.startHand();
+      await game.dealCards();
+      await actWithCurrentPlayer({ call: {} });
+      await actWithCurrentPlayer({ raise: { amount: new anchor.BN(BIG_BLIND) } });
+      await actWithCurrentPlayer({ fold: {} });
+      await game.showdown(statsPDA);
+
+      stats = await program.account.tableStats.fetch(statsPDA);
+      expect(stats.handsPlayed.toNumber()).to.equal(2);
+      expect(stats.biggestPot.toNumber()).to.equal(2 * BIG_BLIND);
+      expect(stats.totalVolume.toNumber()).to.equal(2 * SMALL_BLIND + 2 * BIG_BLIND);
+      expect(stats.totalRaked.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Timeout Guards", () => {
+    it("blocks timeout_player while the acting player's time bank runs", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ timeBankSecs: 120 });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const before = await game.getHandState();
+      const acting = game.players.find(p => p.seatIndex === before.actionOn)!;
+      const useTimeBank = () =>
+        program.methods
+          .useTimeBank()
+          .accounts({
+            player: acting.keypair.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            playerSeat: acting.seatPDA,
+          })
+          .signers([acting.keypair])
+          .rpc();
+
+      await useTimeBank();
+
+      const after = await game.getHandState();
+      expect(after.lastActionTime.toNumber()).to.equal(before.lastActionTime.toNumber() + 120);
+      const seat = await program.account.playerSeat.fetch(acting.seatPDA);
+      expect(seat.timeBankUsed).to.equal(true);
+
+      // The extension pushes the 60s action clock out to 180s
+      const caller = game.players.find(p => p !== acting)!.keypair;
+      try {
+        await program.methods
+          .timeoutPlayer()
+          .accounts({
+            caller: caller.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            playerSeat: acting.seatPDA,
+          })
+          .signers([caller])
+          .rpc();
+        expect.fail("Should have thrown ActionNotTimedOut");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ActionNotTimedOut");
+      }
+
+      // Once per hand
+      try {
+        await useTimeBank();
+        expect.fail("Should have thrown TimeBankAlreadyUsed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TimeBankAlreadyUsed");
+      }
+    });
+
+    it("rejects a non-authority deal before the deal timeout", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+
+      const table = await game.getTable();
+      const occupied = getOccupiedSeats(table.occupiedSeats, table.maxPlayers);
+      const sbPos = findNextOccupied(occupied, table.dealerPosition, table.maxPlayers);
+      const bbPos = findNextOccupied(occupied, sbPos, table.maxPlayers);
+      const player = game.players[0].keypair;
+
+      try {
+        await program.methods
+          .dealCards()
+          .accounts({
+            caller: player.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            deckState: game.deckPDA!,
+            sbSeat: getSeatPDA(game.tablePDA, sbPos)[0],
+            bbSeat: getSeatPDA(game.tablePDA, bbPos)[0],
+          })
+          .signers([player])
+          .rpc();
+        expect.fail("Should have thrown DealNotTimedOut");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("DealNotTimedOut");
+      }
+    });
+
+    it("rejects mucking an unrevealed player before the reveal timeout", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const otherIdx = actionIdx === 0 ? 1 : 0;
+      await game.checkDown();
+
+      const caller = game.players[otherIdx].keypair;
+      try {
+        await program.methods
+          .timeoutReveal(game.players[actionIdx].seatIndex)
+          .accounts({
+            caller: caller.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            targetPlayer: game.players[actionIdx].seatPDA,
+          })
+          .signers([caller])
+          .rpc();
+        expect.fail("Should have thrown RevealNotTimedOut");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RevealNotTimedOut");
+      }
+    });
+
+    it("lets a player muck once another hand has been shown", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const otherIdx = actionIdx === 0 ? 1 : 0;
+      await game.checkDown();
+
+      const shower = game.players[actionIdx];
+      const mucker = game.players[otherIdx];
+      const muck = () =>
+        program.methods
+          .muck()
+          .accounts({
+            player: mucker.keypair.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            playerSeat: mucker.seatPDA,
+          })
+          .remainingAccounts([{ pubkey: shower.seatPDA, isSigner: false, isWritable: false }])
+          .signers([mucker.keypair])
+          .rpc();
+
+      // Nobody has shown yet
+      try {
+        await muck();
+        expect.fail("Should have thrown NoHandShown");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NoHandShown");
+      }
+
+      // Plaintext deal: the seat's stored cards are the cards themselves
+      const seat = await game.getSeat(actionIdx);
+      await program.methods
+        .revealCards(seat.holeCard1.toNumber(), seat.holeCard2.toNumber())
+        .accounts({
+          player: shower.keypair.publicKey,
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          playerSeat: shower.seatPDA,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([shower.keypair])
+        .rpc();
+
+      await muck();
+
+      const after = await game.getHandState();
+      expect(after.activeCount).to.equal(1);
+      expect(after.phase).to.deep.equal({ settled: {} });
+      const muckedSeat = await game.getSeat(otherIdx);
+      expect(muckedSeat.status).to.deep.equal({ folded: {} });
+      expect(muckedSeat.cardsRevealed).to.equal(false);
+
+      // The shown hand takes the pot
+      await game.showdown();
+      const winnerSeat = await game.getSeat(actionIdx);
+      expect(winnerSeat.chips.toNumber()).to.equal(MIN_BUY_IN + BIG_BLIND);
+    });
+
+    it("makes players show in turn on ordered-reveal tables", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ orderedReveal: true });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      await game.checkDown();
+
+      // Checked down: the first seat left of the dealer shows first
+      const dealerIdx = game.players.findIndex(p => p.seatIndex === hand.dealerPosition);
+      const firstIdx = dealerIdx === 0 ? 1 : 0;
+      const reveal = async (idx: number) => {
+        const player = game.players[idx];
+        const seat = await game.getSeat(idx);
+        return program.methods
+          .revealCards(seat.holeCard1.toNumber(), seat.holeCard2.toNumber())
+          .accounts({
+            player: player.keypair.publicKey,
+            table: game.tablePDA,
+            handState: game.handPDA!,
+            playerSeat: player.seatPDA,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .signers([player.keypair])
+          .rpc();
+      };
+
+      try {
+        await reveal(dealerIdx);
+        expect.fail("Should have thrown RevealOutOfOrder");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RevealOutOfOrder");
+      }
+
+      await reveal(firstIdx);
+      await reveal(dealerIdx);
+      expect((await game.getSeat(dealerIdx)).cardsRevealed).to.equal(true);
+    });
+
+    it("rejects closing a table before the inactivity timeout", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+
+      const caller = game.players[0].keypair;
+      try {
+        await program.methods
+          .closeInactiveTable()
+          .accounts({
+            caller: caller.publicKey,
+            table: game.tablePDA,
+            vault: game.vaultPDA,
+          })
+          .remainingAccounts([
+            { pubkey: game.players[0].seatPDA, isSigner: false, isWritable: true },
+            { pubkey: caller.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([caller])
+          .rpc();
+        expect.fail("Should have thrown TableNotInactive");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TableNotInactive");
+      }
+    });
+
+    it("rejects archiving an empty table before its lifetime is up", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+
+      const table = await program.account.table.fetch(game.tablePDA);
+      expect(table.currentPlayers).to.equal(0);
+      expect(table.createdSlot.toNumber()).to.be.greaterThan(0);
+
+      const caller = await createFundedKeypair();
+      try {
+        await program.methods
+          .archiveTable()
+          .accounts({
+            caller: caller.publicKey,
+            table: game.tablePDA,
+            authority: game.authority.publicKey,
+          })
+          .signers([caller])
+          .rpc();
+        expect.fail("Should have thrown TableNotExpired");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TableNotExpired");
+      }
+    });
+  });
+
+  describe("Bomb Pots", () => {
+    it("deals the flop with the full ante pot and no pre-flop action", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.addPlayer(2, MIN_BUY_IN);
+
+      const ante = BIG_BLIND * 5;
+      await game.startBombPot(ante);
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      expect(hand.bombPotAnte.toNumber()).to.equal(ante);
+      expect(hand.pot.toNumber()).to.equal(ante * 3);
+      expect(hand.phase).to.deep.equal({ preFlop: {} });
+      expect(hand.awaitingCommunityReveal).to.be.true;
+
+      for (let i = 0; i < 3; i++) {
+        const seat = await game.getSeat(i);
+        expect(seat.totalBetThisHand.toNumber()).to.equal(ante);
+        expect(seat.chips.toNumber()).to.equal(MIN_BUY_IN - ante);
+      }
+
+      // Nobody acts before the flop
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      try {
+        await game.playerAction(actionIdx, { check: {} });
+        expect.fail("Should have thrown AwaitingCommunityReveal");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AwaitingCommunityReveal");
+      }
+    });
+
+    it("rejects a bomb pot ante below the big blind", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+
+      try {
+        await game.startBombPot(BIG_BLIND - 1);
+        expect.fail("Should have thrown BetTooSmall");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BetTooSmall");
+      }
+    });
+  });
+
+  describe("Rotating Operator", () => {
+    const revealCommunity = (game: GameSetup, caller: Keypair) =>
+      program.methods
+        .revealCommunity(Buffer.from([0, 1, 2]))
+        .accounts({
+          caller: caller.publicKey,
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          deckState: game.deckPDA!,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([caller])
+        .rpc();
+
+    const dealerSeatMeta = (player: { seatPDA: PublicKey }) => [
+      { pubkey: player.seatPDA, isSigner: false, isWritable: false },
+    ];
+
+    it("rotates the operator with the button and lets them reveal", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ gameType: { texasHoldem: {} }, rotatingOperator: true, recordHistory: false });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+
+      // The dealer seat is required to pick the operator
+      try {
+        await game.startHand();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidRemainingAccounts");
+      }
+
+      // Hand 1: the first dealer operates
+      const firstDealer = await game.nextDealerSeat();
+      await game.startHand(dealerSeatMeta(firstDealer));
+      let hand = await game.getHandState();
+      expect(hand.handOperator.toBase58()).to.equal(firstDealer.keypair.publicKey.toBase58());
+
+      await game.dealCards();
+      hand = await game.getHandState();
+      const foldIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      await game.playerAction(foldIdx, { fold: {} });
+      await game.showdown();
+
+      // Hand 2: the button moved, so the operator moved with it
+      const secondDealer = await game.nextDealerSeat();
+      expect(secondDealer.seatIndex).to.not.equal(firstDealer.seatIndex);
+      await game.startHand(dealerSeatMeta(secondDealer));
+      hand = await game.getHandState();
+      expect(hand.handOperator.toBase58()).to.equal(secondDealer.keypair.publicKey.toBase58());
+
+      await game.dealCards();
+      hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const otherIdx = actionIdx === 0 ? 1 : 0;
+      await game.playerAction(actionIdx, { call: {} });
+      await game.playerAction(otherIdx, { check: {} });
+
+      // The plaintext flop was revealed inline when the round closed
+      hand = await game.getHandState();
+      expect(hand.awaitingCommunityReveal).to.equal(false);
+      expect(hand.communityRevealed).to.equal(3);
+
+      // A non-operator player still has to wait for the timeout
+      const nonOperator = game.players.find(p => p.seatIndex !== secondDealer.seatIndex)!;
+      try {
+        await revealCommunity(game, nonOperator.keypair);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TimeoutNotReached");
+      }
+
+      // The operator is authorized immediately, and finds the flop already out
+      try {
+        await revealCommunity(game, secondDealer.keypair);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommunityAlreadyRevealed");
+      }
+    });
+  });
+
+  describe("Betting Mechanics", () => {
+    it("enforces minimum raise", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const actionOn = hand.actionOn;
+      const actionIdx = game.players.findIndex(p => p.seatIndex === actionOn);
+
+      // Try to raise less than min raise (should fail)
+      try {
+        await game.playerAction(actionIdx, { raise: { amount: new anchor.BN(BIG_BLIND / 2) } });
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RaiseTooSmall");
+      }
+    });
+
+    it("caps raises per street on fixed-limit tables", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ bettingMode: { fixedLimit: {} }, maxRaisesPerStreet: 4 });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // Minimum raise from whoever is on the action
+      const minRaise = async () => {
+        const hand = await game.getHandState();
+        const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        const seat = await game.getSeat(actionIdx);
+        const toCall = hand.currentBet.toNumber() - seat.currentBet.toNumber();
+        const amount = new anchor.BN(toCall + hand.minRaise.toNumber());
+        await game.playerAction(actionIdx, { raise: { amount } });
+      };
+
+      // The big blind is the opening bet, so three raises reach the cap
+      expect((await game.getHandState()).raisesThisStreet).to.equal(1);
+      for (let i = 0; i < 3; i++) {
+        await minRaise();
+      }
+      expect((await game.getHandState()).raisesThisStreet).to.equal(4);
+
+      // A 4th raise pre-flop is blocked - call or fold only
+      try {
+        await minRaise();
+        expect.fail("Should have thrown RaiseCapReached");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RaiseCapReached");
+      }
+    });
+
+    it("only allows fixed-size bets and raises on fixed-limit tables", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ bettingMode: { fixedLimit: {} }, maxRaisesPerStreet: 4 });
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // Pre-flop raises are one small bet (the big blind) - no more
+      const hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const seat = await game.getSeat(actionIdx);
+      const toCall = hand.currentBet.toNumber() - seat.currentBet.toNumber();
+      try {
+        await game.playerAction(actionIdx, { raise: { amount: new anchor.BN(toCall + 2 * BIG_BLIND) } });
+        expect.fail("Should have thrown FixedLimitBetSize");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("FixedLimitBetSize");
+      }
+
+      await game.playerAction(actionIdx, { raise: { amount: new anchor.BN(toCall + BIG_BLIND) } });
+      expect((await game.getHandState()).currentBet.toNumber()).to.equal(2 * BIG_BLIND);
+    });
+
+    it("allows valid raise", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const actionOn = hand.actionOn;
+      const actionIdx = game.players.findIndex(p => p.seatIndex === actionOn);
+
+      const seatBefore = await game.getSeat(actionIdx);
+      const alreadyBet = seatBefore.currentBet.toNumber();
 
       // PreFlop: current_bet = BB, min_raise = BB
       // The raise amount is additional chips on top of what player already bet
@@ -710,6 +2020,125 @@ describe("Game Mechanics", () => {
       expect(handAfter.currentBet.toNumber()).to.equal(targetBet);
     });
 
+    it("only allows Bet when there is no bet to face", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // PreFlop the big blind is already a bet, so Bet is invalid
+      let hand = await game.getHandState();
+      let actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      let otherIdx = actionIdx === 0 ? 1 : 0;
+      try {
+        await game.playerAction(actionIdx, { bet: { amount: new anchor.BN(BIG_BLIND * 2) } });
+        expect.fail("Should have thrown InvalidAction");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAction");
+      }
+
+      await game.playerAction(actionIdx, { call: {} });
+      await game.playerAction(otherIdx, { check: {} });
+
+      // Flop opens unbet: the opening bet must be at least the big blind
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ flop: {} });
+      expect(hand.currentBet.toNumber()).to.equal(0);
+      actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      otherIdx = actionIdx === 0 ? 1 : 0;
+      try {
+        await game.playerAction(actionIdx, { bet: { amount: new anchor.BN(BIG_BLIND / 2) } });
+        expect.fail("Should have thrown BetTooSmall");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BetTooSmall");
+      }
+
+      await game.playerAction(actionIdx, { bet: { amount: new anchor.BN(BIG_BLIND) } });
+      hand = await game.getHandState();
+      expect(hand.currentBet.toNumber()).to.equal(BIG_BLIND);
+      expect(hand.minRaise.toNumber()).to.equal(BIG_BLIND);
+
+      // Facing the bet, Bet is invalid again but a raise is fine
+      try {
+        await game.playerAction(otherIdx, { bet: { amount: new anchor.BN(BIG_BLIND * 2) } });
+        expect.fail("Should have thrown InvalidAction");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidAction");
+      }
+      await game.playerAction(otherIdx, { raise: { amount: new anchor.BN(BIG_BLIND * 2) } });
+      hand = await game.getHandState();
+      expect(hand.currentBet.toNumber()).to.equal(BIG_BLIND * 2);
+    });
+
+    it("treats a legacy opening Raise as a Bet", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      let hand = await game.getHandState();
+      const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const otherIdx = actionIdx === 0 ? 1 : 0;
+      await game.playerAction(actionIdx, { call: {} });
+      await game.playerAction(otherIdx, { check: {} });
+
+      hand = await game.getHandState();
+      const flopIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      await game.playerAction(flopIdx, { raise: { amount: new anchor.BN(BIG_BLIND) } });
+
+      hand = await game.getHandState();
+      expect(hand.currentBet.toNumber()).to.equal(BIG_BLIND);
+      expect(hand.minRaise.toNumber()).to.equal(BIG_BLIND);
+    });
+
+    it("returns an uncalled turn bet as soon as it is folded to", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const actBoth = async (first: object, second: object) => {
+        const hand = await game.getHandState();
+        const idx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        await game.playerAction(idx, first);
+        await game.playerAction(idx === 0 ? 1 : 0, second);
+      };
+
+      await actBoth({ call: {} }, { check: {} }); // preflop
+      await actBoth({ check: {} }, { check: {} }); // flop
+
+      let hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ turn: {} });
+      const potBeforeBet = hand.pot.toNumber();
+      expect(potBeforeBet).to.equal(2 * BIG_BLIND);
+
+      // Turn: bet, then fold
+      const bettorIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const folderIdx = bettorIdx === 0 ? 1 : 0;
+      const turnBet = 3 * BIG_BLIND;
+      await game.playerAction(bettorIdx, { bet: { amount: new anchor.BN(turnBet) } });
+      await game.playerAction(folderIdx, { fold: {} });
+
+      // The uncalled bet is out of the pot before settlement
+      hand = await game.getHandState();
+      expect(hand.phase).to.deep.equal({ settled: {} });
+      expect(hand.pot.toNumber()).to.equal(potBeforeBet);
+      expect(hand.uncalledBet.toNumber()).to.equal(turnBet);
+
+      // Settling pays the pot and hands the bet back: bettor is up exactly the folder's blind
+      await game.showdown();
+      const bettor = await game.getSeat(bettorIdx);
+      const folder = await game.getSeat(folderIdx);
+      expect(bettor.chips.toNumber()).to.equal(MIN_BUY_IN + BIG_BLIND);
+      expect(folder.chips.toNumber()).to.equal(MIN_BUY_IN - BIG_BLIND);
+    });
+
     it("prevents acting out of turn", async () => {
       const game = new GameSetup();
       await game.createTable();
@@ -731,6 +2160,30 @@ describe("Game Mechanics", () => {
         expect(err.error.errorCode.code).to.equal("NotPlayersTurn");
       }
     });
+
+    it("logs where the action is when acting out of turn", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.addPlayer(0, MIN_BUY_IN);
+      await game.addPlayer(1, MIN_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      const hand = await game.getHandState();
+      const wrongPlayerIdx = game.players.findIndex(p => p.seatIndex !== hand.actionOn);
+      const wrongSeat = game.players[wrongPlayerIdx].seatIndex;
+
+      try {
+        await game.playerAction(wrongPlayerIdx, { check: {} });
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotPlayersTurn");
+        const logs: string[] = err.logs ?? [];
+        expect(logs.some(log => log.includes(
+          `Not seat ${wrongSeat}'s turn: action is on seat ${hand.actionOn} (PreFlop, current bet ${BIG_BLIND})`
+        ))).to.be.true;
+      }
+    });
   });
 
   describe("6-Player Game", () => {
@@ -765,6 +2218,57 @@ describe("Game Mechanics", () => {
       const finalHand = await game.getHandState();
       expect(finalHand.activeCount).to.equal(1);
     });
+
+    it("settles a full table well inside the default compute budget", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      for (let i = 0; i < 6; i++) {
+        await game.addPlayer(i, MIN_BUY_IN);
+      }
+      await game.startHand();
+      await game.dealCards();
+
+      for (let i = 0; i < 5; i++) {
+        const hand = await game.getHandState();
+        const actionIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+        await game.playerAction(actionIdx, { fold: {} });
+      }
+
+      // Showdown deserializes and writes back each of the 6 seats once
+      const simulation = await program.methods
+        .showdown()
+        .accounts({
+          caller: game.authority.publicKey,
+          table: game.tablePDA,
+          handState: game.handPDA!,
+          vault: game.vaultPDA,
+          handHistory: null,
+          tableStats: null,
+        })
+        .remainingAccounts(game.players.map(p => ({
+          pubkey: p.seatPDA,
+          isSigner: false,
+          isWritable: true,
+        })))
+        .signers([game.authority])
+        .simulate();
+
+      const consumedLog = simulation.raw.find(log => /consumed \d+ of \d+ compute units/.test(log));
+      expect(consumedLog, "compute usage should be logged").to.not.be.undefined;
+      const consumed = Number(consumedLog!.match(/consumed (\d+) of/)![1]);
+      expect(consumed).to.be.lessThan(200_000);
+
+      await game.showdown();
+
+      let totalChips = 0;
+      for (let i = 0; i < game.players.length; i++) {
+        const seat = await game.getSeat(i);
+        expect(seat.status).to.deep.equal({ sitting: {} });
+        expect(seat.totalBetThisHand.toNumber()).to.equal(0);
+        totalChips += seat.chips.toNumber();
+      }
+      expect(totalChips).to.equal(6 * MIN_BUY_IN);
+    });
   });
 
   describe("Chip Tracking", () => {
@@ -823,5 +2327,37 @@ describe("Game Mechanics", () => {
       expect(seat0.totalBetThisHand.toNumber()).to.be.oneOf([SMALL_BLIND, BIG_BLIND]);
       expect(seat1.totalBetThisHand.toNumber()).to.be.oneOf([SMALL_BLIND, BIG_BLIND]);
     });
+
+    it("returns chips over the cap to the winner's wallet on capped tables", async () => {
+      const game = new GameSetup();
+      await game.createTable();
+      await game.configure({ capStackPerHand: true });
+      await game.addPlayer(0, MAX_BUY_IN);
+      await game.addPlayer(1, MAX_BUY_IN);
+      await game.startHand();
+      await game.dealCards();
+
+      // Small blind folds: the big blind wins it and goes over max_buy_in
+      const hand = await game.getHandState();
+      const folderIdx = game.players.findIndex(p => p.seatIndex === hand.actionOn);
+      const winnerIdx = folderIdx === 0 ? 1 : 0;
+      await game.playerAction(folderIdx, { fold: {} });
+
+      // The excess has nowhere to go without the players' wallets
+      try {
+        await game.showdown();
+        expect.fail("Should have thrown StackCapWalletMissing");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("StackCapWalletMissing");
+      }
+
+      const winnerWallet = game.players[winnerIdx].keypair.publicKey;
+      const walletBefore = await provider.connection.getBalance(winnerWallet);
+      await game.showdown(null, game.players.map(p => p.keypair.publicKey));
+
+      const winnerSeat = await game.getSeat(winnerIdx);
+      expect(winnerSeat.chips.toNumber()).to.equal(MAX_BUY_IN);
+      expect(await provider.connection.getBalance(winnerWallet)).to.equal(walletBefore + SMALL_BLIND);
+    });
   });
 });
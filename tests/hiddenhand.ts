@@ -122,6 +122,44 @@ describe("hiddenhand", () => {
       expect(table.occupiedSeats).to.equal(0);
     });
 
+    it("emits TableCreated for lobby indexers", async () => {
+      const tableId = generateTableId();
+      const [tablePDA] = getTablePDA(tableId);
+      const [vaultPDA] = getVaultPDA(tablePDA);
+
+      let created: any = null;
+      const listener = program.addEventListener("tableCreated", (event) => {
+        created = event;
+      });
+
+      await program.methods
+        .createTable(
+          tableId,
+          new anchor.BN(SMALL_BLIND),
+          new anchor.BN(BIG_BLIND),
+          new anchor.BN(MIN_BUY_IN),
+          new anchor.BN(MAX_BUY_IN),
+          4
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          table: tablePDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(created).to.not.be.null;
+      expect(created.tableId).to.deep.equal(tableId);
+      expect(created.authority.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+      expect(created.smallBlind.toNumber()).to.equal(SMALL_BLIND);
+      expect(created.bigBlind.toNumber()).to.equal(BIG_BLIND);
+      expect(created.maxPlayers).to.equal(4);
+    });
+
     it("fails with max_players below minimum (2)", async () => {
       const tableId = generateTableId();
       const [tablePDA] = getTablePDA(tableId);
@@ -202,7 +240,7 @@ describe("hiddenhand", () => {
           .rpc();
         expect.fail("Should have thrown error");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidBuyIn");
+        expect(err.error.errorCode.code).to.equal("BigBlindBelowSmallBlind");
       }
     });
 
@@ -230,7 +268,35 @@ describe("hiddenhand", () => {
           .rpc();
         expect.fail("Should have thrown error");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidBuyIn");
+        expect(err.error.errorCode.code).to.equal("MinBuyInAboveMax");
+      }
+    });
+
+    it("fails with max_buy_in below the big blind", async () => {
+      const tableId = generateTableId();
+      const [tablePDA] = getTablePDA(tableId);
+      const [vaultPDA] = getVaultPDA(tablePDA);
+
+      try {
+        await program.methods
+          .createTable(
+            tableId,
+            new anchor.BN(SMALL_BLIND),
+            new anchor.BN(BIG_BLIND),
+            new anchor.BN(SMALL_BLIND),
+            new anchor.BN(SMALL_BLIND), // Can't even cover the big blind
+            MAX_PLAYERS
+          )
+          .accounts({
+            authority: provider.wallet.publicKey,
+            table: tablePDA,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MaxBuyInBelowBigBlind");
       }
     });
 
@@ -301,7 +367,7 @@ describe("hiddenhand", () => {
       const vaultBalanceBefore = await provider.connection.getBalance(vaultPDA);
 
       await program.methods
-        .joinTable(seatIndex, new anchor.BN(buyIn))
+        .joinTable(seatIndex, new anchor.BN(buyIn), [])
         .accounts({
           player: player.publicKey,
           table: tablePDA,
@@ -326,6 +392,37 @@ describe("hiddenhand", () => {
       expect(vaultBalanceAfter - vaultBalanceBefore).to.equal(buyIn);
     });
 
+    it("emits PlayerJoined with the seat and buy-in", async () => {
+      const player = await createFundedKeypair();
+      const [seatPDA] = getSeatPDA(tablePDA, 2);
+
+      let joined: any = null;
+      const listener = program.addEventListener("playerJoined", (event) => {
+        joined = event;
+      });
+
+      await program.methods
+        .joinTable(2, new anchor.BN(MIN_BUY_IN), [])
+        .accounts({
+          player: player.publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(joined).to.not.be.null;
+      expect(joined.tableId).to.deep.equal(tableId);
+      expect(joined.player.toBase58()).to.equal(player.publicKey.toBase58());
+      expect(joined.seatIndex).to.equal(2);
+      expect(joined.buyIn.toNumber()).to.equal(MIN_BUY_IN);
+    });
+
     it("allows multiple players to join different seats", async () => {
       const player1 = await createFundedKeypair();
       const player2 = await createFundedKeypair();
@@ -334,7 +431,7 @@ describe("hiddenhand", () => {
       const [seat3PDA] = getSeatPDA(tablePDA, 3);
 
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -346,7 +443,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(3, new anchor.BN(MAX_BUY_IN))
+        .joinTable(3, new anchor.BN(MAX_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -368,7 +465,7 @@ describe("hiddenhand", () => {
 
       try {
         await program.methods
-          .joinTable(0, new anchor.BN(MIN_BUY_IN - 1))
+          .joinTable(0, new anchor.BN(MIN_BUY_IN - 1), [])
           .accounts({
             player: player.publicKey,
             table: tablePDA,
@@ -390,7 +487,7 @@ describe("hiddenhand", () => {
 
       try {
         await program.methods
-          .joinTable(0, new anchor.BN(MAX_BUY_IN + 1))
+          .joinTable(0, new anchor.BN(MAX_BUY_IN + 1), [])
           .accounts({
             player: player.publicKey,
             table: tablePDA,
@@ -412,7 +509,7 @@ describe("hiddenhand", () => {
 
       try {
         await program.methods
-          .joinTable(MAX_PLAYERS, new anchor.BN(MIN_BUY_IN))
+          .joinTable(MAX_PLAYERS, new anchor.BN(MIN_BUY_IN), [])
           .accounts({
             player: player.publicKey,
             table: tablePDA,
@@ -435,7 +532,7 @@ describe("hiddenhand", () => {
 
       // First player joins seat 0
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -449,7 +546,7 @@ describe("hiddenhand", () => {
       // Second player tries same seat - will fail at PDA init
       try {
         await program.methods
-          .joinTable(0, new anchor.BN(MIN_BUY_IN))
+          .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
           .accounts({
             player: player2.publicKey,
             table: tablePDA,
@@ -465,6 +562,71 @@ describe("hiddenhand", () => {
         expect(err.toString()).to.include("already in use");
       }
     });
+
+    // join_table_auto: the client derives the seat PDA with the program's rule
+    const lowestFreeSeat = async (): Promise<number> => {
+      const table = await program.account.table.fetch(tablePDA);
+      for (let i = 0; i < table.maxPlayers; i++) {
+        if ((table.occupiedSeats & (1 << i)) === 0) return i;
+      }
+      return 255;
+    };
+
+    const joinAuto = async (player: Keypair, seatIndex: number) => {
+      const [seatPDA] = getSeatPDA(tablePDA, seatIndex);
+      await program.methods
+        .joinTableAuto(new anchor.BN(MIN_BUY_IN), [])
+        .accounts({
+          player: player.publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+    };
+
+    it("join_table_auto seats back-to-back joins at distinct seats", async () => {
+      const players = [await createFundedKeypair(), await createFundedKeypair(), await createFundedKeypair()];
+
+      for (const player of players) {
+        await joinAuto(player, await lowestFreeSeat());
+      }
+
+      const table = await program.account.table.fetch(tablePDA);
+      expect(table.currentPlayers).to.equal(3);
+      expect(table.occupiedSeats).to.equal(0b111);
+
+      const seated = await Promise.all(
+        [0, 1, 2].map(i => program.account.playerSeat.fetch(getSeatPDA(tablePDA, i)[0]))
+      );
+      expect(seated.map(s => s.player.toString())).to.deep.equal(players.map(p => p.publicKey.toString()));
+    });
+
+    it("join_table_auto rejects a stale seat pick and succeeds on retry", async () => {
+      const player1 = await createFundedKeypair();
+      const player2 = await createFundedKeypair();
+
+      // Both clients read the table before either join lands
+      const seen = await lowestFreeSeat();
+      await joinAuto(player1, seen);
+
+      try {
+        await joinAuto(player2, seen);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        // The program's pick moved on to seat 1, so the seat 0 PDA fails its seeds check
+        expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+      }
+
+      // Refetch and retry - lands on the next seat
+      await joinAuto(player2, await lowestFreeSeat());
+      const table = await program.account.table.fetch(tablePDA);
+      expect(table.occupiedSeats).to.equal(0b11);
+      const seat1 = await program.account.playerSeat.fetch(getSeatPDA(tablePDA, 1)[0]);
+      expect(seat1.player.toString()).to.equal(player2.publicKey.toString());
+    });
   });
 
   describe("leave_table", () => {
@@ -502,7 +664,7 @@ describe("hiddenhand", () => {
 
       // Join table
       await program.methods
-        .joinTable(0, new anchor.BN(buyIn))
+        .joinTable(0, new anchor.BN(buyIn), [])
         .accounts({
           player: player.publicKey,
           table: tablePDA,
@@ -513,6 +675,9 @@ describe("hiddenhand", () => {
         .signers([player])
         .rpc();
 
+      const seated = await program.account.table.fetch(tablePDA);
+      expect(seated.seatOwners[0].toBase58()).to.equal(player.publicKey.toBase58());
+
       const playerBalanceBefore = await provider.connection.getBalance(player.publicKey);
 
       // Leave table
@@ -523,6 +688,7 @@ describe("hiddenhand", () => {
           table: tablePDA,
           playerSeat: seatPDA,
           vault: vaultPDA,
+          handState: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([player])
@@ -533,6 +699,7 @@ describe("hiddenhand", () => {
 
       expect(table.currentPlayers).to.equal(0);
       expect(table.occupiedSeats).to.equal(0);
+      expect(table.seatOwners[0].toBase58()).to.equal(PublicKey.default.toBase58());
 
       // Player should have received chips back (minus tx fee, plus seat rent)
       // The seat account is closed and rent returned
@@ -543,6 +710,50 @@ describe("hiddenhand", () => {
       expect(seatAccount).to.be.null;
     });
 
+    it("emits PlayerLeft with the chips returned", async () => {
+      const player = await createFundedKeypair();
+      const [seatPDA] = getSeatPDA(tablePDA, 1);
+
+      await program.methods
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
+        .accounts({
+          player: player.publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      let left: any = null;
+      const listener = program.addEventListener("playerLeft", (event) => {
+        left = event;
+      });
+
+      await program.methods
+        .leaveTable()
+        .accounts({
+          player: player.publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          handState: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(left).to.not.be.null;
+      expect(left.tableId).to.deep.equal(tableId);
+      expect(left.player.toBase58()).to.equal(player.publicKey.toBase58());
+      expect(left.seatIndex).to.equal(1);
+      expect(left.chipsReturned.toNumber()).to.equal(MIN_BUY_IN);
+    });
+
     it("fails when trying to leave with wrong player", async () => {
       const player1 = await createFundedKeypair();
       const player2 = await createFundedKeypair();
@@ -550,7 +761,7 @@ describe("hiddenhand", () => {
 
       // Player 1 joins
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -570,6 +781,7 @@ describe("hiddenhand", () => {
             table: tablePDA,
             playerSeat: seatPDA,
             vault: vaultPDA,
+            handState: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([player2])
@@ -581,6 +793,252 @@ describe("hiddenhand", () => {
     });
   });
 
+  describe("kick_player", () => {
+    let tableId: number[];
+    let tablePDA: PublicKey;
+    let vaultPDA: PublicKey;
+    let player1: Keypair;
+    let player2: Keypair;
+
+    beforeEach(async () => {
+      player1 = await createFundedKeypair();
+      player2 = await createFundedKeypair();
+
+      tableId = generateTableId();
+      [tablePDA] = getTablePDA(tableId);
+      [vaultPDA] = getVaultPDA(tablePDA);
+
+      await program.methods
+        .createTable(
+          tableId,
+          new anchor.BN(SMALL_BLIND),
+          new anchor.BN(BIG_BLIND),
+          new anchor.BN(MIN_BUY_IN),
+          new anchor.BN(MAX_BUY_IN),
+          MAX_PLAYERS
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          table: tablePDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      for (const [seatIndex, player] of [[0, player1], [1, player2]] as [number, Keypair][]) {
+        const [seatPDA] = getSeatPDA(tablePDA, seatIndex);
+        await program.methods
+          .joinTable(seatIndex, new anchor.BN(MIN_BUY_IN), [])
+          .accounts({
+            player: player.publicKey,
+            table: tablePDA,
+            playerSeat: seatPDA,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+      }
+    });
+
+    it("kicks a player and returns their chips", async () => {
+      const [seatPDA] = getSeatPDA(tablePDA, 1);
+      const balanceBefore = await provider.connection.getBalance(player2.publicKey);
+
+      await program.methods
+        .kickPlayer(1)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          playerWallet: player2.publicKey,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const balanceAfter = await provider.connection.getBalance(player2.publicKey);
+      const table = await program.account.table.fetch(tablePDA);
+
+      // Chips plus seat rent come back to the kicked player
+      expect(balanceAfter - balanceBefore).to.be.greaterThanOrEqual(MIN_BUY_IN);
+      expect(table.currentPlayers).to.equal(1);
+      expect(table.occupiedSeats).to.equal(0b1);
+
+      const seatAccount = await provider.connection.getAccountInfo(seatPDA);
+      expect(seatAccount).to.be.null;
+    });
+
+    it("fails to kick mid-hand", async () => {
+      const [seatPDA] = getSeatPDA(tablePDA, 1);
+      const [handPDA] = getHandPDA(tablePDA, 1);
+      const [deckPDA] = getDeckPDA(tablePDA, 1);
+
+      await program.methods
+        .startHand()
+        .accounts({
+          caller: provider.wallet.publicKey,
+          table: tablePDA,
+          handState: handPDA,
+          deckState: deckPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .kickPlayer(1)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            table: tablePDA,
+            playerSeat: seatPDA,
+            playerWallet: player2.publicKey,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("HandInProgress");
+      }
+    });
+  });
+
+  describe("join_waitlist", () => {
+    let tableId: number[];
+    let tablePDA: PublicKey;
+    let vaultPDA: PublicKey;
+    let seated: Keypair[];
+
+    const joinWaitlist = (player: Keypair) =>
+      program.methods
+        .joinWaitlist()
+        .accounts({
+          player: player.publicKey,
+          table: tablePDA,
+        })
+        .signers([player])
+        .rpc();
+
+    beforeEach(async () => {
+      tableId = generateTableId();
+      [tablePDA] = getTablePDA(tableId);
+      [vaultPDA] = getVaultPDA(tablePDA);
+
+      // Two-seat table, filled up
+      await program.methods
+        .createTable(
+          tableId,
+          new anchor.BN(SMALL_BLIND),
+          new anchor.BN(BIG_BLIND),
+          new anchor.BN(MIN_BUY_IN),
+          new anchor.BN(MAX_BUY_IN),
+          2
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          table: tablePDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      seated = [await createFundedKeypair(), await createFundedKeypair()];
+      for (const [seatIndex, player] of seated.entries()) {
+        const [seatPDA] = getSeatPDA(tablePDA, seatIndex);
+        await program.methods
+          .joinTable(seatIndex, new anchor.BN(MIN_BUY_IN), [])
+          .accounts({
+            player: player.publicKey,
+            table: tablePDA,
+            playerSeat: seatPDA,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+      }
+    });
+
+    it("enqueues players in order and rejects duplicates", async () => {
+      const first = await createFundedKeypair();
+      const second = await createFundedKeypair();
+
+      await joinWaitlist(first);
+      await joinWaitlist(second);
+
+      const table = await program.account.table.fetch(tablePDA);
+      expect(table.waitlist.map((p) => p.toBase58())).to.deep.equal([
+        first.publicKey.toBase58(),
+        second.publicKey.toBase58(),
+      ]);
+
+      try {
+        await joinWaitlist(first);
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyOnWaitlist");
+      }
+    });
+
+    it("pops the front of the waitlist when a seat frees", async () => {
+      const waiting = await createFundedKeypair();
+      await joinWaitlist(waiting);
+
+      let seatAvailable: any = null;
+      const listener = program.addEventListener("seatAvailable", (event) => {
+        seatAvailable = event;
+      });
+
+      const [seatPDA] = getSeatPDA(tablePDA, 1);
+      await program.methods
+        .leaveTable()
+        .accounts({
+          player: seated[1].publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          handState: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([seated[1]])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      const table = await program.account.table.fetch(tablePDA);
+      expect(table.waitlist).to.have.length(0);
+      expect(seatAvailable).to.not.be.null;
+      expect(seatAvailable.player.toBase58()).to.equal(waiting.publicKey.toBase58());
+      expect(seatAvailable.seatIndex).to.equal(1);
+    });
+
+    it("rejects the waitlist while the table has open seats", async () => {
+      // Free a seat, then try to queue instead of joining
+      const [seatPDA] = getSeatPDA(tablePDA, 1);
+      await program.methods
+        .leaveTable()
+        .accounts({
+          player: seated[1].publicKey,
+          table: tablePDA,
+          playerSeat: seatPDA,
+          vault: vaultPDA,
+          handState: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([seated[1]])
+        .rpc();
+
+      try {
+        await joinWaitlist(await createFundedKeypair());
+        expect.fail("Should have thrown error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TableHasOpenSeats");
+      }
+    });
+  });
+
   describe("start_hand", () => {
     let tableId: number[];
     let tablePDA: PublicKey;
@@ -624,7 +1082,7 @@ describe("hiddenhand", () => {
 
       // Both players join
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -636,7 +1094,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -681,7 +1139,7 @@ describe("hiddenhand", () => {
 
       // Only 1 player joins
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -718,7 +1176,7 @@ describe("hiddenhand", () => {
       const [seat1PDA] = getSeatPDA(tablePDA, 1);
 
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -730,7 +1188,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -808,7 +1266,7 @@ describe("hiddenhand", () => {
 
       // Players join
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -820,7 +1278,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -956,7 +1414,7 @@ describe("hiddenhand", () => {
 
       // Players join
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -968,7 +1426,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -1096,6 +1554,26 @@ describe("hiddenhand", () => {
       }
     });
 
+    it("sit_in fails for a player who was never sat out", async () => {
+      const seat = await program.account.playerSeat.fetch(seat0PDA);
+      expect(seat.consecutiveTimeouts).to.equal(0);
+
+      try {
+        await program.methods
+          .sitIn()
+          .accounts({
+            player: player1.publicKey,
+            table: tablePDA,
+            playerSeat: seat0PDA,
+          })
+          .signers([player1])
+          .rpc();
+        expect.fail("Should have thrown PlayerNotAway error");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PlayerNotAway");
+      }
+    });
+
     it("verifies last_action_time is set after deal_cards", async () => {
       const handState = await program.account.handState.fetch(handPDA);
 
@@ -1176,7 +1654,7 @@ describe("hiddenhand", () => {
 
       // Players join
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player1.publicKey,
           table: tablePDA,
@@ -1188,7 +1666,7 @@ describe("hiddenhand", () => {
         .rpc();
 
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -1264,6 +1742,8 @@ describe("hiddenhand", () => {
             table: tablePDA,
             handState: handPDA,
             vault: vaultPDA,
+            handHistory: null,
+            tableStats: null,
           })
           .remainingAccounts([
             { pubkey: seat0PDA, isSigner: false, isWritable: true },
@@ -1271,9 +1751,9 @@ describe("hiddenhand", () => {
           ])
           .signers([nonAuthority])
           .rpc();
-        expect.fail("Should have thrown UnauthorizedAuthority error");
+        expect.fail("Should have thrown ShowdownNotTimedOut error");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("UnauthorizedAuthority");
+        expect(err.error.errorCode.code).to.equal("ShowdownNotTimedOut");
       }
     });
 
@@ -1304,6 +1784,8 @@ describe("hiddenhand", () => {
           table: tablePDA,
           handState: handPDA,
           vault: vaultPDA,
+          handHistory: null,
+          tableStats: null,
         })
         .remainingAccounts([
           { pubkey: seat0PDA, isSigner: false, isWritable: true },
@@ -1416,7 +1898,7 @@ describe("hiddenhand", () => {
       // Player 1 (authority) joins at seat 0
       const [seat0PDA] = getSeatPDA(tablePDA, 0);
       await program.methods
-        .joinTable(0, new anchor.BN(MIN_BUY_IN))
+        .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: authority.publicKey,
           table: tablePDA,
@@ -1430,7 +1912,7 @@ describe("hiddenhand", () => {
       // Player 2 joins at seat 1
       const [seat1PDA] = getSeatPDA(tablePDA, 1);
       await program.methods
-        .joinTable(1, new anchor.BN(MIN_BUY_IN))
+        .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
         .accounts({
           player: player2.publicKey,
           table: tablePDA,
@@ -1526,5 +2008,240 @@ describe("hiddenhand", () => {
       console.log("- All community cards are Inco FHE encrypted handles");
       console.log("- VRF seed was NEVER stored on-chain (only used in callback memory)");
     });
+
+    // Table with `playerCount` seated players and hand #1 started, ready to shuffle
+    async function startHandForShuffle(playerCount: number) {
+      const players = Array.from({ length: playerCount }, () => Keypair.generate());
+      for (const player of players) {
+        await fundKeypair(player, 2 * LAMPORTS_PER_SOL);
+      }
+      const authority = players[0];
+
+      const tableId = generateTableId();
+      const [tablePDA] = getTablePDA(tableId);
+      const [vaultPDA] = getVaultPDA(tablePDA);
+
+      await program.methods
+        .createTable(
+          tableId,
+          new anchor.BN(SMALL_BLIND),
+          new anchor.BN(BIG_BLIND),
+          new anchor.BN(MIN_BUY_IN),
+          new anchor.BN(MAX_BUY_IN),
+          MAX_PLAYERS
+        )
+        .accounts({
+          authority: authority.publicKey,
+          table: tablePDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const seatPDAs: PublicKey[] = [];
+      for (const [seatIndex, player] of players.entries()) {
+        const [seatPDA] = getSeatPDA(tablePDA, seatIndex);
+        seatPDAs.push(seatPDA);
+        await program.methods
+          .joinTable(seatIndex, new anchor.BN(MIN_BUY_IN), [])
+          .accounts({
+            player: player.publicKey,
+            table: tablePDA,
+            playerSeat: seatPDA,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      const [handPDA] = getHandPDA(tablePDA, 1);
+      const [deckPDA] = getDeckPDA(tablePDA, 1);
+
+      await program.methods
+        .startHand()
+        .accounts({
+          caller: authority.publicKey,
+          table: tablePDA,
+          handState: handPDA,
+          deckState: deckPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const requestShuffle = (accounts: PublicKey[]) =>
+        program.methods
+          .requestShuffle()
+          .accounts({
+            authority: authority.publicKey,
+            table: tablePDA,
+            handState: handPDA,
+            deckState: deckPDA,
+            oracleQueue: DEFAULT_QUEUE,
+            incoProgram: INCO_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+          .signers([authority])
+          .rpc();
+
+      return { players, seatPDAs, deckPDA, requestShuffle };
+    }
+
+    it("rejects request_shuffle with fewer than two dealable seats", async () => {
+      const { players, seatPDAs, deckPDA, requestShuffle } = await startHandForShuffle(2);
+
+      // One real seat plus a stray account: caught before the VRF request is sent
+      try {
+        await requestShuffle([seatPDAs[0], players[1].publicKey]);
+        expect.fail("Should have thrown NotEnoughPlayers");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotEnoughPlayers");
+      }
+
+      const deckState = await program.account.deckState.fetch(deckPDA);
+      expect(deckState.isShuffled).to.be.false;
+    });
+
+    it("rejects request_shuffle missing a seat in the hand", async () => {
+      const { seatPDAs, deckPDA, requestShuffle } = await startHandForShuffle(3);
+
+      // The callback would have to abort the hand - fail now so it can be resent
+      try {
+        await requestShuffle(seatPDAs.slice(0, 2));
+        expect.fail("Should have thrown MissingSeatAccounts");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MissingSeatAccounts");
+      }
+
+      const deckState = await program.account.deckState.fetch(deckPDA);
+      expect(deckState.isShuffled).to.be.false;
+    });
+
+    it("grants hole card allowances for three players in one call", async () => {
+      const authority = Keypair.generate();
+      const others = [Keypair.generate(), Keypair.generate()];
+      await fundKeypair(authority, 2 * LAMPORTS_PER_SOL);
+      for (const player of others) {
+        await fundKeypair(player, 2 * LAMPORTS_PER_SOL);
+      }
+      const players = [authority, ...others];
+
+      const tableId = generateTableId();
+      const [tablePDA] = getTablePDA(tableId);
+      const [vaultPDA] = getVaultPDA(tablePDA);
+
+      await program.methods
+        .createTable(
+          tableId,
+          new anchor.BN(SMALL_BLIND),
+          new anchor.BN(BIG_BLIND),
+          new anchor.BN(MIN_BUY_IN),
+          new anchor.BN(MAX_BUY_IN),
+          MAX_PLAYERS
+        )
+        .accounts({
+          authority: authority.publicKey,
+          table: tablePDA,
+          vault: vaultPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const seatPDAs: PublicKey[] = [];
+      for (const [seatIndex, player] of players.entries()) {
+        const [seatPDA] = getSeatPDA(tablePDA, seatIndex);
+        seatPDAs.push(seatPDA);
+        await program.methods
+          .joinTable(seatIndex, new anchor.BN(MIN_BUY_IN), [])
+          .accounts({
+            player: player.publicKey,
+            table: tablePDA,
+            playerSeat: seatPDA,
+            vault: vaultPDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      const [handPDA] = getHandPDA(tablePDA, 1);
+      const [deckPDA] = getDeckPDA(tablePDA, 1);
+
+      await program.methods
+        .startHand()
+        .accounts({
+          caller: authority.publicKey,
+          table: tablePDA,
+          handState: handPDA,
+          deckState: deckPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .requestShuffle()
+        .accounts({
+          authority: authority.publicKey,
+          table: tablePDA,
+          handState: handPDA,
+          deckState: deckPDA,
+          oracleQueue: DEFAULT_QUEUE,
+          incoProgram: INCO_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          seatPDAs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([authority])
+        .rpc();
+
+      expect(await waitForShuffle(deckPDA, 60000, 2000)).to.be.true;
+
+      // [seat, allowance1, allowance2, player] for every player
+      const allowancePDA = (handle: anchor.BN, player: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [handle.toArrayLike(Buffer, "le", 16), player.toBuffer()],
+          INCO_PROGRAM_ID
+        )[0];
+
+      const remainingAccounts = [];
+      const allowances: PublicKey[] = [];
+      for (const [seatIndex, player] of players.entries()) {
+        const seat = await program.account.playerSeat.fetch(seatPDAs[seatIndex]);
+        const allowance1 = allowancePDA(seat.holeCard1, player.publicKey);
+        const allowance2 = allowancePDA(seat.holeCard2, player.publicKey);
+        allowances.push(allowance1, allowance2);
+        remainingAccounts.push(
+          { pubkey: seatPDAs[seatIndex], isSigner: false, isWritable: false },
+          { pubkey: allowance1, isSigner: false, isWritable: true },
+          { pubkey: allowance2, isSigner: false, isWritable: true },
+          { pubkey: player.publicKey, isSigner: false, isWritable: false }
+        );
+      }
+
+      await program.methods
+        .grantAllAllowances()
+        .accounts({
+          authority: authority.publicKey,
+          table: tablePDA,
+          incoProgram: INCO_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([authority])
+        .rpc();
+
+      // Inco created an allowance account for each of the six hole cards
+      for (const allowance of allowances) {
+        const info = await provider.connection.getAccountInfo(allowance);
+        expect(info).to.not.be.null;
+        expect(info!.owner.toBase58()).to.equal(INCO_PROGRAM_ID.toBase58());
+      }
+    });
   });
 });
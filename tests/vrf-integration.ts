@@ -170,7 +170,7 @@ async function main() {
     const [seat1PDA] = getSeatPDA(tablePDA, 1);
 
     await program.methods
-      .joinTable(0, new anchor.BN(MIN_BUY_IN))
+      .joinTable(0, new anchor.BN(MIN_BUY_IN), [])
       .accounts({
         player: authority.publicKey,
         table: tablePDA,
@@ -183,7 +183,7 @@ async function main() {
     console.log("   Player 1 joined seat 0");
 
     await program.methods
-      .joinTable(1, new anchor.BN(MIN_BUY_IN))
+      .joinTable(1, new anchor.BN(MIN_BUY_IN), [])
       .accounts({
         player: player2.publicKey,
         table: tablePDA,